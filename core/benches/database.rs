@@ -0,0 +1,129 @@
+//! Compares opening a new SQLite connection for every query, which is what
+//! the local service used to do, with the shared connection pool.
+//!
+//! Run with `cargo bench -p core_done --bench database`.
+
+use std::path::PathBuf;
+
+use core_done::{
+	models::list::List, service::Service, services::local::database::Database,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use diesel::{
+	sql_query, sql_types::BigInt, Connection, QueryableByName, RunQueryDsl,
+	SqliteConnection,
+};
+use tokio::runtime::Runtime;
+
+const TASK_COUNT: i64 = 20_000;
+
+#[derive(QueryableByName)]
+struct Count {
+	#[diesel(sql_type = BigInt)]
+	count: i64,
+}
+
+fn database_path() -> PathBuf {
+	std::env::temp_dir().join(format!("done-bench-{}.db", std::process::id()))
+}
+
+/// Removes the database along with the files SQLite keeps next to it in WAL
+/// mode.
+fn remove_database() {
+	let path = database_path().display().to_string();
+	for suffix in ["", "-wal", "-shm"] {
+		let _ = std::fs::remove_file(format!("{path}{suffix}"));
+	}
+}
+
+/// Creates a fresh database with a single list holding `TASK_COUNT` tasks and
+/// returns the id of the list.
+fn seed(runtime: &Runtime) -> String {
+	remove_database();
+	Database::set_database_path(Some(database_path()));
+	Database::ensure_migrations_up_to_date().unwrap();
+
+	let list = runtime
+		.block_on(
+			Service::Computer
+				.get_service()
+				.create_list(List::new("Benchmark", Service::Computer)),
+		)
+		.unwrap();
+
+	sql_query(format!(
+		"WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < {TASK_COUNT})
+		INSERT INTO tasks (id_task, parent, title, favorite, today, status, priority,
+			created_date_time, last_modified_date_time, position)
+		SELECT 'task-' || i, ?, 'Task ' || i, i % 7 = 0, i % 11 = 0, i % 2, i % 3,
+			datetime('now'), datetime('now'), i
+		FROM n"
+	))
+	.bind::<diesel::sql_types::Text, _>(&list.id)
+	.execute(&mut Database::establish_connection().unwrap())
+	.unwrap();
+
+	list.id
+}
+
+fn connection(c: &mut Criterion) {
+	let runtime = Runtime::new().unwrap();
+	seed(&runtime);
+	let url = database_path().display().to_string();
+	let count = sql_query("SELECT COUNT(*) AS count FROM tasks");
+
+	let mut group = c.benchmark_group("connection");
+	group.bench_function("establish per query", |b| {
+		b.iter(|| {
+			let mut connection = SqliteConnection::establish(&url).unwrap();
+			count
+				.clone()
+				.get_result::<Count>(&mut connection)
+				.unwrap()
+				.count
+		})
+	});
+	group.bench_function("pooled", |b| {
+		b.iter(|| {
+			let mut connection = Database::establish_connection().unwrap();
+			count
+				.clone()
+				.get_result::<Count>(&mut connection)
+				.unwrap()
+				.count
+		})
+	});
+	group.finish();
+}
+
+fn service(c: &mut Criterion) {
+	let runtime = Runtime::new().unwrap();
+	let list_id = seed(&runtime);
+	let mut service = Service::Computer.get_service();
+
+	let mut group = c.benchmark_group("service");
+	group.sample_size(10);
+	group.bench_function("read_tasks_from_list", |b| {
+		b.iter(|| {
+			runtime
+				.block_on(service.read_tasks_from_list(list_id.clone()))
+				.unwrap()
+		})
+	});
+	group.bench_function("read_task", |b| {
+		let mut i = 0;
+		b.iter(|| {
+			i = i % TASK_COUNT + 1;
+			runtime
+				.block_on(service.read_task(list_id.clone(), format!("task-{i}")))
+				.unwrap()
+		})
+	});
+	group.finish();
+
+	Database::close();
+	remove_database();
+}
+
+criterion_group!(benches, connection, service);
+criterion_main!(benches);
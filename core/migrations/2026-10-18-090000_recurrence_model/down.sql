@@ -0,0 +1,21 @@
+UPDATE tasks
+SET recurrence = substr(recurrence, instr(recurrence, '"days_of_week":['))
+WHERE recurrence IS NOT NULL;
+
+UPDATE tasks
+SET recurrence = substr(recurrence, 1, instr(recurrence, ']'))
+WHERE recurrence IS NOT NULL;
+
+UPDATE tasks
+SET recurrence = CASE
+    WHEN recurrence IS NULL THEN ''
+    ELSE rtrim(
+        CASE WHEN instr(recurrence, '"Monday"') > 0 THEN 'Mon, ' ELSE '' END ||
+        CASE WHEN instr(recurrence, '"Tuesday"') > 0 THEN 'Tue, ' ELSE '' END ||
+        CASE WHEN instr(recurrence, '"Wednesday"') > 0 THEN 'Wed, ' ELSE '' END ||
+        CASE WHEN instr(recurrence, '"Thursday"') > 0 THEN 'Thu, ' ELSE '' END ||
+        CASE WHEN instr(recurrence, '"Friday"') > 0 THEN 'Fri, ' ELSE '' END ||
+        CASE WHEN instr(recurrence, '"Saturday"') > 0 THEN 'Sat, ' ELSE '' END ||
+        CASE WHEN instr(recurrence, '"Sunday"') > 0 THEN 'Sun, ' ELSE '' END,
+    ', ')
+END;
@@ -0,0 +1,13 @@
+UPDATE tasks
+SET recurrence = CASE
+    WHEN recurrence IS NULL OR trim(recurrence) = '' THEN NULL
+    ELSE '{"frequency":"Weekly","interval":1,"days_of_week":[' || rtrim(
+        CASE WHEN instr(recurrence, 'Mon') > 0 THEN '"Monday",' ELSE '' END ||
+        CASE WHEN instr(recurrence, 'Tue') > 0 THEN '"Tuesday",' ELSE '' END ||
+        CASE WHEN instr(recurrence, 'Wed') > 0 THEN '"Wednesday",' ELSE '' END ||
+        CASE WHEN instr(recurrence, 'Thu') > 0 THEN '"Thursday",' ELSE '' END ||
+        CASE WHEN instr(recurrence, 'Fri') > 0 THEN '"Friday",' ELSE '' END ||
+        CASE WHEN instr(recurrence, 'Sat') > 0 THEN '"Saturday",' ELSE '' END ||
+        CASE WHEN instr(recurrence, 'Sun') > 0 THEN '"Sunday",' ELSE '' END,
+    ',') || ']}'
+END;
@@ -0,0 +1,15 @@
+DROP TRIGGER remove_task_tags_on_tag_delete;
+DROP TRIGGER remove_task_tags_on_task_delete;
+
+ALTER TABLE tasks ADD COLUMN tags TEXT DEFAULT "[]" NOT NULL;
+
+UPDATE tasks
+SET tags = (
+    SELECT json_group_array(tags.name)
+    FROM task_tags JOIN tags ON tags.id_tag = task_tags.id_tag
+    WHERE task_tags.id_task = tasks.id_task
+)
+WHERE id_task IN (SELECT id_task FROM task_tags);
+
+DROP TABLE task_tags;
+DROP TABLE tags;
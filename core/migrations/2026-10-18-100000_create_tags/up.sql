@@ -0,0 +1,33 @@
+CREATE TABLE tags (
+    id_tag TEXT NOT NULL CONSTRAINT tags_pk PRIMARY KEY,
+    name TEXT NOT NULL
+);
+CREATE UNIQUE INDEX tags_name_uindex ON tags (name);
+
+CREATE TABLE task_tags (
+    id_task TEXT NOT NULL,
+    id_tag TEXT NOT NULL,
+    CONSTRAINT task_tags_pk PRIMARY KEY (id_task, id_tag)
+);
+
+INSERT OR IGNORE INTO tags (id_tag, name)
+SELECT lower(hex(randomblob(16))), tag.value
+FROM tasks, json_each(CASE WHEN json_valid(tasks.tags) THEN tasks.tags ELSE '[]' END) AS tag
+WHERE tag.value <> '';
+
+INSERT OR IGNORE INTO task_tags (id_task, id_tag)
+SELECT tasks.id_task, tags.id_tag
+FROM tasks, json_each(CASE WHEN json_valid(tasks.tags) THEN tasks.tags ELSE '[]' END) AS tag
+JOIN tags ON tags.name = tag.value;
+
+ALTER TABLE tasks DROP COLUMN tags;
+
+CREATE TRIGGER remove_task_tags_on_task_delete BEFORE DELETE ON tasks BEGIN
+DELETE FROM task_tags
+WHERE task_tags.id_task = old.id_task;
+END;
+
+CREATE TRIGGER remove_task_tags_on_tag_delete BEFORE DELETE ON tags BEGIN
+DELETE FROM task_tags
+WHERE task_tags.id_tag = old.id_tag;
+END;
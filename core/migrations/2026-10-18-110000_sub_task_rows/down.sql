@@ -0,0 +1,85 @@
+ALTER TABLE tasks ADD COLUMN sub_tasks TEXT DEFAULT "[]" NOT NULL;
+
+UPDATE tasks
+SET sub_tasks = (
+    SELECT json_group_array(json_object(
+        'id', sub_task.id_task,
+        'parent', sub_task.parent,
+        'title', sub_task.title,
+        'favorite', json(CASE WHEN sub_task.favorite THEN 'true' ELSE 'false' END),
+        'today', json(CASE WHEN sub_task.today THEN 'true' ELSE 'false' END),
+        'status', CASE sub_task.status
+            WHEN 1 THEN 'Completed'
+            WHEN 2 THEN 'InProgress'
+            WHEN 3 THEN 'Waiting'
+            WHEN 4 THEN 'Deferred'
+            ELSE 'NotStarted'
+        END,
+        'priority', CASE sub_task.priority
+            WHEN 1 THEN 'Normal'
+            WHEN 2 THEN 'High'
+            ELSE 'Low'
+        END,
+        'sub_tasks', json('[]'),
+        'tags', json('[]'),
+        'notes', sub_task.notes,
+        'completion_date', strftime('%Y-%m-%dT%H:%M:%SZ', sub_task.completion_date),
+        'deletion_date', strftime('%Y-%m-%dT%H:%M:%SZ', sub_task.deletion_date),
+        'due_date', strftime('%Y-%m-%dT%H:%M:%SZ', sub_task.due_date),
+        'reminder_date', strftime('%Y-%m-%dT%H:%M:%SZ', sub_task.reminder_date),
+        'recurrence', json(sub_task.recurrence),
+        'created_date_time', strftime('%Y-%m-%dT%H:%M:%SZ', sub_task.created_date_time),
+        'last_modified_date_time', strftime('%Y-%m-%dT%H:%M:%SZ', sub_task.last_modified_date_time)
+    ))
+    FROM (
+        SELECT * FROM tasks AS child
+        WHERE child.parent_task = tasks.id_task
+        ORDER BY child.position
+    ) AS sub_task
+)
+WHERE id_task IN (SELECT parent_task FROM tasks WHERE parent_task IS NOT NULL);
+
+DELETE FROM tasks WHERE parent_task IS NOT NULL;
+
+DROP INDEX tasks_parent_task_index;
+
+CREATE TABLE tasks_without_parent_task (
+    id_task TEXT NOT NULL CONSTRAINT tasks_pk PRIMARY KEY,
+    parent TEXT NOT NULL,
+    title TEXT NOT NULL,
+    notes TEXT,
+    priority INTEGER DEFAULT 1 NOT NULL,
+    favorite BOOLEAN DEFAULT false NOT NULL,
+    status INTEGER DEFAULT 1 NOT NULL,
+    completion_date TIMESTAMP,
+    due_date TIMESTAMP,
+    reminder_date TIMESTAMP,
+    created_date_time TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    last_modified_date_time TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    today BOOLEAN DEFAULT false NOT NULL,
+    deletion_date TIMESTAMP,
+    recurrence TEXT,
+    sub_tasks TEXT DEFAULT "[]" NOT NULL
+);
+
+INSERT INTO tasks_without_parent_task
+SELECT
+    id_task, parent, title, notes, priority, favorite, status, completion_date,
+    due_date, reminder_date, created_date_time, last_modified_date_time, today,
+    deletion_date, recurrence, sub_tasks
+FROM tasks;
+
+DROP TRIGGER remove_tasks_on_list_delete;
+DROP TABLE tasks;
+ALTER TABLE tasks_without_parent_task RENAME TO tasks;
+CREATE UNIQUE INDEX tasks_id_uindex ON tasks (id_task);
+
+CREATE TRIGGER remove_tasks_on_list_delete BEFORE DELETE ON lists BEGIN
+DELETE FROM tasks
+WHERE tasks.parent = old.id_list;
+END;
+
+CREATE TRIGGER remove_task_tags_on_task_delete BEFORE DELETE ON tasks BEGIN
+DELETE FROM task_tags
+WHERE task_tags.id_task = old.id_task;
+END;
@@ -0,0 +1,96 @@
+ALTER TABLE tasks ADD COLUMN parent_task TEXT REFERENCES tasks (id_task);
+ALTER TABLE tasks ADD COLUMN position INTEGER DEFAULT 0 NOT NULL;
+CREATE INDEX tasks_parent_task_index ON tasks (parent_task, position);
+
+CREATE TEMPORARY TABLE sub_task_rows AS
+WITH RECURSIVE sub_task_tree (id_task, parent_task, list, position, body) AS (
+    SELECT
+        coalesce(nullif(json_extract(sub_task.value, '$.id'), ''), lower(hex(randomblob(16)))),
+        tasks.id_task,
+        tasks.parent,
+        sub_task.key,
+        sub_task.value
+    FROM tasks, json_each(CASE WHEN json_valid(tasks.sub_tasks) THEN tasks.sub_tasks ELSE '[]' END) AS sub_task
+    WHERE sub_task.type = 'object'
+    UNION ALL
+    SELECT
+        coalesce(nullif(json_extract(sub_task.value, '$.id'), ''), lower(hex(randomblob(16)))),
+        sub_task_tree.id_task,
+        sub_task_tree.list,
+        sub_task.key,
+        sub_task.value
+    FROM sub_task_tree, json_each(coalesce(json_extract(sub_task_tree.body, '$.sub_tasks'), '[]')) AS sub_task
+    WHERE sub_task.type = 'object'
+)
+SELECT
+    id_task,
+    parent_task,
+    list,
+    position,
+    body,
+    rtrim(
+        CASE WHEN json_extract(body, '$.recurrence.monday') THEN '"Monday",' ELSE '' END ||
+        CASE WHEN json_extract(body, '$.recurrence.tuesday') THEN '"Tuesday",' ELSE '' END ||
+        CASE WHEN json_extract(body, '$.recurrence.wednesday') THEN '"Wednesday",' ELSE '' END ||
+        CASE WHEN json_extract(body, '$.recurrence.thursday') THEN '"Thursday",' ELSE '' END ||
+        CASE WHEN json_extract(body, '$.recurrence.friday') THEN '"Friday",' ELSE '' END ||
+        CASE WHEN json_extract(body, '$.recurrence.saturday') THEN '"Saturday",' ELSE '' END ||
+        CASE WHEN json_extract(body, '$.recurrence.sunday') THEN '"Sunday",' ELSE '' END,
+    ',') AS days_of_week
+FROM sub_task_tree;
+
+INSERT OR IGNORE INTO tasks (
+    id_task, parent, title, notes, priority, favorite, status, completion_date,
+    due_date, reminder_date, created_date_time, last_modified_date_time, today,
+    deletion_date, recurrence, parent_task, position
+)
+SELECT
+    id_task,
+    list,
+    coalesce(json_extract(body, '$.title'), ''),
+    json_extract(body, '$.notes'),
+    CASE json_extract(body, '$.priority')
+        WHEN 'Normal' THEN 1
+        WHEN 'High' THEN 2
+        ELSE 0
+    END,
+    coalesce(json_extract(body, '$.favorite'), false),
+    CASE json_extract(body, '$.status')
+        WHEN 'Completed' THEN 1
+        WHEN 'InProgress' THEN 2
+        WHEN 'Waiting' THEN 3
+        WHEN 'Deferred' THEN 4
+        ELSE 0
+    END,
+    datetime(json_extract(body, '$.completion_date')),
+    datetime(json_extract(body, '$.due_date')),
+    datetime(json_extract(body, '$.reminder_date')),
+    coalesce(datetime(json_extract(body, '$.created_date_time')), CURRENT_TIMESTAMP),
+    coalesce(datetime(json_extract(body, '$.last_modified_date_time')), CURRENT_TIMESTAMP),
+    coalesce(json_extract(body, '$.today'), false),
+    datetime(json_extract(body, '$.deletion_date')),
+    CASE
+        WHEN days_of_week = '' THEN NULL
+        ELSE '{"frequency":"Weekly","interval":1,"days_of_week":[' || days_of_week || ']}'
+    END,
+    parent_task,
+    position
+FROM sub_task_rows;
+
+INSERT OR IGNORE INTO tags (id_tag, name)
+SELECT lower(hex(randomblob(16))), tag.value
+FROM sub_task_rows, json_each(coalesce(json_extract(sub_task_rows.body, '$.tags'), '[]')) AS tag
+WHERE tag.type = 'text' AND tag.value <> '';
+
+INSERT OR IGNORE INTO task_tags (id_task, id_tag)
+SELECT sub_task_rows.id_task, tags.id_tag
+FROM sub_task_rows
+JOIN tasks
+    ON tasks.id_task = sub_task_rows.id_task
+    AND tasks.parent_task = sub_task_rows.parent_task,
+json_each(coalesce(json_extract(sub_task_rows.body, '$.tags'), '[]')) AS tag
+JOIN tags ON tags.name = tag.value;
+
+DROP TABLE sub_task_rows;
+
+ALTER TABLE tasks DROP COLUMN sub_tasks;
@@ -0,0 +1,3 @@
+DELETE FROM tasks WHERE deletion_date IS NOT NULL;
+DELETE FROM lists WHERE deletion_date IS NOT NULL;
+ALTER TABLE lists DROP COLUMN deletion_date;
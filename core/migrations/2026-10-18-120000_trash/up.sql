@@ -0,0 +1 @@
+ALTER TABLE lists ADD COLUMN deletion_date TIMESTAMP;
@@ -0,0 +1,3 @@
+DROP TABLE remote_task_positions;
+DROP INDEX tasks_parent_position_index;
+UPDATE tasks SET position = 0 WHERE parent_task IS NULL;
@@ -0,0 +1,20 @@
+UPDATE tasks
+SET position = (
+    SELECT count(*)
+    FROM tasks AS sibling
+    WHERE sibling.parent = tasks.parent
+      AND sibling.parent_task IS NULL
+      AND (
+        sibling.created_date_time < tasks.created_date_time
+        OR (sibling.created_date_time = tasks.created_date_time AND sibling.id_task < tasks.id_task)
+      )
+)
+WHERE parent_task IS NULL;
+
+CREATE INDEX tasks_parent_position_index ON tasks (parent, position);
+
+CREATE TABLE remote_task_positions (
+    id_task TEXT NOT NULL CONSTRAINT remote_task_positions_pk PRIMARY KEY,
+    id_list TEXT NOT NULL,
+    position INTEGER NOT NULL
+);
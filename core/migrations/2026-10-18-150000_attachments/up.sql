@@ -0,0 +1,14 @@
+CREATE TABLE attachments (
+    id_attachment TEXT NOT NULL CONSTRAINT attachments_pk PRIMARY KEY,
+    id_task TEXT NOT NULL,
+    name TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    created_date_time TIMESTAMP NOT NULL
+);
+CREATE INDEX attachments_id_task_index ON attachments (id_task);
+
+CREATE TRIGGER remove_attachments_on_task_delete BEFORE DELETE ON tasks BEGIN
+DELETE FROM attachments
+WHERE attachments.id_task = old.id_task;
+END;
@@ -0,0 +1,3 @@
+DROP TRIGGER remove_time_entries_on_task_delete;
+DROP TABLE time_entries;
+ALTER TABLE tasks DROP COLUMN estimated_minutes;
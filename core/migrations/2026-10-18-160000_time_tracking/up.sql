@@ -0,0 +1,14 @@
+ALTER TABLE tasks ADD COLUMN estimated_minutes BIGINT;
+
+CREATE TABLE time_entries (
+    id_entry TEXT NOT NULL CONSTRAINT time_entries_pk PRIMARY KEY,
+    id_task TEXT NOT NULL,
+    start_date TIMESTAMP NOT NULL,
+    end_date TIMESTAMP
+);
+CREATE INDEX time_entries_id_task_index ON time_entries (id_task);
+
+CREATE TRIGGER remove_time_entries_on_task_delete BEFORE DELETE ON tasks BEGIN
+DELETE FROM time_entries
+WHERE time_entries.id_task = old.id_task;
+END;
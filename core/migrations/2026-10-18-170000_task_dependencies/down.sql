@@ -0,0 +1,2 @@
+DROP TRIGGER remove_dependencies_on_task_delete;
+DROP TABLE task_dependencies;
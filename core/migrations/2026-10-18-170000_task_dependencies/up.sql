@@ -0,0 +1,12 @@
+CREATE TABLE task_dependencies (
+    id_task TEXT NOT NULL,
+    id_blocking_task TEXT NOT NULL,
+    CONSTRAINT task_dependencies_pk PRIMARY KEY (id_task, id_blocking_task)
+);
+CREATE INDEX task_dependencies_id_blocking_task_index ON task_dependencies (id_blocking_task);
+
+CREATE TRIGGER remove_dependencies_on_task_delete BEFORE DELETE ON tasks BEGIN
+DELETE FROM task_dependencies
+WHERE task_dependencies.id_task = old.id_task
+    OR task_dependencies.id_blocking_task = old.id_task;
+END;
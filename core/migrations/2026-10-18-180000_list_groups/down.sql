@@ -0,0 +1,4 @@
+DROP TRIGGER ungroup_lists_on_group_delete;
+DROP TABLE remote_list_groups;
+ALTER TABLE lists DROP COLUMN id_group;
+DROP TABLE list_groups;
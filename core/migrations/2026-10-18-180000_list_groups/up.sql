@@ -0,0 +1,19 @@
+CREATE TABLE list_groups (
+    id_group TEXT NOT NULL CONSTRAINT list_groups_pk PRIMARY KEY,
+    name TEXT NOT NULL,
+    service TEXT NOT NULL DEFAULT 'Computer'
+);
+
+ALTER TABLE lists ADD COLUMN id_group TEXT;
+
+CREATE TABLE remote_list_groups (
+    id_list TEXT NOT NULL CONSTRAINT remote_list_groups_pk PRIMARY KEY,
+    id_group TEXT NOT NULL
+);
+
+CREATE TRIGGER ungroup_lists_on_group_delete BEFORE DELETE ON list_groups BEGIN
+UPDATE lists SET id_group = NULL
+WHERE lists.id_group = old.id_group;
+DELETE FROM remote_list_groups
+WHERE remote_list_groups.id_group = old.id_group;
+END;
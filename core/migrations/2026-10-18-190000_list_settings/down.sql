@@ -0,0 +1,5 @@
+DROP TABLE remote_list_settings;
+ALTER TABLE lists DROP COLUMN grouping;
+ALTER TABLE lists DROP COLUMN show_completed;
+ALTER TABLE lists DROP COLUMN sort_order;
+ALTER TABLE lists DROP COLUMN color;
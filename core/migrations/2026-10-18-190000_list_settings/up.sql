@@ -0,0 +1,12 @@
+ALTER TABLE lists ADD COLUMN color TEXT;
+ALTER TABLE lists ADD COLUMN sort_order INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE lists ADD COLUMN show_completed BOOLEAN NOT NULL DEFAULT 1;
+ALTER TABLE lists ADD COLUMN grouping INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE remote_list_settings (
+    id_list TEXT NOT NULL CONSTRAINT remote_list_settings_pk PRIMARY KEY,
+    color TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    show_completed BOOLEAN NOT NULL DEFAULT 1,
+    grouping INTEGER NOT NULL DEFAULT 0
+);
@@ -0,0 +1,2 @@
+DROP TRIGGER remove_linked_resources_on_task_delete;
+DROP TABLE linked_resources;
@@ -0,0 +1,14 @@
+CREATE TABLE linked_resources (
+    id_linked_resource TEXT NOT NULL CONSTRAINT linked_resources_pk PRIMARY KEY,
+    id_task TEXT NOT NULL,
+    web_url TEXT NOT NULL,
+    display_name TEXT,
+    application_name TEXT,
+    position INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX linked_resources_id_task_index ON linked_resources (id_task);
+
+CREATE TRIGGER remove_linked_resources_on_task_delete BEFORE DELETE ON tasks BEGIN
+DELETE FROM linked_resources
+WHERE linked_resources.id_task = old.id_task;
+END;
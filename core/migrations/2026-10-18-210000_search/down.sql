@@ -0,0 +1,11 @@
+DROP TRIGGER index_list_on_delete;
+DROP TRIGGER index_list_on_update;
+DROP TRIGGER index_list_on_insert;
+DROP TRIGGER index_tag_on_update;
+DROP TRIGGER index_task_tags_on_delete;
+DROP TRIGGER index_task_tags_on_insert;
+DROP TRIGGER index_task_on_delete;
+DROP TRIGGER index_task_on_update;
+DROP TRIGGER index_task_on_insert;
+DROP TABLE list_search;
+DROP TABLE task_search;
@@ -0,0 +1,80 @@
+-- Full-text indexes share the rowid of the row they index.
+CREATE VIRTUAL TABLE task_search USING fts5 (title, notes, tags);
+CREATE VIRTUAL TABLE list_search USING fts5 (name, description);
+
+INSERT INTO task_search (rowid, title, notes, tags)
+SELECT tasks.rowid,
+       tasks.title,
+       COALESCE(tasks.notes, ''),
+       COALESCE((SELECT GROUP_CONCAT(tags.name, ' ')
+                 FROM task_tags
+                          INNER JOIN tags ON tags.id_tag = task_tags.id_tag
+                 WHERE task_tags.id_task = tasks.id_task), '')
+FROM tasks;
+
+INSERT INTO list_search (rowid, name, description)
+SELECT rowid, name, description
+FROM lists;
+
+CREATE TRIGGER index_task_on_insert AFTER INSERT ON tasks BEGIN
+INSERT INTO task_search (rowid, title, notes, tags)
+VALUES (new.rowid, new.title, COALESCE(new.notes, ''), '');
+END;
+
+CREATE TRIGGER index_task_on_update AFTER UPDATE OF title, notes ON tasks BEGIN
+UPDATE task_search
+SET title = new.title,
+    notes = COALESCE(new.notes, '')
+WHERE rowid = new.rowid;
+END;
+
+CREATE TRIGGER index_task_on_delete AFTER DELETE ON tasks BEGIN
+DELETE FROM task_search
+WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER index_task_tags_on_insert AFTER INSERT ON task_tags BEGIN
+UPDATE task_search
+SET tags = COALESCE((SELECT GROUP_CONCAT(tags.name, ' ')
+                     FROM task_tags
+                              INNER JOIN tags ON tags.id_tag = task_tags.id_tag
+                     WHERE task_tags.id_task = new.id_task), '')
+WHERE rowid = (SELECT rowid FROM tasks WHERE id_task = new.id_task);
+END;
+
+CREATE TRIGGER index_task_tags_on_delete AFTER DELETE ON task_tags BEGIN
+UPDATE task_search
+SET tags = COALESCE((SELECT GROUP_CONCAT(tags.name, ' ')
+                     FROM task_tags
+                              INNER JOIN tags ON tags.id_tag = task_tags.id_tag
+                     WHERE task_tags.id_task = old.id_task), '')
+WHERE rowid = (SELECT rowid FROM tasks WHERE id_task = old.id_task);
+END;
+
+CREATE TRIGGER index_tag_on_update AFTER UPDATE OF name ON tags BEGIN
+UPDATE task_search
+SET tags = COALESCE((SELECT GROUP_CONCAT(tags.name, ' ')
+                     FROM task_tags
+                              INNER JOIN tags ON tags.id_tag = task_tags.id_tag
+                     WHERE task_tags.id_task = tasks.id_task), '')
+FROM tasks
+WHERE task_search.rowid = tasks.rowid
+  AND tasks.id_task IN (SELECT id_task FROM task_tags WHERE id_tag = new.id_tag);
+END;
+
+CREATE TRIGGER index_list_on_insert AFTER INSERT ON lists BEGIN
+INSERT INTO list_search (rowid, name, description)
+VALUES (new.rowid, new.name, new.description);
+END;
+
+CREATE TRIGGER index_list_on_update AFTER UPDATE OF name, description ON lists BEGIN
+UPDATE list_search
+SET name = new.name,
+    description = new.description
+WHERE rowid = new.rowid;
+END;
+
+CREATE TRIGGER index_list_on_delete AFTER DELETE ON lists BEGIN
+DELETE FROM list_search
+WHERE rowid = old.rowid;
+END;
@@ -0,0 +1,267 @@
+PRAGMA foreign_keys = OFF;
+
+BEGIN;
+
+DROP TRIGGER index_task_on_insert;
+DROP TRIGGER index_task_on_update;
+DROP TRIGGER index_task_on_delete;
+DROP TRIGGER index_task_tags_on_insert;
+DROP TRIGGER index_task_tags_on_delete;
+DROP TRIGGER index_tag_on_update;
+DROP TRIGGER index_list_on_insert;
+DROP TRIGGER index_list_on_update;
+DROP TRIGGER index_list_on_delete;
+
+CREATE TABLE old_lists (
+    id_list TEXT NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    icon_name TEXT DEFAULT 'view-list-symbolic',
+    deletion_date TIMESTAMP,
+    id_group TEXT,
+    color TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    show_completed BOOLEAN NOT NULL DEFAULT 1,
+    grouping INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT INTO old_lists (rowid, id_list, name, description, icon_name, deletion_date,
+                       id_group, color, sort_order, show_completed, grouping)
+SELECT rowid, id_list, name, description, icon_name, deletion_date,
+       id_group, color, sort_order, show_completed, grouping
+FROM lists;
+
+CREATE TABLE old_tasks (
+    id_task TEXT NOT NULL CONSTRAINT tasks_pk PRIMARY KEY,
+    parent TEXT NOT NULL,
+    title TEXT NOT NULL,
+    notes TEXT,
+    priority INTEGER DEFAULT 1 NOT NULL,
+    favorite BOOLEAN DEFAULT false NOT NULL,
+    status INTEGER DEFAULT 1 NOT NULL,
+    completion_date TIMESTAMP,
+    due_date TIMESTAMP,
+    reminder_date TIMESTAMP,
+    created_date_time TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    last_modified_date_time TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    today BOOLEAN DEFAULT false NOT NULL,
+    deletion_date TIMESTAMP,
+    recurrence TEXT,
+    parent_task TEXT REFERENCES tasks (id_task),
+    position INTEGER DEFAULT 0 NOT NULL,
+    start_date TIMESTAMP,
+    estimated_minutes BIGINT
+);
+
+INSERT INTO old_tasks (rowid, id_task, parent, title, notes, priority, favorite, status,
+                       completion_date, due_date, reminder_date, created_date_time,
+                       last_modified_date_time, today, deletion_date, recurrence,
+                       parent_task, position, start_date, estimated_minutes)
+SELECT rowid, id_task, parent, title, notes, priority, favorite, status,
+       completion_date, due_date, reminder_date, created_date_time,
+       last_modified_date_time, today, deletion_date, recurrence,
+       parent_task, position, start_date, estimated_minutes
+FROM tasks;
+
+CREATE TABLE old_task_tags (
+    id_task TEXT NOT NULL,
+    id_tag TEXT NOT NULL,
+    CONSTRAINT task_tags_pk PRIMARY KEY (id_task, id_tag)
+);
+
+INSERT INTO old_task_tags (id_task, id_tag)
+SELECT id_task, id_tag
+FROM task_tags;
+
+CREATE TABLE old_attachments (
+    id_attachment TEXT NOT NULL CONSTRAINT attachments_pk PRIMARY KEY,
+    id_task TEXT NOT NULL,
+    name TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    created_date_time TIMESTAMP NOT NULL
+);
+
+INSERT INTO old_attachments (id_attachment, id_task, name, content_type, size, created_date_time)
+SELECT id_attachment, id_task, name, content_type, size, created_date_time
+FROM attachments;
+
+CREATE TABLE old_time_entries (
+    id_entry TEXT NOT NULL CONSTRAINT time_entries_pk PRIMARY KEY,
+    id_task TEXT NOT NULL,
+    start_date TIMESTAMP NOT NULL,
+    end_date TIMESTAMP
+);
+
+INSERT INTO old_time_entries (id_entry, id_task, start_date, end_date)
+SELECT id_entry, id_task, start_date, end_date
+FROM time_entries;
+
+CREATE TABLE old_task_dependencies (
+    id_task TEXT NOT NULL,
+    id_blocking_task TEXT NOT NULL,
+    CONSTRAINT task_dependencies_pk PRIMARY KEY (id_task, id_blocking_task)
+);
+
+INSERT INTO old_task_dependencies (id_task, id_blocking_task)
+SELECT id_task, id_blocking_task
+FROM task_dependencies;
+
+CREATE TABLE old_linked_resources (
+    id_linked_resource TEXT NOT NULL CONSTRAINT linked_resources_pk PRIMARY KEY,
+    id_task TEXT NOT NULL,
+    web_url TEXT NOT NULL,
+    display_name TEXT,
+    application_name TEXT,
+    position INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT INTO old_linked_resources (id_linked_resource, id_task, web_url, display_name,
+                                  application_name, position)
+SELECT id_linked_resource, id_task, web_url, display_name, application_name, position
+FROM linked_resources;
+
+CREATE TABLE old_remote_list_groups (
+    id_list TEXT NOT NULL CONSTRAINT remote_list_groups_pk PRIMARY KEY,
+    id_group TEXT NOT NULL
+);
+
+INSERT INTO old_remote_list_groups (id_list, id_group)
+SELECT id_list, id_group
+FROM remote_list_groups;
+
+DROP TABLE remote_list_groups;
+DROP TABLE linked_resources;
+DROP TABLE task_dependencies;
+DROP TABLE time_entries;
+DROP TABLE attachments;
+DROP TABLE task_tags;
+DROP TABLE tasks;
+DROP TABLE lists;
+
+ALTER TABLE old_lists RENAME TO lists;
+ALTER TABLE old_tasks RENAME TO tasks;
+ALTER TABLE old_task_tags RENAME TO task_tags;
+ALTER TABLE old_attachments RENAME TO attachments;
+ALTER TABLE old_time_entries RENAME TO time_entries;
+ALTER TABLE old_task_dependencies RENAME TO task_dependencies;
+ALTER TABLE old_linked_resources RENAME TO linked_resources;
+ALTER TABLE old_remote_list_groups RENAME TO remote_list_groups;
+
+CREATE UNIQUE INDEX tasks_id_uindex ON tasks (id_task);
+CREATE INDEX tasks_parent_task_index ON tasks (parent_task, position);
+CREATE INDEX tasks_parent_position_index ON tasks (parent, position);
+CREATE INDEX attachments_id_task_index ON attachments (id_task);
+CREATE INDEX time_entries_id_task_index ON time_entries (id_task);
+CREATE INDEX task_dependencies_id_blocking_task_index ON task_dependencies (id_blocking_task);
+CREATE INDEX linked_resources_id_task_index ON linked_resources (id_task);
+
+CREATE TRIGGER remove_tasks_on_list_delete BEFORE DELETE ON lists BEGIN
+DELETE FROM tasks
+WHERE tasks.parent = old.id_list;
+END;
+
+CREATE TRIGGER remove_task_tags_on_task_delete BEFORE DELETE ON tasks BEGIN
+DELETE FROM task_tags
+WHERE task_tags.id_task = old.id_task;
+END;
+
+CREATE TRIGGER remove_task_tags_on_tag_delete BEFORE DELETE ON tags BEGIN
+DELETE FROM task_tags
+WHERE task_tags.id_tag = old.id_tag;
+END;
+
+CREATE TRIGGER remove_attachments_on_task_delete BEFORE DELETE ON tasks BEGIN
+DELETE FROM attachments
+WHERE attachments.id_task = old.id_task;
+END;
+
+CREATE TRIGGER remove_time_entries_on_task_delete BEFORE DELETE ON tasks BEGIN
+DELETE FROM time_entries
+WHERE time_entries.id_task = old.id_task;
+END;
+
+CREATE TRIGGER remove_dependencies_on_task_delete BEFORE DELETE ON tasks BEGIN
+DELETE FROM task_dependencies
+WHERE task_dependencies.id_task = old.id_task
+    OR task_dependencies.id_blocking_task = old.id_task;
+END;
+
+CREATE TRIGGER ungroup_lists_on_group_delete BEFORE DELETE ON list_groups BEGIN
+UPDATE lists SET id_group = NULL
+WHERE lists.id_group = old.id_group;
+DELETE FROM remote_list_groups
+WHERE remote_list_groups.id_group = old.id_group;
+END;
+
+CREATE TRIGGER remove_linked_resources_on_task_delete BEFORE DELETE ON tasks BEGIN
+DELETE FROM linked_resources
+WHERE linked_resources.id_task = old.id_task;
+END;
+
+CREATE TRIGGER index_task_on_insert AFTER INSERT ON tasks BEGIN
+INSERT INTO task_search (rowid, title, notes, tags)
+VALUES (new.rowid, new.title, COALESCE(new.notes, ''), '');
+END;
+
+CREATE TRIGGER index_task_on_update AFTER UPDATE OF title, notes ON tasks BEGIN
+UPDATE task_search
+SET title = new.title,
+    notes = COALESCE(new.notes, '')
+WHERE rowid = new.rowid;
+END;
+
+CREATE TRIGGER index_task_on_delete AFTER DELETE ON tasks BEGIN
+DELETE FROM task_search
+WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER index_task_tags_on_insert AFTER INSERT ON task_tags BEGIN
+UPDATE task_search
+SET tags = COALESCE((SELECT GROUP_CONCAT(tags.name, ' ')
+                     FROM task_tags
+                              INNER JOIN tags ON tags.id_tag = task_tags.id_tag
+                     WHERE task_tags.id_task = new.id_task), '')
+WHERE rowid = (SELECT rowid FROM tasks WHERE id_task = new.id_task);
+END;
+
+CREATE TRIGGER index_task_tags_on_delete AFTER DELETE ON task_tags BEGIN
+UPDATE task_search
+SET tags = COALESCE((SELECT GROUP_CONCAT(tags.name, ' ')
+                     FROM task_tags
+                              INNER JOIN tags ON tags.id_tag = task_tags.id_tag
+                     WHERE task_tags.id_task = old.id_task), '')
+WHERE rowid = (SELECT rowid FROM tasks WHERE id_task = old.id_task);
+END;
+
+CREATE TRIGGER index_tag_on_update AFTER UPDATE OF name ON tags BEGIN
+UPDATE task_search
+SET tags = COALESCE((SELECT GROUP_CONCAT(tags.name, ' ')
+                     FROM task_tags
+                              INNER JOIN tags ON tags.id_tag = task_tags.id_tag
+                     WHERE task_tags.id_task = tasks.id_task), '')
+FROM tasks
+WHERE task_search.rowid = tasks.rowid
+  AND tasks.id_task IN (SELECT id_task FROM task_tags WHERE id_tag = new.id_tag);
+END;
+
+CREATE TRIGGER index_list_on_insert AFTER INSERT ON lists BEGIN
+INSERT INTO list_search (rowid, name, description)
+VALUES (new.rowid, new.name, new.description);
+END;
+
+CREATE TRIGGER index_list_on_update AFTER UPDATE OF name, description ON lists BEGIN
+UPDATE list_search
+SET name = new.name,
+    description = new.description
+WHERE rowid = new.rowid;
+END;
+
+CREATE TRIGGER index_list_on_delete AFTER DELETE ON lists BEGIN
+DELETE FROM list_search
+WHERE rowid = old.rowid;
+END;
+
+COMMIT;
+
+PRAGMA foreign_keys = ON;
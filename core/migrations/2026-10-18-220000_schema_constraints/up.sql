@@ -0,0 +1,326 @@
+-- Tables are rebuilt to add foreign keys and checks, which SQLite can't add
+-- to an existing table. Rowids are kept so the search index stays valid.
+PRAGMA foreign_keys = OFF;
+
+BEGIN;
+
+DROP TRIGGER remove_tasks_on_list_delete;
+DROP TRIGGER remove_task_tags_on_task_delete;
+DROP TRIGGER remove_task_tags_on_tag_delete;
+DROP TRIGGER remove_attachments_on_task_delete;
+DROP TRIGGER remove_time_entries_on_task_delete;
+DROP TRIGGER remove_dependencies_on_task_delete;
+DROP TRIGGER ungroup_lists_on_group_delete;
+DROP TRIGGER remove_linked_resources_on_task_delete;
+DROP TRIGGER index_task_on_insert;
+DROP TRIGGER index_task_on_update;
+DROP TRIGGER index_task_on_delete;
+DROP TRIGGER index_task_tags_on_insert;
+DROP TRIGGER index_task_tags_on_delete;
+DROP TRIGGER index_tag_on_update;
+DROP TRIGGER index_list_on_insert;
+DROP TRIGGER index_list_on_update;
+DROP TRIGGER index_list_on_delete;
+
+CREATE TABLE new_lists (
+    id_list TEXT NOT NULL CONSTRAINT lists_pk PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    icon_name TEXT DEFAULT 'view-list-symbolic',
+    deletion_date TIMESTAMP,
+    id_group TEXT REFERENCES list_groups (id_group) ON DELETE SET NULL,
+    color TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0 CHECK (sort_order BETWEEN 0 AND 4),
+    show_completed BOOLEAN NOT NULL DEFAULT 1,
+    grouping INTEGER NOT NULL DEFAULT 0 CHECK (grouping BETWEEN 0 AND 3)
+);
+
+INSERT INTO new_lists (rowid, id_list, name, description, icon_name, deletion_date,
+                       id_group, color, sort_order, show_completed, grouping)
+SELECT rowid,
+       id_list,
+       name,
+       COALESCE(description, ''),
+       icon_name,
+       deletion_date,
+       (SELECT id_group FROM list_groups WHERE list_groups.id_group = lists.id_group),
+       color,
+       CASE WHEN sort_order BETWEEN 0 AND 4 THEN sort_order ELSE 0 END,
+       show_completed,
+       CASE WHEN grouping BETWEEN 0 AND 3 THEN grouping ELSE 0 END
+FROM lists;
+
+CREATE TABLE new_tasks (
+    id_task TEXT NOT NULL CONSTRAINT tasks_pk PRIMARY KEY,
+    parent TEXT NOT NULL REFERENCES lists (id_list) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    notes TEXT,
+    priority INTEGER NOT NULL DEFAULT 0 CHECK (priority BETWEEN 0 AND 2),
+    favorite BOOLEAN NOT NULL DEFAULT false,
+    status INTEGER NOT NULL DEFAULT 0 CHECK (status BETWEEN 0 AND 4),
+    completion_date TIMESTAMP,
+    due_date TIMESTAMP,
+    reminder_date TIMESTAMP,
+    created_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_modified_date_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    today BOOLEAN NOT NULL DEFAULT false,
+    deletion_date TIMESTAMP,
+    recurrence TEXT,
+    parent_task TEXT REFERENCES tasks (id_task) ON DELETE CASCADE,
+    position INTEGER NOT NULL DEFAULT 0,
+    start_date TIMESTAMP,
+    estimated_minutes BIGINT CHECK (estimated_minutes >= 0)
+);
+
+-- Sub-tasks whose parent task no longer exists, or that are part of a loop,
+-- become top level tasks. Tasks are kept in the list of their top level task.
+CREATE TEMPORARY TABLE task_parents AS
+WITH RECURSIVE
+    parents (id_task, parent_task) AS (
+        SELECT id_task,
+               CASE WHEN parent_task IN (SELECT id_task FROM tasks) THEN parent_task END
+        FROM tasks
+    ),
+    reachable (id_task, root) AS (
+        SELECT id_task, id_task
+        FROM parents
+        WHERE parent_task IS NULL
+        UNION
+        SELECT parents.id_task, reachable.root
+        FROM parents
+                 INNER JOIN reachable ON parents.parent_task = reachable.id_task
+    )
+SELECT parents.id_task,
+       CASE WHEN reachable.id_task IS NOT NULL THEN parents.parent_task END AS parent_task,
+       COALESCE(reachable.root, parents.id_task) AS root
+FROM parents
+         LEFT JOIN reachable ON reachable.id_task = parents.id_task;
+
+-- Tasks whose list no longer exists were never shown, they are moved to a
+-- list of their own instead of failing the foreign keys.
+INSERT INTO new_lists (id_list, name, icon_name)
+SELECT 'recovered-tasks', 'Recovered tasks', 'view-list-symbolic'
+WHERE EXISTS (SELECT 1
+              FROM task_parents
+                       INNER JOIN tasks ON tasks.id_task = task_parents.root
+              WHERE tasks.parent NOT IN (SELECT id_list FROM new_lists));
+
+INSERT INTO new_tasks (rowid, id_task, parent, title, notes, priority, favorite, status,
+                       completion_date, due_date, reminder_date, created_date_time,
+                       last_modified_date_time, today, deletion_date, recurrence,
+                       parent_task, position, start_date, estimated_minutes)
+SELECT tasks.rowid,
+       tasks.id_task,
+       CASE
+           WHEN root_task.parent IN (SELECT id_list FROM new_lists) THEN root_task.parent
+           ELSE 'recovered-tasks'
+           END,
+       tasks.title,
+       tasks.notes,
+       CASE WHEN tasks.priority BETWEEN 0 AND 2 THEN tasks.priority ELSE 0 END,
+       tasks.favorite,
+       CASE WHEN tasks.status BETWEEN 0 AND 4 THEN tasks.status ELSE 0 END,
+       tasks.completion_date,
+       tasks.due_date,
+       tasks.reminder_date,
+       tasks.created_date_time,
+       tasks.last_modified_date_time,
+       tasks.today,
+       tasks.deletion_date,
+       tasks.recurrence,
+       task_parents.parent_task,
+       tasks.position,
+       tasks.start_date,
+       CASE WHEN tasks.estimated_minutes >= 0 THEN tasks.estimated_minutes END
+FROM tasks
+         INNER JOIN task_parents ON task_parents.id_task = tasks.id_task
+         INNER JOIN tasks AS root_task ON root_task.id_task = task_parents.root;
+
+DROP TABLE task_parents;
+
+CREATE TABLE new_task_tags (
+    id_task TEXT NOT NULL REFERENCES tasks (id_task) ON DELETE CASCADE,
+    id_tag TEXT NOT NULL REFERENCES tags (id_tag) ON DELETE CASCADE,
+    CONSTRAINT task_tags_pk PRIMARY KEY (id_task, id_tag)
+);
+
+INSERT INTO new_task_tags (id_task, id_tag)
+SELECT id_task, id_tag
+FROM task_tags
+WHERE id_task IN (SELECT id_task FROM new_tasks)
+  AND id_tag IN (SELECT id_tag FROM tags);
+
+CREATE TABLE new_attachments (
+    id_attachment TEXT NOT NULL CONSTRAINT attachments_pk PRIMARY KEY,
+    id_task TEXT NOT NULL REFERENCES tasks (id_task) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size BIGINT NOT NULL CHECK (size >= 0),
+    created_date_time TIMESTAMP NOT NULL
+);
+
+INSERT INTO new_attachments (id_attachment, id_task, name, content_type, size, created_date_time)
+SELECT id_attachment, id_task, name, content_type, size, created_date_time
+FROM attachments
+WHERE id_task IN (SELECT id_task FROM new_tasks);
+
+CREATE TABLE new_time_entries (
+    id_entry TEXT NOT NULL CONSTRAINT time_entries_pk PRIMARY KEY,
+    id_task TEXT NOT NULL REFERENCES tasks (id_task) ON DELETE CASCADE,
+    start_date TIMESTAMP NOT NULL,
+    end_date TIMESTAMP CHECK (end_date >= start_date)
+);
+
+INSERT INTO new_time_entries (id_entry, id_task, start_date, end_date)
+SELECT id_entry, id_task, start_date, CASE WHEN end_date >= start_date THEN end_date ELSE start_date END
+FROM time_entries
+WHERE id_task IN (SELECT id_task FROM new_tasks);
+
+CREATE TABLE new_task_dependencies (
+    id_task TEXT NOT NULL REFERENCES tasks (id_task) ON DELETE CASCADE,
+    id_blocking_task TEXT NOT NULL REFERENCES tasks (id_task) ON DELETE CASCADE,
+    CONSTRAINT task_dependencies_pk PRIMARY KEY (id_task, id_blocking_task),
+    CHECK (id_task <> id_blocking_task)
+);
+
+INSERT INTO new_task_dependencies (id_task, id_blocking_task)
+SELECT id_task, id_blocking_task
+FROM task_dependencies
+WHERE id_task <> id_blocking_task
+  AND id_task IN (SELECT id_task FROM new_tasks)
+  AND id_blocking_task IN (SELECT id_task FROM new_tasks);
+
+CREATE TABLE new_linked_resources (
+    id_linked_resource TEXT NOT NULL CONSTRAINT linked_resources_pk PRIMARY KEY,
+    id_task TEXT NOT NULL REFERENCES tasks (id_task) ON DELETE CASCADE,
+    web_url TEXT NOT NULL,
+    display_name TEXT,
+    application_name TEXT,
+    position INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT INTO new_linked_resources (id_linked_resource, id_task, web_url, display_name,
+                                  application_name, position)
+SELECT id_linked_resource, id_task, web_url, display_name, application_name, position
+FROM linked_resources
+WHERE id_task IN (SELECT id_task FROM new_tasks);
+
+CREATE TABLE new_remote_list_groups (
+    id_list TEXT NOT NULL CONSTRAINT remote_list_groups_pk PRIMARY KEY,
+    id_group TEXT NOT NULL REFERENCES list_groups (id_group) ON DELETE CASCADE
+);
+
+INSERT INTO new_remote_list_groups (id_list, id_group)
+SELECT id_list, id_group
+FROM remote_list_groups
+WHERE id_group IN (SELECT id_group FROM list_groups);
+
+DROP TABLE remote_list_groups;
+DROP TABLE linked_resources;
+DROP TABLE task_dependencies;
+DROP TABLE time_entries;
+DROP TABLE attachments;
+DROP TABLE task_tags;
+DROP TABLE tasks;
+DROP TABLE lists;
+
+ALTER TABLE new_lists RENAME TO lists;
+ALTER TABLE new_tasks RENAME TO tasks;
+ALTER TABLE new_task_tags RENAME TO task_tags;
+ALTER TABLE new_attachments RENAME TO attachments;
+ALTER TABLE new_time_entries RENAME TO time_entries;
+ALTER TABLE new_task_dependencies RENAME TO task_dependencies;
+ALTER TABLE new_linked_resources RENAME TO linked_resources;
+ALTER TABLE new_remote_list_groups RENAME TO remote_list_groups;
+
+CREATE INDEX lists_id_group_index ON lists (id_group);
+CREATE INDEX tasks_parent_position_index ON tasks (parent, position);
+CREATE INDEX tasks_parent_task_index ON tasks (parent_task, position);
+CREATE INDEX tasks_status_index ON tasks (status);
+CREATE INDEX tasks_due_date_index ON tasks (due_date);
+CREATE INDEX tasks_deletion_date_index ON tasks (deletion_date);
+CREATE INDEX task_tags_id_tag_index ON task_tags (id_tag);
+CREATE INDEX attachments_id_task_index ON attachments (id_task);
+CREATE INDEX time_entries_id_task_index ON time_entries (id_task);
+CREATE INDEX task_dependencies_id_blocking_task_index ON task_dependencies (id_blocking_task);
+CREATE INDEX linked_resources_id_task_index ON linked_resources (id_task);
+CREATE INDEX remote_list_groups_id_group_index ON remote_list_groups (id_group);
+
+DELETE FROM task_search
+WHERE rowid NOT IN (SELECT rowid FROM tasks);
+
+DELETE FROM list_search
+WHERE rowid NOT IN (SELECT rowid FROM lists)
+   OR rowid IN (SELECT rowid FROM lists WHERE id_list = 'recovered-tasks');
+
+INSERT INTO list_search (rowid, name, description)
+SELECT rowid, name, description
+FROM lists
+WHERE id_list = 'recovered-tasks';
+
+CREATE TRIGGER index_task_on_insert AFTER INSERT ON tasks BEGIN
+INSERT INTO task_search (rowid, title, notes, tags)
+VALUES (new.rowid, new.title, COALESCE(new.notes, ''), '');
+END;
+
+CREATE TRIGGER index_task_on_update AFTER UPDATE OF title, notes ON tasks BEGIN
+UPDATE task_search
+SET title = new.title,
+    notes = COALESCE(new.notes, '')
+WHERE rowid = new.rowid;
+END;
+
+CREATE TRIGGER index_task_on_delete AFTER DELETE ON tasks BEGIN
+DELETE FROM task_search
+WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER index_task_tags_on_insert AFTER INSERT ON task_tags BEGIN
+UPDATE task_search
+SET tags = COALESCE((SELECT GROUP_CONCAT(tags.name, ' ')
+                     FROM task_tags
+                              INNER JOIN tags ON tags.id_tag = task_tags.id_tag
+                     WHERE task_tags.id_task = new.id_task), '')
+WHERE rowid = (SELECT rowid FROM tasks WHERE id_task = new.id_task);
+END;
+
+CREATE TRIGGER index_task_tags_on_delete AFTER DELETE ON task_tags BEGIN
+UPDATE task_search
+SET tags = COALESCE((SELECT GROUP_CONCAT(tags.name, ' ')
+                     FROM task_tags
+                              INNER JOIN tags ON tags.id_tag = task_tags.id_tag
+                     WHERE task_tags.id_task = old.id_task), '')
+WHERE rowid = (SELECT rowid FROM tasks WHERE id_task = old.id_task);
+END;
+
+CREATE TRIGGER index_tag_on_update AFTER UPDATE OF name ON tags BEGIN
+UPDATE task_search
+SET tags = COALESCE((SELECT GROUP_CONCAT(tags.name, ' ')
+                     FROM task_tags
+                              INNER JOIN tags ON tags.id_tag = task_tags.id_tag
+                     WHERE task_tags.id_task = tasks.id_task), '')
+FROM tasks
+WHERE task_search.rowid = tasks.rowid
+  AND tasks.id_task IN (SELECT id_task FROM task_tags WHERE id_tag = new.id_tag);
+END;
+
+CREATE TRIGGER index_list_on_insert AFTER INSERT ON lists BEGIN
+INSERT INTO list_search (rowid, name, description)
+VALUES (new.rowid, new.name, new.description);
+END;
+
+CREATE TRIGGER index_list_on_update AFTER UPDATE OF name, description ON lists BEGIN
+UPDATE list_search
+SET name = new.name,
+    description = new.description
+WHERE rowid = new.rowid;
+END;
+
+CREATE TRIGGER index_list_on_delete AFTER DELETE ON lists BEGIN
+DELETE FROM list_search
+WHERE rowid = old.rowid;
+END;
+
+COMMIT;
+
+PRAGMA foreign_keys = ON;
@@ -0,0 +1,3 @@
+DROP TRIGGER record_task_changes;
+DROP TRIGGER prevent_task_history_update;
+DROP TABLE task_history;
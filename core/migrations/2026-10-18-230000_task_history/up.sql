@@ -0,0 +1,57 @@
+-- Every change to the fields a user edits is kept, rows are only removed
+-- along with their task.
+CREATE TABLE task_history (
+    id_change TEXT NOT NULL CONSTRAINT task_history_pk PRIMARY KEY,
+    id_task TEXT NOT NULL REFERENCES tasks (id_task) ON DELETE CASCADE,
+    field TEXT NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    changed_date_time TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+);
+CREATE INDEX task_history_id_task_index ON task_history (id_task, changed_date_time);
+
+CREATE TRIGGER prevent_task_history_update BEFORE UPDATE ON task_history BEGIN
+SELECT RAISE(ABORT, 'The task history can not be modified');
+END;
+
+CREATE TRIGGER record_task_changes AFTER UPDATE ON tasks BEGIN
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'title', old.title, new.title
+WHERE old.title IS NOT new.title;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'notes', old.notes, new.notes
+WHERE old.notes IS NOT new.notes;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'status', old.status, new.status
+WHERE old.status IS NOT new.status;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'priority', old.priority, new.priority
+WHERE old.priority IS NOT new.priority;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'favorite', old.favorite, new.favorite
+WHERE old.favorite IS NOT new.favorite;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'today', old.today, new.today
+WHERE old.today IS NOT new.today;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'due_date', old.due_date, new.due_date
+WHERE old.due_date IS NOT new.due_date;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'reminder_date', old.reminder_date, new.reminder_date
+WHERE old.reminder_date IS NOT new.reminder_date;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'start_date', old.start_date, new.start_date
+WHERE old.start_date IS NOT new.start_date;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'recurrence', old.recurrence, new.recurrence
+WHERE old.recurrence IS NOT new.recurrence;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'parent', old.parent, new.parent
+WHERE old.parent IS NOT new.parent;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'deletion_date', old.deletion_date, new.deletion_date
+WHERE old.deletion_date IS NOT new.deletion_date;
+INSERT INTO task_history (id_change, id_task, field, old_value, new_value)
+SELECT lower(hex(randomblob(16))), new.id_task, 'estimated_minutes', old.estimated_minutes, new.estimated_minutes
+WHERE old.estimated_minutes IS NOT new.estimated_minutes;
+END;
@@ -1,5 +1,7 @@
 pub mod models;
+pub mod notes;
 pub(crate) mod schema;
 pub mod service;
 pub mod services;
 pub(crate) mod task_service;
+pub mod time_zone;
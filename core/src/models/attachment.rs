@@ -0,0 +1,60 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::services::microsoft::models::attachment::TaskFileAttachment;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+	pub id: String,
+	pub task_id: String,
+	pub name: String,
+	pub content_type: String,
+	pub size: i64,
+	pub created_date_time: DateTime<Utc>,
+}
+
+impl Attachment {
+	pub fn new(task_id: &str, name: &str, content_type: &str, size: i64) -> Self {
+		Self {
+			id: Uuid::new_v4().to_string(),
+			task_id: task_id.to_string(),
+			name: name.to_string(),
+			content_type: content_type.to_string(),
+			size,
+			created_date_time: Utc::now(),
+		}
+	}
+}
+
+impl From<TaskFileAttachment> for Attachment {
+	fn from(attachment: TaskFileAttachment) -> Self {
+		Self {
+			id: attachment.id,
+			task_id: String::new(),
+			name: attachment.name,
+			content_type: attachment.content_type,
+			size: attachment.size,
+			created_date_time: attachment
+				.last_modified_date_time
+				.and_then(|date| DateTime::<Utc>::from_str(&date).ok())
+				.unwrap_or_else(Utc::now),
+		}
+	}
+}
+
+impl From<Attachment> for TaskFileAttachment {
+	fn from(attachment: Attachment) -> Self {
+		Self {
+			odata_type: TaskFileAttachment::ODATA_TYPE.to_string(),
+			id: attachment.id,
+			name: attachment.name,
+			content_type: attachment.content_type,
+			size: attachment.size,
+			last_modified_date_time: None,
+			content_bytes: None,
+		}
+	}
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+/// A task that has to be completed before another one can be worked on.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dependency {
+	pub task_id: String,
+	pub title: String,
+	pub completed: bool,
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::service::Service;
+
+/// A folder task lists can be filed under in the sidebar.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Group {
+	pub id: String,
+	pub name: String,
+	pub service: Service,
+}
+
+impl Group {
+	pub fn new(name: &str, service: Service) -> Self {
+		Self {
+			id: Uuid::new_v4().to_string(),
+			name: name.to_string(),
+			service,
+		}
+	}
+}
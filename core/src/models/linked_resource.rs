@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::services::microsoft::models::linked_resource::TaskLinkedResource;
+
+/// A link kept on a task, like a document, a web page or an email.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkedResource {
+	pub id: String,
+	pub web_url: String,
+	pub display_name: Option<String>,
+	/// The app the link was added from.
+	pub application_name: Option<String>,
+}
+
+impl LinkedResource {
+	pub fn new(web_url: &str, display_name: Option<String>) -> Self {
+		Self {
+			id: Uuid::new_v4().to_string(),
+			web_url: web_url.to_string(),
+			display_name,
+			application_name: Some("Done".to_string()),
+		}
+	}
+
+	/// The name to show for the link, the address itself if it has none.
+	pub fn title(&self) -> &str {
+		self
+			.display_name
+			.as_deref()
+			.filter(|name| !name.is_empty())
+			.unwrap_or(&self.web_url)
+	}
+}
+
+impl From<TaskLinkedResource> for LinkedResource {
+	fn from(resource: TaskLinkedResource) -> Self {
+		Self {
+			id: resource.id,
+			web_url: resource.web_url.unwrap_or_default(),
+			display_name: resource.display_name,
+			application_name: resource.application_name,
+		}
+	}
+}
+
+impl From<LinkedResource> for TaskLinkedResource {
+	fn from(resource: LinkedResource) -> Self {
+		Self {
+			id: resource.id,
+			web_url: Some(resource.web_url),
+			display_name: resource.display_name,
+			application_name: resource.application_name,
+		}
+	}
+}
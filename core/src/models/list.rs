@@ -1,7 +1,9 @@
+use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::list_settings::ListSettings;
 use crate::service::Service;
 use crate::services::microsoft::models::list::TodoTaskList;
 
@@ -12,6 +14,15 @@ pub struct List {
 	pub description: String,
 	pub icon: Option<String>,
 	pub service: Service,
+	/// The id of the group the list is filed under, if any.
+	pub group: Option<String>,
+	pub settings: ListSettings,
+	/// Whether the user owns the list, only the owner can rename or delete
+	/// a shared list.
+	pub is_owner: bool,
+	/// Whether the list is shared with other people.
+	pub is_shared: bool,
+	pub deletion_date: Option<DateTime<Utc>>,
 }
 
 impl FromIterator<List> for List {
@@ -32,6 +43,11 @@ impl List {
 			service,
 			description: String::new(),
 			icon: Some("✍️".to_string()),
+			group: None,
+			settings: ListSettings::default(),
+			is_owner: true,
+			is_shared: false,
+			deletion_date: None,
 		}
 	}
 }
@@ -46,6 +62,11 @@ impl From<TodoTaskList> for List {
 			description: String::new(),
 			icon,
 			service: Service::Microsoft,
+			group: None,
+			settings: ListSettings::default(),
+			is_owner: task.is_owner,
+			is_shared: task.is_shared,
+			deletion_date: None,
 		}
 	}
 }
@@ -58,8 +79,8 @@ impl From<List> for TodoTaskList {
 		Self {
 			id: list.id,
 			display_name,
-			is_owner: true,
-			is_shared: false,
+			is_owner: list.is_owner,
+			is_shared: list.is_shared,
 			wellknown_list_name: Default::default(),
 		}
 	}
@@ -0,0 +1,158 @@
+use std::cmp::Ordering;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::time_zone;
+
+use super::{status::Status, task::Task};
+
+/// How a list looks and how its tasks are shown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListSettings {
+	/// Accent color of the list, as a `#rrggbb` string.
+	pub color: Option<String>,
+	pub sort_order: SortOrder,
+	pub show_completed: bool,
+	pub grouping: TaskGrouping,
+}
+
+impl Default for ListSettings {
+	fn default() -> Self {
+		Self {
+			color: None,
+			sort_order: SortOrder::Manual,
+			show_completed: true,
+			grouping: TaskGrouping::None,
+		}
+	}
+}
+
+impl ListSettings {
+	/// Whether a task is shown in the list.
+	pub fn shows(&self, task: &Task) -> bool {
+		self.show_completed || task.status != Status::Completed
+	}
+
+	/// Compares two tasks by their section first and then by the sort order.
+	pub fn compare(&self, a: &Task, b: &Task) -> Ordering {
+		self
+			.grouping
+			.section(a)
+			.cmp(&self.grouping.section(b))
+			.then_with(|| self.sort_order.compare(a, b))
+	}
+
+	/// Whether the tasks are shown in an order other than the manual one.
+	pub fn sorted(&self) -> bool {
+		self.sort_order != SortOrder::Manual || self.grouping != TaskGrouping::None
+	}
+}
+
+#[derive(
+	Clone, Copy, Default, Debug, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub enum SortOrder {
+	/// The order the user dragged the tasks into.
+	#[default]
+	Manual = 0,
+	Title = 1,
+	DueDate = 2,
+	Priority = 3,
+	CreationDate = 4,
+}
+
+impl From<i32> for SortOrder {
+	fn from(value: i32) -> Self {
+		match value {
+			1 => SortOrder::Title,
+			2 => SortOrder::DueDate,
+			3 => SortOrder::Priority,
+			4 => SortOrder::CreationDate,
+			_ => SortOrder::Manual,
+		}
+	}
+}
+
+impl From<SortOrder> for i32 {
+	fn from(value: SortOrder) -> Self {
+		value as i32
+	}
+}
+
+impl SortOrder {
+	/// Compares two tasks by this order. Tasks without a due date go last
+	/// and the most important tasks go first.
+	pub fn compare(&self, a: &Task, b: &Task) -> Ordering {
+		match self {
+			SortOrder::Manual => Ordering::Equal,
+			SortOrder::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+			SortOrder::DueDate => match (a.due_date, b.due_date) {
+				(Some(a), Some(b)) => a.cmp(&b),
+				(Some(_), None) => Ordering::Less,
+				(None, Some(_)) => Ordering::Greater,
+				(None, None) => Ordering::Equal,
+			},
+			SortOrder::Priority => b.priority.cmp(&a.priority),
+			SortOrder::CreationDate => a.created_date_time.cmp(&b.created_date_time),
+		}
+	}
+}
+
+#[derive(
+	Clone, Copy, Default, Debug, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub enum TaskGrouping {
+	#[default]
+	None = 0,
+	Status = 1,
+	Priority = 2,
+	DueDate = 3,
+}
+
+impl From<i32> for TaskGrouping {
+	fn from(value: i32) -> Self {
+		match value {
+			1 => TaskGrouping::Status,
+			2 => TaskGrouping::Priority,
+			3 => TaskGrouping::DueDate,
+			_ => TaskGrouping::None,
+		}
+	}
+}
+
+impl From<TaskGrouping> for i32 {
+	fn from(value: TaskGrouping) -> Self {
+		value as i32
+	}
+}
+
+impl TaskGrouping {
+	/// The section a task falls in, sections are shown in ascending order.
+	/// Open tasks come before completed ones, important tasks before the
+	/// rest, and overdue tasks before the ones due today, later or never.
+	pub fn section(&self, task: &Task) -> i32 {
+		match self {
+			TaskGrouping::None => 0,
+			TaskGrouping::Status => match task.status {
+				Status::InProgress => 0,
+				Status::NotStarted => 1,
+				Status::Waiting => 2,
+				Status::Deferred => 3,
+				Status::Completed => 4,
+			},
+			TaskGrouping::Priority => -i32::from(task.priority),
+			TaskGrouping::DueDate => match task.due_date {
+				Some(date) => {
+					let today = time_zone::local_date(Utc::now());
+					match time_zone::local_date(date).cmp(&today) {
+						Ordering::Less => 0,
+						Ordering::Equal => 1,
+						Ordering::Greater => 2,
+					}
+				},
+				None => 3,
+			},
+		}
+	}
+}
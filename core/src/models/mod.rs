@@ -7,3 +7,23 @@ pub mod priority;
 pub mod status;
 
 pub mod recurrence;
+
+pub mod tag;
+
+pub mod attachment;
+
+pub mod time_entry;
+
+pub mod dependency;
+
+pub mod group;
+
+pub mod list_settings;
+
+pub mod linked_resource;
+
+pub mod search;
+
+pub mod task_change;
+
+pub mod placement;
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a task is moved to among its siblings. It is given by the sibling it
+/// lands next to, since the tasks shown are not always all of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Placement {
+	Before(String),
+	After(String),
+}
+
+impl Placement {
+	/// The index the task goes to in `ids`, the ids of its siblings without
+	/// it, or `None` if the sibling is not one of them.
+	pub fn position(&self, ids: &[String]) -> Option<usize> {
+		match self {
+			Placement::Before(sibling) => ids.iter().position(|id| id == sibling),
+			Placement::After(sibling) => ids
+				.iter()
+				.position(|id| id == sibling)
+				.map(|index| index + 1),
+		}
+	}
+}
@@ -1,7 +1,13 @@
-use crate::services::microsoft::models::recurrence::DayOfWeek;
-use crate::services::microsoft::models::recurrence::TaskRecurrence;
+use crate::services::microsoft::models::recurrence::{
+	DayOfWeek, RecurrencePatternType, RecurrenceRangeType, TaskRecurrence,
+	TaskRecurrencePattern, TaskRecurrenceRange, WeekIndex as TodoWeekIndex,
+};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
-#[derive(Debug)]
+
+#[derive(
+	Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
 pub enum Day {
 	Monday,
 	Tuesday,
@@ -9,6 +15,7 @@ pub enum Day {
 	Thursday,
 	Friday,
 	Saturday,
+	#[default]
 	Sunday,
 }
 
@@ -26,69 +33,580 @@ impl ToString for Day {
 	}
 }
 
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+/// How often a task repeats, mirrors the pattern types supported by
+/// Microsoft To Do.
+#[derive(
+	Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub enum Frequency {
+	#[default]
+	Daily,
+	Weekly,
+	/// Repeats on a given day of the month, e.g. the 15th.
+	AbsoluteMonthly,
+	/// Repeats on a given weekday of the month, e.g. the last Friday.
+	RelativeMonthly,
+	/// Repeats on a given day of a given month.
+	AbsoluteYearly,
+	/// Repeats on a given weekday of a given month.
+	RelativeYearly,
+}
+
+#[derive(
+	Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+pub enum WeekIndex {
+	#[default]
+	First,
+	Second,
+	Third,
+	Fourth,
+	Last,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RangeType {
+	#[default]
+	NoEnd,
+	/// Repeats until the given date, included.
+	EndDate(NaiveDate),
+	Numbered,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "StoredRecurrenceRange", into = "StoredRecurrenceRange")]
+pub struct RecurrenceRange {
+	pub range_type: RangeType,
+	/// The day the recurrence starts on, Microsoft To Do requires it.
+	pub start_date: Option<NaiveDate>,
+	pub number_of_occurrences: i32,
+	pub time_zone: String,
+}
+
+impl RecurrenceRange {
+	pub fn end_date(&self) -> Option<NaiveDate> {
+		match self.range_type {
+			RangeType::EndDate(end_date) => Some(end_date),
+			_ => None,
+		}
+	}
+}
+
+/// The shape ranges are saved with, the end date is kept next to the range
+/// type so that recurrences saved before it was part of it still load.
+#[derive(
+	Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
+)]
+enum StoredRangeType {
+	#[default]
+	NoEnd,
+	EndDate,
+	Numbered,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct StoredRecurrenceRange {
+	range_type: StoredRangeType,
+	start_date: Option<NaiveDate>,
+	end_date: Option<NaiveDate>,
+	number_of_occurrences: i32,
+	time_zone: String,
+}
+
+impl From<StoredRecurrenceRange> for RecurrenceRange {
+	fn from(value: StoredRecurrenceRange) -> Self {
+		Self {
+			range_type: range_type(value.range_type, value.end_date),
+			start_date: value.start_date,
+			number_of_occurrences: value.number_of_occurrences,
+			time_zone: value.time_zone,
+		}
+	}
+}
+
+impl From<RecurrenceRange> for StoredRecurrenceRange {
+	fn from(value: RecurrenceRange) -> Self {
+		Self {
+			range_type: match value.range_type {
+				RangeType::NoEnd => StoredRangeType::NoEnd,
+				RangeType::EndDate(_) => StoredRangeType::EndDate,
+				RangeType::Numbered => StoredRangeType::Numbered,
+			},
+			end_date: value.end_date(),
+			start_date: value.start_date,
+			number_of_occurrences: value.number_of_occurrences,
+			time_zone: value.time_zone,
+		}
+	}
+}
+
+/// A range that should end on a date but has none never ends.
+fn range_type(
+	range_type: StoredRangeType,
+	end_date: Option<NaiveDate>,
+) -> RangeType {
+	match (range_type, end_date) {
+		(StoredRangeType::EndDate, Some(end_date)) => RangeType::EndDate(end_date),
+		(StoredRangeType::Numbered, _) => RangeType::Numbered,
+		_ => RangeType::NoEnd,
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Recurrence {
-	pub monday: bool,
-	pub tuesday: bool,
-	pub wednesday: bool,
-	pub thursday: bool,
-	pub friday: bool,
-	pub saturday: bool,
-	pub sunday: bool,
+	pub frequency: Frequency,
+	pub interval: i32,
+	pub month: i32,
+	pub day_of_month: i32,
+	pub days_of_week: Vec<Day>,
+	pub first_day_of_week: Day,
+	pub index: Option<WeekIndex>,
+	pub range: RecurrenceRange,
 }
 
-impl Recurrence {
-	pub fn from_string(value: String) -> Self {
+impl Default for Recurrence {
+	fn default() -> Self {
 		Self {
-			monday: value.contains(Day::Monday.to_string().as_str()),
-			tuesday: value.contains(Day::Tuesday.to_string().as_str()),
-			wednesday: value.contains(Day::Wednesday.to_string().as_str()),
-			thursday: value.contains(Day::Thursday.to_string().as_str()),
-			friday: value.contains(Day::Friday.to_string().as_str()),
-			saturday: value.contains(Day::Saturday.to_string().as_str()),
-			sunday: value.contains(Day::Sunday.to_string().as_str()),
+			frequency: Frequency::default(),
+			interval: 1,
+			month: 0,
+			day_of_month: 0,
+			days_of_week: vec![],
+			first_day_of_week: Day::default(),
+			index: None,
+			range: RecurrenceRange::default(),
 		}
 	}
 }
 
-impl ToString for Recurrence {
-	fn to_string(&self) -> String {
-		let mut rec = vec![];
-		if self.monday {
-			rec.push(Day::Monday.to_string())
+impl Recurrence {
+	pub fn new(frequency: Frequency) -> Self {
+		let mut recurrence = Self {
+			frequency,
+			..Default::default()
+		};
+		match frequency {
+			Frequency::Daily => (),
+			Frequency::Weekly => recurrence.days_of_week = vec![Day::Monday],
+			Frequency::AbsoluteMonthly => recurrence.day_of_month = 1,
+			Frequency::RelativeMonthly => {
+				recurrence.days_of_week = vec![Day::Monday];
+				recurrence.index = Some(WeekIndex::First);
+			},
+			Frequency::AbsoluteYearly => {
+				recurrence.day_of_month = 1;
+				recurrence.month = 1;
+			},
+			Frequency::RelativeYearly => {
+				recurrence.days_of_week = vec![Day::Monday];
+				recurrence.index = Some(WeekIndex::First);
+				recurrence.month = 1;
+			},
 		}
-		if self.tuesday {
-			rec.push(Day::Tuesday.to_string())
+		recurrence
+	}
+
+	pub fn has_day(&self, day: Day) -> bool {
+		self.days_of_week.contains(&day)
+	}
+
+	pub fn set_day(&mut self, day: Day, active: bool) {
+		if active && !self.has_day(day) {
+			self.days_of_week.push(day);
+		} else if !active {
+			self.days_of_week.retain(|d| *d != day);
 		}
-		if self.wednesday {
-			rec.push(Day::Wednesday.to_string())
+	}
+
+	/// Returns the first date after `from` that matches this recurrence,
+	/// or `None` if the recurrence range has been exhausted.
+	pub fn next_occurrence(&self, from: NaiveDate) -> Option<NaiveDate> {
+		if self.range.range_type == RangeType::Numbered
+			&& self.range.number_of_occurrences <= 1
+		{
+			return None;
 		}
-		if self.thursday {
-			rec.push(Day::Thursday.to_string())
+
+		let interval = self.interval.max(1);
+		let next = match self.frequency {
+			Frequency::Daily => {
+				from.checked_add_signed(Duration::days(interval.into()))
+			},
+			Frequency::Weekly => self.next_weekly(from, interval),
+			Frequency::AbsoluteMonthly => (0..=12).find_map(|step| {
+				let (year, month) =
+					add_months(from.year(), from.month(), step * interval);
+				Some(absolute_day(year, month, self.day_of_month))
+					.filter(|date| *date > from)
+			}),
+			Frequency::RelativeMonthly => (0..=12).find_map(|step| {
+				let (year, month) =
+					add_months(from.year(), from.month(), step * interval);
+				self.relative_day(year, month, from)
+			}),
+			Frequency::AbsoluteYearly => (0..=4).find_map(|step| {
+				let year = from.year() + step * interval;
+				Some(absolute_day(year, self.month_of_year(), self.day_of_month))
+					.filter(|date| *date > from)
+			}),
+			Frequency::RelativeYearly => (0..=4).find_map(|step| {
+				let year = from.year() + step * interval;
+				self.relative_day(year, self.month_of_year(), from)
+			}),
+		}?;
+
+		match self.range.range_type {
+			RangeType::EndDate(end_date) if next > end_date => None,
+			_ => Some(next),
 		}
-		if self.friday {
-			rec.push(Day::Friday.to_string())
+	}
+
+	fn next_weekly(&self, from: NaiveDate, interval: i32) -> Option<NaiveDate> {
+		let first_day: Weekday = self.first_day_of_week.into();
+		let week_start = |date: NaiveDate| {
+			date - Duration::days(days_between(first_day, date.weekday()))
+		};
+		let days: Vec<Weekday> = if self.days_of_week.is_empty() {
+			vec![from.weekday()]
+		} else {
+			self.days_of_week.iter().map(|day| (*day).into()).collect()
+		};
+		(1..=7 * (interval as i64 + 1))
+			.map(|offset| from + Duration::days(offset))
+			.find(|date| {
+				let weeks = (week_start(*date) - week_start(from)).num_weeks();
+				weeks % interval as i64 == 0 && days.contains(&date.weekday())
+			})
+	}
+
+	fn relative_day(
+		&self,
+		year: i32,
+		month: u32,
+		after: NaiveDate,
+	) -> Option<NaiveDate> {
+		let index = self.index.unwrap_or_default();
+		let days: Vec<Weekday> = if self.days_of_week.is_empty() {
+			vec![Weekday::Mon]
+		} else {
+			self.days_of_week.iter().map(|day| (*day).into()).collect()
+		};
+		days
+			.into_iter()
+			.filter_map(|weekday| match index {
+				WeekIndex::Last => {
+					let last = absolute_day(year, month, 31);
+					let back = days_between(weekday, last.weekday());
+					Some(last - Duration::days(back))
+				},
+				_ => NaiveDate::from_weekday_of_month_opt(
+					year,
+					month,
+					weekday,
+					index as u8 + 1,
+				),
+			})
+			.filter(|date| *date > after)
+			.min()
+	}
+
+	fn month_of_year(&self) -> u32 {
+		self.month.clamp(1, 12) as u32
+	}
+}
+
+/// Returns the given day of the month, clamped to the length of the month.
+fn absolute_day(year: i32, month: u32, day: i32) -> NaiveDate {
+	let (next_year, next_month) = add_months(year, month, 1);
+	let last_day = NaiveDate::from_ymd_opt(next_year, next_month, 1)
+		.and_then(|date| date.pred_opt())
+		.map(|date| date.day())
+		.unwrap_or(28);
+	NaiveDate::from_ymd_opt(year, month, (day.max(1) as u32).min(last_day))
+		.expect("Day should be within the month")
+}
+
+/// Number of days from `from` forward to `to` within a week.
+fn days_between(from: Weekday, to: Weekday) -> i64 {
+	let days = to.num_days_from_monday() + 7 - from.num_days_from_monday();
+	(days % 7).into()
+}
+
+fn add_months(year: i32, month: u32, months: i32) -> (i32, u32) {
+	let total = year * 12 + month as i32 - 1 + months;
+	(total.div_euclid(12), total.rem_euclid(12) as u32 + 1)
+}
+
+impl From<DayOfWeek> for Day {
+	fn from(value: DayOfWeek) -> Self {
+		match value {
+			DayOfWeek::Monday => Day::Monday,
+			DayOfWeek::Tuesday => Day::Tuesday,
+			DayOfWeek::Wednesday => Day::Wednesday,
+			DayOfWeek::Thursday => Day::Thursday,
+			DayOfWeek::Friday => Day::Friday,
+			DayOfWeek::Saturday => Day::Saturday,
+			DayOfWeek::Sunday => Day::Sunday,
 		}
-		if self.saturday {
-			rec.push(Day::Saturday.to_string())
+	}
+}
+
+impl From<Day> for DayOfWeek {
+	fn from(value: Day) -> Self {
+		match value {
+			Day::Monday => DayOfWeek::Monday,
+			Day::Tuesday => DayOfWeek::Tuesday,
+			Day::Wednesday => DayOfWeek::Wednesday,
+			Day::Thursday => DayOfWeek::Thursday,
+			Day::Friday => DayOfWeek::Friday,
+			Day::Saturday => DayOfWeek::Saturday,
+			Day::Sunday => DayOfWeek::Sunday,
+		}
+	}
+}
+
+impl From<Day> for Weekday {
+	fn from(value: Day) -> Self {
+		match value {
+			Day::Monday => Weekday::Mon,
+			Day::Tuesday => Weekday::Tue,
+			Day::Wednesday => Weekday::Wed,
+			Day::Thursday => Weekday::Thu,
+			Day::Friday => Weekday::Fri,
+			Day::Saturday => Weekday::Sat,
+			Day::Sunday => Weekday::Sun,
+		}
+	}
+}
+
+impl From<RecurrencePatternType> for Frequency {
+	fn from(value: RecurrencePatternType) -> Self {
+		match value {
+			RecurrencePatternType::Daily => Frequency::Daily,
+			RecurrencePatternType::Weekly => Frequency::Weekly,
+			RecurrencePatternType::AbsoluteMonthly => Frequency::AbsoluteMonthly,
+			RecurrencePatternType::RelativeMonthly => Frequency::RelativeMonthly,
+			RecurrencePatternType::AbsoluteYearly => Frequency::AbsoluteYearly,
+			RecurrencePatternType::RelativeYearly => Frequency::RelativeYearly,
+		}
+	}
+}
+
+impl From<Frequency> for RecurrencePatternType {
+	fn from(value: Frequency) -> Self {
+		match value {
+			Frequency::Daily => RecurrencePatternType::Daily,
+			Frequency::Weekly => RecurrencePatternType::Weekly,
+			Frequency::AbsoluteMonthly => RecurrencePatternType::AbsoluteMonthly,
+			Frequency::RelativeMonthly => RecurrencePatternType::RelativeMonthly,
+			Frequency::AbsoluteYearly => RecurrencePatternType::AbsoluteYearly,
+			Frequency::RelativeYearly => RecurrencePatternType::RelativeYearly,
+		}
+	}
+}
+
+impl From<TodoWeekIndex> for WeekIndex {
+	fn from(value: TodoWeekIndex) -> Self {
+		match value {
+			TodoWeekIndex::First => WeekIndex::First,
+			TodoWeekIndex::Second => WeekIndex::Second,
+			TodoWeekIndex::Third => WeekIndex::Third,
+			TodoWeekIndex::Fourth => WeekIndex::Fourth,
+			TodoWeekIndex::Last => WeekIndex::Last,
+		}
+	}
+}
+
+impl From<WeekIndex> for TodoWeekIndex {
+	fn from(value: WeekIndex) -> Self {
+		match value {
+			WeekIndex::First => TodoWeekIndex::First,
+			WeekIndex::Second => TodoWeekIndex::Second,
+			WeekIndex::Third => TodoWeekIndex::Third,
+			WeekIndex::Fourth => TodoWeekIndex::Fourth,
+			WeekIndex::Last => TodoWeekIndex::Last,
 		}
-		if self.sunday {
-			rec.push(Day::Sunday.to_string())
+	}
+}
+
+impl From<RangeType> for RecurrenceRangeType {
+	fn from(value: RangeType) -> Self {
+		match value {
+			RangeType::EndDate(_) => RecurrenceRangeType::EndDate,
+			RangeType::NoEnd => RecurrenceRangeType::NoEnd,
+			RangeType::Numbered => RecurrenceRangeType::Numbered,
+		}
+	}
+}
+
+impl From<TaskRecurrenceRange> for RecurrenceRange {
+	fn from(value: TaskRecurrenceRange) -> Self {
+		let parse_date = |date: Option<String>| {
+			date.and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok())
+		};
+		let stored_type = match value.recurrence_type {
+			RecurrenceRangeType::EndDate => StoredRangeType::EndDate,
+			RecurrenceRangeType::NoEnd => StoredRangeType::NoEnd,
+			RecurrenceRangeType::Numbered => StoredRangeType::Numbered,
+		};
+		Self {
+			range_type: range_type(stored_type, parse_date(value.end_date)),
+			start_date: parse_date(value.start_date),
+			number_of_occurrences: value.number_of_occurrences,
+			time_zone: value.recurrence_time_zone,
+		}
+	}
+}
+
+impl From<RecurrenceRange> for TaskRecurrenceRange {
+	fn from(value: RecurrenceRange) -> Self {
+		Self {
+			recurrence_type: value.range_type.into(),
+			start_date: value
+				.start_date
+				.map(|date| date.format("%Y-%m-%d").to_string()),
+			end_date: value
+				.end_date()
+				.map(|date| date.format("%Y-%m-%d").to_string()),
+			recurrence_time_zone: value.time_zone,
+			number_of_occurrences: value.number_of_occurrences,
 		}
-		rec.join(", ")
 	}
 }
 
 impl From<TaskRecurrence> for Recurrence {
 	fn from(value: TaskRecurrence) -> Self {
 		Self {
-			monday: value.pattern.days_of_week.contains(&DayOfWeek::Monday),
-			tuesday: value.pattern.days_of_week.contains(&DayOfWeek::Tuesday),
-			wednesday: value.pattern.days_of_week.contains(&DayOfWeek::Wednesday),
-			thursday: value.pattern.days_of_week.contains(&DayOfWeek::Thursday),
-			friday: value.pattern.days_of_week.contains(&DayOfWeek::Friday),
-			saturday: value.pattern.days_of_week.contains(&DayOfWeek::Saturday),
-			sunday: value.pattern.days_of_week.contains(&DayOfWeek::Sunday),
+			frequency: value.pattern.recurrence_pattern_type.into(),
+			interval: value.pattern.interval,
+			month: value.pattern.month,
+			day_of_month: value.pattern.day_of_month,
+			days_of_week: value
+				.pattern
+				.days_of_week
+				.into_iter()
+				.map(|day| day.into())
+				.collect(),
+			first_day_of_week: value.pattern.first_day_of_week.into(),
+			index: value.pattern.index.map(|index| index.into()),
+			range: value.range.into(),
 		}
 	}
 }
+
+impl From<Recurrence> for TaskRecurrence {
+	fn from(value: Recurrence) -> Self {
+		Self {
+			pattern: TaskRecurrencePattern {
+				recurrence_pattern_type: value.frequency.into(),
+				interval: value.interval,
+				month: value.month,
+				day_of_month: value.day_of_month,
+				days_of_week: value
+					.days_of_week
+					.into_iter()
+					.map(|day| day.into())
+					.collect(),
+				first_day_of_week: value.first_day_of_week.into(),
+				index: value.index.map(|index| index.into()),
+			},
+			range: value.range.into(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+		NaiveDate::from_ymd_opt(year, month, day).unwrap()
+	}
+
+	#[test]
+	fn weekly_moves_to_the_next_chosen_day() {
+		let mut recurrence = Recurrence::new(Frequency::Weekly);
+		recurrence.days_of_week = vec![Day::Monday, Day::Wednesday, Day::Friday];
+		// 2024-01-01 is a Monday.
+		assert_eq!(
+			recurrence.next_occurrence(date(2024, 1, 1)),
+			Some(date(2024, 1, 3))
+		);
+		assert_eq!(
+			recurrence.next_occurrence(date(2024, 1, 3)),
+			Some(date(2024, 1, 5))
+		);
+		assert_eq!(
+			recurrence.next_occurrence(date(2024, 1, 5)),
+			Some(date(2024, 1, 8))
+		);
+		assert_eq!(
+			recurrence.next_occurrence(date(2024, 1, 6)),
+			Some(date(2024, 1, 8))
+		);
+	}
+
+	#[test]
+	fn weekly_skips_weeks_with_an_interval() {
+		let mut recurrence = Recurrence::new(Frequency::Weekly);
+		recurrence.interval = 2;
+		recurrence.first_day_of_week = Day::Monday;
+		recurrence.days_of_week = vec![Day::Tuesday, Day::Thursday];
+		assert_eq!(
+			recurrence.next_occurrence(date(2024, 1, 2)),
+			Some(date(2024, 1, 4))
+		);
+		assert_eq!(
+			recurrence.next_occurrence(date(2024, 1, 4)),
+			Some(date(2024, 1, 16))
+		);
+	}
+
+	#[test]
+	fn monthly_on_the_31st_lands_on_the_last_day_of_short_months() {
+		let mut recurrence = Recurrence::new(Frequency::AbsoluteMonthly);
+		recurrence.day_of_month = 31;
+		assert_eq!(
+			recurrence.next_occurrence(date(2024, 1, 31)),
+			Some(date(2024, 2, 29))
+		);
+		assert_eq!(
+			recurrence.next_occurrence(date(2023, 1, 31)),
+			Some(date(2023, 2, 28))
+		);
+		assert_eq!(
+			recurrence.next_occurrence(date(2024, 3, 31)),
+			Some(date(2024, 4, 30))
+		);
+		assert_eq!(
+			recurrence.next_occurrence(date(2024, 4, 30)),
+			Some(date(2024, 5, 31))
+		);
+	}
+
+	#[test]
+	fn end_date_range_stops_after_the_end_date() {
+		let mut recurrence = Recurrence::new(Frequency::Daily);
+		recurrence.range.range_type = RangeType::EndDate(date(2024, 1, 2));
+		assert_eq!(
+			recurrence.next_occurrence(date(2024, 1, 1)),
+			Some(date(2024, 1, 2))
+		);
+		assert_eq!(recurrence.next_occurrence(date(2024, 1, 2)), None);
+	}
+
+	#[test]
+	fn numbered_range_stops_on_the_last_occurrence() {
+		let mut recurrence = Recurrence::new(Frequency::Daily);
+		recurrence.range.range_type = RangeType::Numbered;
+		recurrence.range.number_of_occurrences = 2;
+		assert_eq!(
+			recurrence.next_occurrence(date(2024, 1, 1)),
+			Some(date(2024, 1, 2))
+		);
+		recurrence.range.number_of_occurrences = 1;
+		assert_eq!(recurrence.next_occurrence(date(2024, 1, 2)), None);
+	}
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+use super::{list::List, task::Task};
+
+/// A task list, or a task along with its list, that matched a search.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResult {
+	pub list: List,
+	pub task: Option<Task>,
+}
+
+impl SearchResult {
+	pub fn list(list: List) -> Self {
+		Self { list, task: None }
+	}
+
+	pub fn task(list: List, task: Task) -> Self {
+		Self {
+			list,
+			task: Some(task),
+		}
+	}
+}
@@ -18,6 +18,9 @@ pub enum Status {
 	#[default]
 	NotStarted = 0,
 	Completed = 1,
+	InProgress = 2,
+	Waiting = 3,
+	Deferred = 4,
 }
 
 impl From<i32> for Status {
@@ -25,6 +28,9 @@ impl From<i32> for Status {
 		match value {
 			0 => Self::NotStarted,
 			1 => Status::Completed,
+			2 => Status::InProgress,
+			3 => Status::Waiting,
+			4 => Status::Deferred,
 			_ => panic!("Invalid value for Status"),
 		}
 	}
@@ -35,6 +41,9 @@ impl From<Status> for i32 {
 		match value {
 			Status::NotStarted => 0,
 			Status::Completed => 1,
+			Status::InProgress => 2,
+			Status::Waiting => 3,
+			Status::Deferred => 4,
 		}
 	}
 }
@@ -44,6 +53,9 @@ impl Status {
 		match self {
 			Status::NotStarted => "NOT_STARTED",
 			Status::Completed => "COMPLETED",
+			Status::InProgress => "IN_PROGRESS",
+			Status::Waiting => "WAITING",
+			Status::Deferred => "DEFERRED",
 		}
 	}
 	/// Creates an enum from field names used in the ProtoBuf definition.
@@ -51,6 +63,9 @@ impl Status {
 		match value {
 			"NOT_STARTED" => Some(Self::NotStarted),
 			"COMPLETED" => Some(Self::Completed),
+			"IN_PROGRESS" => Some(Self::InProgress),
+			"WAITING" => Some(Self::Waiting),
+			"DEFERRED" => Some(Self::Deferred),
 			_ => None,
 		}
 	}
@@ -60,10 +75,10 @@ impl From<TaskStatus> for Status {
 	fn from(value: TaskStatus) -> Self {
 		match value {
 			TaskStatus::NotStarted => Self::NotStarted,
-			TaskStatus::Started => Self::NotStarted,
+			TaskStatus::Started => Self::InProgress,
 			TaskStatus::Completed => Self::Completed,
-			TaskStatus::WaitingOnOthers => Self::NotStarted,
-			TaskStatus::Deferred => Self::NotStarted,
+			TaskStatus::WaitingOnOthers => Self::Waiting,
+			TaskStatus::Deferred => Self::Deferred,
 		}
 	}
 }
@@ -73,6 +88,9 @@ impl From<Status> for TaskStatus {
 		match value {
 			Status::NotStarted => TaskStatus::NotStarted,
 			Status::Completed => TaskStatus::Completed,
+			Status::InProgress => TaskStatus::Started,
+			Status::Waiting => TaskStatus::WaitingOnOthers,
+			Status::Deferred => TaskStatus::Deferred,
 		}
 	}
 }
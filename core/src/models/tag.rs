@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tag {
+	pub id: String,
+	pub name: String,
+}
+
+impl Tag {
+	pub fn new(name: &str) -> Self {
+		Self {
+			id: Uuid::new_v4().to_string(),
+			name: name.to_string(),
+		}
+	}
+}
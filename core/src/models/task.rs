@@ -5,11 +5,21 @@ use crate::services::microsoft::models::{
 	checklist_item::ChecklistItem,
 	task::TodoTask,
 };
-use chrono::{DateTime, Utc};
+use crate::{notes, time_zone};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{priority::Priority, recurrence::Recurrence, status::Status};
+use super::{
+	attachment::Attachment,
+	dependency::Dependency,
+	linked_resource::LinkedResource,
+	priority::Priority,
+	recurrence::{RangeType, Recurrence},
+	status::Status,
+	time_entry::TimeEntry,
+};
 
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Task {
@@ -22,12 +32,24 @@ pub struct Task {
 	pub priority: Priority,
 	pub sub_tasks: Vec<Task>,
 	pub tags: Vec<String>,
+	pub attachments: Vec<Attachment>,
+	pub linked_resources: Vec<LinkedResource>,
+	/// The tasks that have to be completed before this one can start.
+	pub blocked_by: Vec<Dependency>,
+	/// Notes in Markdown.
 	pub notes: Option<String>,
+	/// The HTML body the notes were converted from, sent back as is while the
+	/// notes are left alone so formatting Markdown can't express survives.
+	pub notes_html: Option<String>,
 	pub completion_date: Option<DateTime<Utc>>,
 	pub deletion_date: Option<DateTime<Utc>>,
+	pub start_date: Option<DateTime<Utc>>,
 	pub due_date: Option<DateTime<Utc>>,
 	pub reminder_date: Option<DateTime<Utc>>,
-	pub recurrence: Recurrence,
+	pub recurrence: Option<Recurrence>,
+	/// How long the task is expected to take, in minutes.
+	pub estimated_minutes: Option<i64>,
+	pub time_entries: Vec<TimeEntry>,
 	pub created_date_time: DateTime<Utc>,
 	pub last_modified_date_time: DateTime<Utc>,
 }
@@ -45,14 +67,160 @@ impl Task {
 			priority: Priority::Low,
 			sub_tasks: vec![],
 			tags: vec![],
+			attachments: vec![],
+			linked_resources: vec![],
+			blocked_by: vec![],
 			notes: None,
+			notes_html: None,
 			completion_date: None,
 			deletion_date: None,
+			start_date: None,
 			due_date: None,
 			reminder_date: None,
-			recurrence: Default::default(),
+			recurrence: None,
+			estimated_minutes: None,
+			time_entries: vec![],
+			created_date_time: now,
+			last_modified_date_time: now,
+		}
+	}
+
+	/// Whether the task can be worked on, that is, its start date, if any,
+	/// has been reached.
+	pub fn actionable(&self) -> bool {
+		self.start_date.map_or(true, |date| date <= Utc::now())
+	}
+
+	/// Whether a task it depends on is still to be completed.
+	pub fn is_blocked(&self) -> bool {
+		self
+			.blocked_by
+			.iter()
+			.any(|dependency| !dependency.completed)
+	}
+
+	/// The time spent on the task, including a running timer.
+	pub fn tracked_time(&self) -> Duration {
+		self
+			.time_entries
+			.iter()
+			.fold(Duration::zero(), |total, entry| total + entry.duration())
+	}
+
+	pub fn is_tracking(&self) -> bool {
+		self.time_entries.iter().any(|entry| entry.end.is_none())
+	}
+
+	pub fn start_timer(&mut self) {
+		if !self.is_tracking() {
+			self.time_entries.push(TimeEntry::new());
+		}
+	}
+
+	pub fn stop_timer(&mut self) {
+		let now = Utc::now();
+		self
+			.time_entries
+			.iter_mut()
+			.filter(|entry| entry.end.is_none())
+			.for_each(|entry| entry.end = Some(now));
+	}
+
+	/// Makes the change from `from` to `to` on the task as it is now. Only
+	/// the fields that differ between them are set, so undoing a change keeps
+	/// the ones made to the task since. Attachments and dependencies aren't
+	/// saved along with the task and are left alone.
+	pub fn apply_change(&mut self, from: &Task, to: &Task) {
+		macro_rules! apply {
+			($($field:ident),*) => {
+				$(
+					if from.$field != to.$field {
+						self.$field = to.$field.clone();
+					}
+				)*
+			};
+		}
+		apply!(
+			parent,
+			title,
+			favorite,
+			today,
+			status,
+			priority,
+			sub_tasks,
+			tags,
+			linked_resources,
+			notes,
+			notes_html,
+			completion_date,
+			deletion_date,
+			start_date,
+			due_date,
+			reminder_date,
+			recurrence,
+			estimated_minutes,
+			time_entries
+		);
+	}
+
+	/// Builds the next instance of a recurring task, with its dates moved to
+	/// the next occurrence and its sub-tasks reset.
+	pub fn next_occurrence(&self) -> Option<Task> {
+		self.next_occurrence_in(time_zone::user_time_zone())
+	}
+
+	/// The rules go by the days the dates fall on in `zone`, and the dates
+	/// keep their time of day there.
+	fn next_occurrence_in(&self, zone: Tz) -> Option<Task> {
+		let mut recurrence = self.recurrence.clone()?;
+		let anchor = self
+			.due_date
+			.or(self.reminder_date)
+			.unwrap_or_else(Utc::now);
+		let anchor_date = anchor.with_timezone(&zone).date_naive();
+		let next_date = recurrence.next_occurrence(anchor_date)?;
+		let offset = next_date - anchor_date;
+		let shift = |date: DateTime<Utc>| {
+			time_zone::from_local(
+				date.with_timezone(&zone).naive_local() + offset,
+				zone,
+			)
+		};
+		if recurrence.range.range_type == RangeType::Numbered {
+			recurrence.range.number_of_occurrences -= 1;
+		}
+		let now = Utc::now();
+		Some(Task {
+			attachments: vec![],
+			linked_resources: self
+				.linked_resources
+				.iter()
+				.map(|resource| LinkedResource {
+					id: Uuid::new_v4().to_string(),
+					..resource.clone()
+				})
+				.collect(),
+			blocked_by: vec![],
+			time_entries: vec![],
+			deletion_date: None,
+			start_date: self.start_date.map(shift),
+			due_date: Some(shift(self.due_date.unwrap_or(anchor))),
+			reminder_date: self.reminder_date.map(shift),
+			recurrence: Some(recurrence),
 			created_date_time: now,
 			last_modified_date_time: now,
+			..self.reset()
+		})
+	}
+
+	/// A copy of the task and its sub-tasks, all with new ids and not started.
+	fn reset(&self) -> Task {
+		Task {
+			id: Uuid::new_v4().to_string(),
+			status: Status::NotStarted,
+			completion_date: None,
+			sub_tasks: self.sub_tasks.iter().map(Task::reset).collect(),
+			..self.clone()
 		}
 	}
 }
@@ -76,13 +244,30 @@ impl From<TodoTask> for Task {
 				.iter()
 				.map(|item| item.clone().into())
 				.collect(),
-			tags: vec![],
-			notes: Some(task.body.content),
+			tags: task.categories,
+			attachments: vec![],
+			linked_resources: task
+				.linked_resources
+				.unwrap_or_default()
+				.into_iter()
+				.map(|resource| resource.into())
+				.collect(),
+			blocked_by: vec![],
+			notes: match task.body.content_type {
+				BodyType::Html => Some(notes::html_to_markdown(&task.body.content)),
+				BodyType::Text => Some(task.body.content.clone()),
+			}
+			.filter(|notes| !notes.is_empty()),
+			notes_html: (task.body.content_type == BodyType::Html)
+				.then_some(task.body.content),
 			completion_date: task.completed_date_time.map(|date| date.into()),
 			deletion_date: None,
+			start_date: task.start_date_time.map(|date| date.into()),
 			due_date: task.due_date_time.map(|date| date.into()),
 			reminder_date: task.reminder_date_time.map(|date| date.into()),
-			recurrence: task.recurrence.unwrap_or_default().into(),
+			recurrence: task.recurrence.map(|recurrence| recurrence.into()),
+			estimated_minutes: None,
+			time_entries: vec![],
 			created_date_time: DateTime::<Utc>::from_str(&task.created_date_time)
 				.unwrap(),
 			last_modified_date_time: DateTime::<Utc>::from_str(
@@ -99,20 +284,44 @@ impl From<Task> for TodoTask {
 			task.sub_tasks.iter().map(|t| t.to_owned().into()).collect();
 		Self {
 			id: task.id,
-			body: ItemBody {
-				content: task.notes.unwrap_or_default(),
-				content_type: BodyType::Text,
+			body: match (task.notes, task.notes_html) {
+				(Some(notes), Some(html))
+					if notes::html_to_markdown(&html) == notes =>
+				{
+					ItemBody {
+						content: html,
+						content_type: BodyType::Html,
+					}
+				},
+				(Some(notes), _) => ItemBody {
+					content: notes::markdown_to_html(&notes),
+					content_type: BodyType::Html,
+				},
+				(None, _) => ItemBody::default(),
 			},
-			categories: vec![],
+			categories: task.tags,
 			completed_date_time: task.completion_date.map(|date| date.into()),
 			due_date_time: task.due_date.map(|date| date.into()),
 			importance: task.priority.into(),
 			is_reminder_on: task.reminder_date.is_some(),
-			recurrence: Default::default(),
+			recurrence: task.recurrence.map(|mut recurrence| {
+				// Graph rejects a recurrence range without a start date.
+				recurrence.range.start_date.get_or_insert_with(|| {
+					time_zone::local_date(task.due_date.unwrap_or_else(Utc::now))
+				});
+				recurrence.into()
+			}),
 			title: task.title,
 			status: task.status.into(),
-			has_attachments: false,
+			has_attachments: !task.attachments.is_empty(),
 			checklist_items: Some(checklist_items),
+			linked_resources: Some(
+				task
+					.linked_resources
+					.into_iter()
+					.map(|resource| resource.into())
+					.collect(),
+			),
 			created_date_time: task
 				.created_date_time
 				.format("%Y-%m-%dT%H:%M:%S%.fZ")
@@ -122,7 +331,7 @@ impl From<Task> for TodoTask {
 				.format("%Y-%m-%dT%H:%M:%S%.fZ")
 				.to_string(),
 			reminder_date_time: task.reminder_date.map(|date| date.into()),
-			start_date_time: None,
+			start_date_time: task.start_date.map(|date| date.into()),
 		}
 	}
 }
@@ -157,3 +366,129 @@ impl From<Task> for ChecklistItem {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use chrono::TimeZone;
+
+	use super::*;
+	use crate::models::recurrence::{Day, Frequency};
+
+	fn recurring_task(recurrence: Recurrence) -> Task {
+		let mut task = Task::new("Water the plants".into(), "list".into());
+		task.status = Status::Completed;
+		task.completion_date = Some(Utc::now());
+		task.due_date = Some(Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap());
+		task.recurrence = Some(recurrence);
+		task
+	}
+
+	#[test]
+	fn next_occurrence_moves_the_dates_and_resets_sub_tasks() {
+		let mut task = recurring_task(Recurrence::new(Frequency::Daily));
+		task.reminder_date =
+			Some(Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap());
+		let mut step = Task::new("Open the tap".into(), "list".into());
+		step.status = Status::Completed;
+		step.completion_date = Some(Utc::now());
+		let mut sub_task = Task::new("Fill the can".into(), "list".into());
+		sub_task.status = Status::Completed;
+		sub_task.completion_date = Some(Utc::now());
+		sub_task.sub_tasks = vec![step.clone()];
+		task.sub_tasks = vec![sub_task.clone()];
+
+		let next = task.next_occurrence_in(Tz::UTC).unwrap();
+		assert_ne!(next.id, task.id);
+		assert_eq!(next.status, Status::NotStarted);
+		assert_eq!(next.completion_date, None);
+		assert_eq!(
+			next.due_date,
+			Some(Utc.with_ymd_and_hms(2024, 1, 2, 9, 0, 0).unwrap())
+		);
+		assert_eq!(
+			next.reminder_date,
+			Some(Utc.with_ymd_and_hms(2024, 1, 2, 8, 0, 0).unwrap())
+		);
+		assert_eq!(next.sub_tasks.len(), 1);
+		assert_ne!(next.sub_tasks[0].id, sub_task.id);
+		assert_eq!(next.sub_tasks[0].title, sub_task.title);
+		assert_eq!(next.sub_tasks[0].status, Status::NotStarted);
+		assert_eq!(next.sub_tasks[0].completion_date, None);
+		let next_step = &next.sub_tasks[0].sub_tasks[0];
+		assert_ne!(next_step.id, step.id);
+		assert_eq!(next_step.title, step.title);
+		assert_eq!(next_step.status, Status::NotStarted);
+		assert_eq!(next_step.completion_date, None);
+	}
+
+	#[test]
+	fn next_occurrence_follows_the_local_day() {
+		// 20:00 UTC on Monday is 09:00 on Tuesday in Auckland.
+		let mut recurrence = Recurrence::new(Frequency::Weekly);
+		recurrence.days_of_week = vec![Day::Tuesday];
+		let mut task = recurring_task(recurrence);
+		task.due_date = Some(Utc.with_ymd_and_hms(2024, 1, 1, 20, 0, 0).unwrap());
+
+		let next = task.next_occurrence_in(Tz::Pacific__Auckland).unwrap();
+		assert_eq!(
+			next.due_date,
+			Some(Utc.with_ymd_and_hms(2024, 1, 8, 20, 0, 0).unwrap())
+		);
+
+		let mut recurrence = Recurrence::new(Frequency::AbsoluteMonthly);
+		recurrence.day_of_month = 2;
+		task.recurrence = Some(recurrence);
+		let next = task.next_occurrence_in(Tz::Pacific__Auckland).unwrap();
+		assert_eq!(
+			next.due_date,
+			Some(Utc.with_ymd_and_hms(2024, 2, 1, 20, 0, 0).unwrap())
+		);
+	}
+
+	#[test]
+	fn next_occurrence_counts_down_numbered_ranges() {
+		let mut recurrence = Recurrence::new(Frequency::Daily);
+		recurrence.range.range_type = RangeType::Numbered;
+		recurrence.range.number_of_occurrences = 2;
+		let task = recurring_task(recurrence);
+
+		let next = task.next_occurrence_in(Tz::UTC).unwrap();
+		assert_eq!(
+			next
+				.recurrence
+				.as_ref()
+				.unwrap()
+				.range
+				.number_of_occurrences,
+			1
+		);
+		assert!(next.next_occurrence_in(Tz::UTC).is_none());
+	}
+
+	#[test]
+	fn next_occurrence_ends_with_the_end_date() {
+		let mut recurrence = Recurrence::new(Frequency::Weekly);
+		recurrence.range.range_type =
+			RangeType::EndDate(chrono::NaiveDate::from_ymd_opt(2024, 1, 5).unwrap());
+		let task = recurring_task(recurrence);
+		assert!(task.next_occurrence_in(Tz::UTC).is_none());
+	}
+
+	#[test]
+	fn apply_change_only_sets_the_fields_that_changed() {
+		let before = Task::new("Water the plants".into(), "list".into());
+		let mut after = before.clone();
+		after.status = Status::Completed;
+		after.completion_date = Some(Utc::now());
+
+		let mut current = after.clone();
+		current.title = "Water the garden".into();
+		current.priority = Priority::High;
+		current.apply_change(&after, &before);
+
+		assert_eq!(current.status, Status::NotStarted);
+		assert_eq!(current.completion_date, None);
+		assert_eq!(current.title, "Water the garden");
+		assert_eq!(current.priority, Priority::High);
+	}
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use strum_macros::{AsRefStr, EnumString};
+
+/// The fields of a task whose changes are kept in its history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskField {
+	Title,
+	Notes,
+	Status,
+	Priority,
+	Favorite,
+	Today,
+	DueDate,
+	ReminderDate,
+	StartDate,
+	Recurrence,
+	/// The list the task is in.
+	Parent,
+	DeletionDate,
+	EstimatedMinutes,
+}
+
+/// A single field of a task that was changed, with the values it had before
+/// and after, as they are stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskChange {
+	pub id: String,
+	pub task_id: String,
+	pub field: TaskField,
+	pub old_value: Option<String>,
+	pub new_value: Option<String>,
+	pub changed_date_time: DateTime<Utc>,
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A span of time spent on a task, still running while it has no end.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeEntry {
+	pub id: String,
+	pub start: DateTime<Utc>,
+	pub end: Option<DateTime<Utc>>,
+}
+
+impl TimeEntry {
+	pub fn new() -> Self {
+		Self {
+			id: Uuid::new_v4().to_string(),
+			start: Utc::now(),
+			end: None,
+		}
+	}
+
+	pub fn duration(&self) -> Duration {
+		self.end.unwrap_or_else(Utc::now) - self.start
+	}
+}
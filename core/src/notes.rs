@@ -0,0 +1,121 @@
+use pulldown_cmark::{html, Event, Options, Parser, Tag};
+use regex::Regex;
+
+/// Notes are written in Markdown, these are the extensions understood on
+/// top of CommonMark.
+fn options() -> Options {
+	Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS
+}
+
+/// Renders Markdown notes as HTML, the way Graph stores rich task bodies.
+pub fn markdown_to_html(markdown: &str) -> String {
+	// Markup typed in the notes is text, not HTML to pass through.
+	let events = Parser::new_ext(markdown, options()).map(|event| match event {
+		Event::Html(html) => Event::Text(html),
+		event => event,
+	});
+	let mut output = String::new();
+	html::push_html(&mut output, events);
+	output
+}
+
+/// Turns an HTML task body, like the ones written by Outlook, into Markdown.
+pub fn html_to_markdown(html: &str) -> String {
+	let head = Regex::new(r"(?is)<head.*?</head>|<style.*?</style>").unwrap();
+	html2md::parse_html(&head.replace_all(html, ""))
+		.trim()
+		.to_string()
+}
+
+/// Renders Markdown notes as Pango markup so they can be shown in a label.
+/// Pango has no block elements, headings, lists and quotes are spelled out
+/// with line breaks, bullets and indentation.
+pub fn markdown_to_markup(markdown: &str) -> String {
+	let mut markup = String::new();
+	let mut lists: Vec<Option<u64>> = vec![];
+	for event in Parser::new_ext(markdown, options()) {
+		match event {
+			Event::Start(tag) => match tag {
+				Tag::Heading(..) => {
+					markup.push_str("<span weight=\"bold\" size=\"large\">")
+				},
+				Tag::BlockQuote => markup.push_str("<i>"),
+				Tag::CodeBlock(_) => markup.push_str("<tt>"),
+				Tag::List(start) => {
+					if !lists.is_empty() && !markup.ends_with('\n') {
+						markup.push('\n');
+					}
+					lists.push(start)
+				},
+				Tag::Item => {
+					markup.push_str(&"    ".repeat(lists.len().saturating_sub(1)));
+					match lists.last_mut() {
+						Some(Some(number)) => {
+							markup.push_str(&format!("{number}. "));
+							*number += 1;
+						},
+						_ => markup.push_str("• "),
+					}
+				},
+				Tag::Emphasis => markup.push_str("<i>"),
+				Tag::Strong => markup.push_str("<b>"),
+				Tag::Strikethrough => markup.push_str("<s>"),
+				Tag::Link(_, url, _) => {
+					markup.push_str(&format!("<a href=\"{}\">", escape(&url)))
+				},
+				Tag::Paragraph
+				| Tag::FootnoteDefinition(_)
+				| Tag::Table(_)
+				| Tag::TableHead
+				| Tag::TableRow
+				| Tag::TableCell
+				| Tag::Image(..) => {},
+			},
+			Event::End(tag) => match tag {
+				Tag::Heading(..) => markup.push_str("</span>\n\n"),
+				Tag::Paragraph if lists.is_empty() => markup.push_str("\n\n"),
+				Tag::Paragraph => markup.push('\n'),
+				Tag::BlockQuote => markup.push_str("</i>"),
+				Tag::CodeBlock(_) => markup.push_str("</tt>\n"),
+				Tag::List(_) => {
+					lists.pop();
+					if lists.is_empty() {
+						markup.push('\n');
+					}
+				},
+				Tag::Item => {
+					if !markup.ends_with('\n') {
+						markup.push('\n');
+					}
+				},
+				Tag::Emphasis => markup.push_str("</i>"),
+				Tag::Strong => markup.push_str("</b>"),
+				Tag::Strikethrough => markup.push_str("</s>"),
+				Tag::Link(..) => markup.push_str("</a>"),
+				_ => {},
+			},
+			Event::Text(text) | Event::Html(text) => markup.push_str(&escape(&text)),
+			Event::Code(code) => {
+				markup.push_str(&format!("<tt>{}</tt>", escape(&code)))
+			},
+			Event::TaskListMarker(done) => {
+				markup.push_str(if done { "☑ " } else { "☐ " })
+			},
+			Event::SoftBreak | Event::HardBreak => markup.push('\n'),
+			Event::Rule => markup.push_str("――――――――\n\n"),
+			Event::FootnoteReference(reference) => {
+				markup.push_str(&format!("[{}]", escape(&reference)))
+			},
+		}
+	}
+	markup.trim_end().to_string()
+}
+
+fn escape(text: &str) -> String {
+	text
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+		.replace('\'', "&apos;")
+}
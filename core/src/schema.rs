@@ -1,11 +1,47 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+		attachments (id_attachment) {
+				id_attachment -> Text,
+				id_task -> Text,
+				name -> Text,
+				content_type -> Text,
+				size -> BigInt,
+				created_date_time -> Timestamp,
+		}
+}
+
 diesel::table! {
 		lists (id_list) {
 				id_list -> Text,
 				name -> Text,
 				description -> Text,
 				icon_name -> Nullable<Text>,
+				deletion_date -> Nullable<Timestamp>,
+				id_group -> Nullable<Text>,
+				color -> Nullable<Text>,
+				sort_order -> Integer,
+				show_completed -> Bool,
+				grouping -> Integer,
+		}
+}
+
+diesel::table! {
+		linked_resources (id_linked_resource) {
+				id_linked_resource -> Text,
+				id_task -> Text,
+				web_url -> Text,
+				display_name -> Nullable<Text>,
+				application_name -> Nullable<Text>,
+				position -> Integer,
+		}
+}
+
+diesel::table! {
+		list_groups (id_group) {
+				id_group -> Text,
+				name -> Text,
+				service -> Text,
 		}
 }
 
@@ -18,17 +54,107 @@ diesel::table! {
 				today -> Bool,
 				status -> Integer,
 				priority -> Integer,
-				sub_tasks -> Text,
-				tags -> Text,
 				notes -> Nullable<Text>,
 				completion_date -> Nullable<Timestamp>,
 				deletion_date -> Nullable<Timestamp>,
 				due_date -> Nullable<Timestamp>,
 				reminder_date -> Nullable<Timestamp>,
-				recurrence -> Text,
+				recurrence -> Nullable<Text>,
 				created_date_time -> Timestamp,
 				last_modified_date_time -> Timestamp,
+				parent_task -> Nullable<Text>,
+				position -> Integer,
+				start_date -> Nullable<Timestamp>,
+				estimated_minutes -> Nullable<BigInt>,
+		}
+}
+
+diesel::table! {
+		remote_list_groups (id_list) {
+				id_list -> Text,
+				id_group -> Text,
+		}
+}
+
+diesel::table! {
+		remote_list_settings (id_list) {
+				id_list -> Text,
+				color -> Nullable<Text>,
+				sort_order -> Integer,
+				show_completed -> Bool,
+				grouping -> Integer,
+		}
+}
+
+diesel::table! {
+		remote_task_positions (id_task) {
+				id_task -> Text,
+				id_list -> Text,
+				position -> Integer,
+		}
+}
+
+diesel::table! {
+		tags (id_tag) {
+				id_tag -> Text,
+				name -> Text,
+		}
+}
+
+diesel::table! {
+		task_dependencies (id_task, id_blocking_task) {
+				id_task -> Text,
+				id_blocking_task -> Text,
+		}
+}
+
+diesel::table! {
+		task_history (id_change) {
+				id_change -> Text,
+				id_task -> Text,
+				field -> Text,
+				old_value -> Nullable<Text>,
+				new_value -> Nullable<Text>,
+				changed_date_time -> Timestamp,
 		}
 }
 
-diesel::allow_tables_to_appear_in_same_query!(lists, tasks,);
+diesel::table! {
+		task_tags (id_task, id_tag) {
+				id_task -> Text,
+				id_tag -> Text,
+		}
+}
+
+diesel::table! {
+		time_entries (id_entry) {
+				id_entry -> Text,
+				id_task -> Text,
+				start_date -> Timestamp,
+				end_date -> Nullable<Timestamp>,
+		}
+}
+
+diesel::joinable!(attachments -> tasks (id_task));
+diesel::joinable!(linked_resources -> tasks (id_task));
+diesel::joinable!(task_dependencies -> tasks (id_blocking_task));
+diesel::joinable!(task_history -> tasks (id_task));
+diesel::joinable!(task_tags -> tags (id_tag));
+diesel::joinable!(task_tags -> tasks (id_task));
+diesel::joinable!(time_entries -> tasks (id_task));
+
+diesel::allow_tables_to_appear_in_same_query!(
+	attachments,
+	linked_resources,
+	list_groups,
+	lists,
+	remote_list_groups,
+	remote_list_settings,
+	remote_task_positions,
+	tags,
+	task_dependencies,
+	task_history,
+	task_tags,
+	tasks,
+	time_entries,
+);
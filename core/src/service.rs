@@ -7,13 +7,14 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
-use strum_macros::{EnumIter, EnumString};
+use strum_macros::{AsRefStr, EnumIter, EnumString};
 
 #[derive(
 	Debug,
 	Default,
 	EnumIter,
 	EnumString,
+	AsRefStr,
 	Clone,
 	Copy,
 	PartialEq,
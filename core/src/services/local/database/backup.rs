@@ -0,0 +1,117 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use diesel::{sql_query, sql_types::Text, RunQueryDsl};
+
+use super::Database;
+
+/// How many snapshots are kept, older ones are removed.
+const BACKUP_COUNT: usize = 10;
+
+/// A snapshot of the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+	pub path: PathBuf,
+	pub created: DateTime<Utc>,
+	pub size: u64,
+}
+
+impl Backup {
+	fn read(path: PathBuf) -> Result<Self> {
+		let metadata = fs::metadata(&path)?;
+		Ok(Self {
+			created: metadata.modified()?.into(),
+			size: metadata.len(),
+			path,
+		})
+	}
+}
+
+impl Database {
+	/// Snapshots are kept in a folder next to the database.
+	pub fn backup_dir() -> Result<PathBuf> {
+		let database_path = Self::database_path()?;
+		let folder = database_path
+			.parent()
+			.context("The database is not in a folder")?
+			.join("backups");
+		fs::create_dir_all(&folder)?;
+		Ok(folder)
+	}
+
+	/// The snapshots of the database, newest first.
+	pub fn backups() -> Result<Vec<Backup>> {
+		let mut paths: Vec<PathBuf> = fs::read_dir(Self::backup_dir()?)?
+			.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+			.filter(|path| path.extension().is_some_and(|ext| ext == "db"))
+			.collect();
+		paths.sort();
+		paths.into_iter().rev().map(Backup::read).collect()
+	}
+
+	/// Writes a consistent copy of the database and removes the oldest
+	/// snapshots past the ones that are kept.
+	pub fn create_backup() -> Result<Backup> {
+		let database_path = Self::database_path()?;
+		let name = database_path
+			.file_stem()
+			.and_then(|stem| stem.to_str())
+			.unwrap_or("database");
+		let path = Self::backup_dir()?.join(format!(
+			"{name}-{}.db",
+			Utc::now().format("%Y%m%d-%H%M%S-%3f")
+		));
+
+		// A database that can't be opened is still copied as it is, it might
+		// be what the user needs to recover.
+		let vacuum = Self::establish_connection().and_then(|mut connection| {
+			sql_query("VACUUM INTO ?")
+				.bind::<Text, _>(path.display().to_string())
+				.execute(&mut connection)
+				.map_err(anyhow::Error::from)
+		});
+		if let Err(err) = vacuum {
+			tracing::error!("Failed to snapshot the database: {err}");
+			fs::copy(&database_path, &path).context("Failed to copy the database")?;
+		}
+
+		for backup in Self::backups()?.iter().skip(BACKUP_COUNT) {
+			fs::remove_file(&backup.path)?;
+		}
+
+		Backup::read(path)
+	}
+
+	/// Takes a snapshot if the newest one is older than `interval`.
+	pub fn backup_if_due(interval: Duration) -> Result<Option<Backup>> {
+		let due = match Self::backups()?.first() {
+			Some(newest) => newest.created + interval <= Utc::now(),
+			None => true,
+		};
+		if due {
+			return Self::create_backup().map(Some);
+		}
+		Ok(None)
+	}
+
+	/// Replaces the database with a snapshot. The current database is saved
+	/// as a snapshot first, so a restore can be undone.
+	pub fn restore_backup(backup: &Backup) -> Result<()> {
+		Self::open_file(&backup.path, Self::encryption_key()?.as_deref())
+			.context("The backup can't be opened with the current encryption key")?;
+		let content =
+			fs::read(&backup.path).context("Failed to read the backup")?;
+		if let Err(err) = Self::create_backup() {
+			tracing::error!("Failed to back up the current database: {err}");
+		}
+
+		Self::close();
+		let database_path = Self::database_path()?;
+		Self::remove_journal(&database_path)?;
+		fs::write(&database_path, content)
+			.context("Failed to write the database")?;
+
+		Self::ensure_migrations_up_to_date()
+	}
+}
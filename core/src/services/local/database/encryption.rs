@@ -0,0 +1,160 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use diesel::{
+	connection::SimpleConnection, sql_query, sql_types::Text, RunQueryDsl,
+};
+use uuid::Uuid;
+
+use super::{Database, DATABASE_PATH};
+use crate::services::microsoft::service::APP_ID;
+
+/// The keyring entry that holds the key of the database in the default
+/// location.
+const KEY_ACCOUNT: &str = "database_key";
+
+/// The keyring entry that holds the key of the database at `path`, `None`
+/// being the default location.
+fn key_account(path: Option<&Path>) -> String {
+	match path {
+		Some(path) => format!("{KEY_ACCOUNT}:{}", path.display()),
+		None => KEY_ACCOUNT.to_string(),
+	}
+}
+
+/// The entry for the database in use.
+fn current_key_account() -> String {
+	key_account(DATABASE_PATH.read().unwrap().as_deref())
+}
+
+/// Reads a key from the keyring, `None` if there is none. A keyring that
+/// can't be reached is an error rather than a database that isn't encrypted,
+/// opening it without its key would fail anyway.
+fn read_key(account: &str) -> Result<Option<String>> {
+	let key = keytar::get_password(APP_ID, account)
+		.context("Failed to read the key from the keyring")?;
+	Ok(Some(key.password).filter(|key| !key.is_empty()))
+}
+
+impl Database {
+	/// The key the database is encrypted with, `None` if it isn't.
+	pub(super) fn encryption_key() -> Result<Option<String>> {
+		read_key(&current_key_account())
+	}
+
+	/// Gives a copy of a database the key of the original.
+	pub(super) fn copy_key(from: Option<&Path>, to: &Path) -> Result<()> {
+		match read_key(&key_account(from))? {
+			Some(key) => keytar::set_password(APP_ID, &key_account(Some(to)), &key)
+				.context("Failed to store the key in the keyring"),
+			None => Ok(()),
+		}
+	}
+
+	pub fn encrypted() -> Result<bool> {
+		Ok(Self::encryption_key()?.is_some())
+	}
+
+	/// Encrypts the database, and its backups, with a new key stored in the
+	/// system keyring.
+	pub fn encrypt() -> Result<()> {
+		if Self::encrypted()? {
+			return Ok(());
+		}
+		let key = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+		Self::close();
+		let database_path = Self::database_path()?;
+		let converted = Self::convert(&database_path, None, Some(&key))
+			.context("Failed to encrypt the database")?;
+		// The key is stored before the database is replaced, losing it would
+		// mean losing every task.
+		if let Err(err) = keytar::set_password(APP_ID, &current_key_account(), &key)
+		{
+			fs::remove_file(&converted)?;
+			return Err(err).context("Failed to store the key in the keyring");
+		}
+		Self::replace(&database_path, &converted)?;
+		Self::convert_backups(None, Some(&key));
+		Ok(())
+	}
+
+	/// Decrypts the database and its backups, then forgets the key.
+	pub fn decrypt() -> Result<()> {
+		let Some(key) = Self::encryption_key()? else {
+			return Ok(());
+		};
+		Self::close();
+		let database_path = Self::database_path()?;
+		let converted = Self::convert(&database_path, Some(&key), None)
+			.context("Failed to decrypt the database")?;
+		Self::replace(&database_path, &converted)?;
+		keytar::delete_password(APP_ID, &current_key_account())
+			.context("Failed to remove the key from the keyring")?;
+		Self::convert_backups(Some(&key), None);
+		Ok(())
+	}
+
+	/// Writes a copy of the database encrypted with `to`, or in plain text if
+	/// it is `None`, next to it and returns its path.
+	fn convert(
+		path: &Path,
+		from: Option<&str>,
+		to: Option<&str>,
+	) -> Result<PathBuf> {
+		let mut converted = path.to_path_buf().into_os_string();
+		converted.push(".converted");
+		let converted = PathBuf::from(converted);
+		if converted.exists() {
+			fs::remove_file(&converted)?;
+		}
+
+		let mut connection = Self::open_file(path, from)?;
+		sql_query("ATTACH DATABASE ? AS converted KEY ?")
+			.bind::<Text, _>(converted.display().to_string())
+			.bind::<Text, _>(to.map(|key| format!("x'{key}'")).unwrap_or_default())
+			.execute(&mut connection)?;
+		connection.batch_execute(
+			"SELECT sqlcipher_export('converted');
+			DETACH DATABASE converted;",
+		)?;
+		Ok(converted)
+	}
+
+	/// Swaps a database for its converted copy.
+	fn replace(path: &Path, converted: &Path) -> Result<()> {
+		Self::remove_journal(path)?;
+		fs::rename(converted, path).context("Failed to replace the database")
+	}
+
+	/// Backups share the key of the database, so that they can be restored.
+	fn convert_backups(from: Option<&str>, to: Option<&str>) {
+		let backups = match Self::backups() {
+			Ok(backups) => backups,
+			Err(err) => {
+				tracing::error!("Failed to read the backups: {err}");
+				return;
+			},
+		};
+		for backup in backups {
+			let converted =
+				Self::convert(&backup.path, from, to).and_then(|converted| {
+					Self::replace(&backup.path, &converted)?;
+					// The date of a backup is read from the file.
+					fs::File::options()
+						.write(true)
+						.open(&backup.path)?
+						.set_modified(backup.created.into())?;
+					Ok(())
+				});
+			if let Err(err) = converted {
+				tracing::error!(
+					"Failed to convert the backup {}: {err}",
+					backup.path.display()
+				);
+			}
+		}
+	}
+}
@@ -1,7 +1,23 @@
+pub mod backup;
+#[cfg(feature = "encryption")]
+pub mod encryption;
 pub mod models;
 
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	sync::RwLock,
+	time::Duration,
+};
+
 use anyhow::{anyhow, Context, Result};
-use diesel::{Connection, SqliteConnection};
+use diesel::{
+	connection::SimpleConnection,
+	r2d2::{ConnectionManager, CustomizeConnection, Pool, PooledConnection},
+	sql_query,
+	sql_types::BigInt,
+	Connection, QueryableByName, RunQueryDsl, SqliteConnection,
+};
 use diesel_migrations::{
 	embed_migrations, EmbeddedMigrations, MigrationHarness,
 };
@@ -12,30 +28,249 @@ use crate::services::microsoft::service::APP_ID;
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
 pub const DATABASE_NAME: &str = "dev.edfloreshz.Done.db";
 
+/// How long a query waits for another connection to finish writing before
+/// giving up with `SQLITE_BUSY`.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The version of the migration that adds foreign keys and checks.
+const CONSTRAINTS_MIGRATION: &str = "20261018220000";
+
+/// SQLite allows a single writer at a time, a handful of connections is
+/// enough for the readers that run alongside it.
+const POOL_SIZE: u32 = 4;
+
+type ConnectionPool = Pool<ConnectionManager<SqliteConnection>>;
+pub type DatabaseConnection =
+	PooledConnection<ConnectionManager<SqliteConnection>>;
+
+/// Opened on first use and shared by every service afterwards.
+static POOL: RwLock<Option<ConnectionPool>> = RwLock::new(None);
+
+/// Overrides the location of the database, `None` uses the app data folder.
+static DATABASE_PATH: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Applies the settings every connection needs once, when it is opened.
+/// Foreign keys are off by default in SQLite and have to be enabled on each
+/// connection.
+#[derive(Debug)]
+struct ConnectionOptions {
+	/// The key of an encrypted database, it has to be given before anything
+	/// else is read.
+	key: Option<String>,
+}
+
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error>
+	for ConnectionOptions
+{
+	fn on_acquire(
+		&self,
+		connection: &mut SqliteConnection,
+	) -> Result<(), diesel::r2d2::Error> {
+		if let Some(key) = &self.key {
+			connection
+				.batch_execute(&format!("PRAGMA key = \"x'{key}'\";"))
+				.map_err(diesel::r2d2::Error::QueryError)?;
+		}
+		connection
+			.batch_execute(&format!(
+				"PRAGMA busy_timeout = {};
+				PRAGMA journal_mode = WAL;
+				PRAGMA synchronous = NORMAL;
+				PRAGMA foreign_keys = ON;",
+				BUSY_TIMEOUT.as_millis()
+			))
+			.map_err(diesel::r2d2::Error::QueryError)
+	}
+}
+
+#[derive(QueryableByName)]
+struct Count {
+	#[diesel(sql_type = BigInt)]
+	count: i64,
+}
+
 pub struct Database;
 
 impl Database {
-	fn database_url() -> Result<String> {
-		let url = Config::new(APP_ID, 1, Some("database"))?
-			.path(DATABASE_NAME, libset::FileType::Plain)?;
-		Ok(url.display().to_string())
+	pub fn database_path() -> Result<PathBuf> {
+		if let Some(path) = DATABASE_PATH.read().unwrap().clone() {
+			return Ok(path);
+		}
+		Self::default_database_path()
 	}
 
-	pub fn establish_connection() -> Result<SqliteConnection> {
-		SqliteConnection::establish(Database::database_url()?.as_str())
-			.context("Error connecting to database")
+	/// Where the database is kept when no other location was given.
+	pub fn default_database_path() -> Result<PathBuf> {
+		Ok(
+			Config::new(APP_ID, 1, Some("database"))?
+				.path(DATABASE_NAME, libset::FileType::Plain)?,
+		)
 	}
 
-	pub fn ensure_migrations_up_to_date() -> Result<()> {
+	/// Points the app at another database file, connections to the previous
+	/// one are closed once they are returned.
+	pub fn set_database_path(path: Option<PathBuf>) {
+		*DATABASE_PATH.write().unwrap() = path;
+		Self::close();
+	}
+
+	/// Drops the shared pool, the next connection opens a new one.
+	pub fn close() {
+		POOL.write().unwrap().take();
+	}
+
+	fn pool() -> Result<ConnectionPool> {
+		if let Some(pool) = POOL.read().unwrap().as_ref() {
+			return Ok(pool.clone());
+		}
+		let mut pool = POOL.write().unwrap();
+		if let Some(pool) = pool.as_ref() {
+			return Ok(pool.clone());
+		}
+		let url = Self::database_path()?.display().to_string();
+		let new_pool = Pool::builder()
+			.max_size(POOL_SIZE)
+			.connection_timeout(BUSY_TIMEOUT)
+			.connection_customizer(Box::new(ConnectionOptions {
+				key: Self::encryption_key()?,
+			}))
+			.build(ConnectionManager::<SqliteConnection>::new(url))
+			.context("Error connecting to database")?;
+		*pool = Some(new_pool.clone());
+		Ok(new_pool)
+	}
+
+	/// Copies the database at `from`, `None` being the default location, to
+	/// `to` along with the key it is encrypted with, the original is kept.
+	pub fn copy_database(from: Option<&Path>, to: &Path) -> Result<()> {
+		let source = match from {
+			Some(path) => path.to_path_buf(),
+			None => Self::default_database_path()?,
+		};
+		if to.exists() {
+			return Err(anyhow!("There is already a database in {}", to.display()));
+		}
+		if let Some(folder) = to.parent() {
+			fs::create_dir_all(folder)?;
+		}
+		// Changes that are still in the journal are copied along with it.
+		Self::close();
+		fs::copy(&source, to).context("Failed to copy the database")?;
+		let mut journal = source.into_os_string();
+		journal.push("-wal");
+		let journal = PathBuf::from(journal);
+		if journal.exists() {
+			let mut copied = to.to_path_buf().into_os_string();
+			copied.push("-wal");
+			fs::copy(journal, copied).context("Failed to copy the database")?;
+		}
+		Self::copy_key(from, to)
+	}
+
+	/// Removes the files SQLite keeps next to the database in WAL mode, they
+	/// can only be removed once every connection is closed.
+	fn remove_journal(database_path: &Path) -> Result<()> {
+		for suffix in ["-wal", "-shm"] {
+			let mut path = database_path.to_path_buf().into_os_string();
+			path.push(suffix);
+			if let Err(err) = fs::remove_file(path) {
+				if err.kind() != std::io::ErrorKind::NotFound {
+					return Err(err.into());
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Databases can only be encrypted when built with SQLCipher.
+	#[cfg(not(feature = "encryption"))]
+	fn encryption_key() -> Result<Option<String>> {
+		Ok(None)
+	}
+
+	#[cfg(not(feature = "encryption"))]
+	fn copy_key(_from: Option<&Path>, _to: &Path) -> Result<()> {
+		Ok(())
+	}
+
+	/// Opens a database file on its own connection, checking that the key is
+	/// the one it was encrypted with.
+	fn open_file(path: &Path, key: Option<&str>) -> Result<SqliteConnection> {
 		let mut connection =
-			SqliteConnection::establish(Database::database_url()?.as_str())
-				.context("Error connecting to database")?;
-		match connection.run_pending_migrations(MIGRATIONS) {
-			Ok(_) => Ok(()),
-			Err(err) => {
+			SqliteConnection::establish(&path.display().to_string())?;
+		if let Some(key) = key {
+			connection.batch_execute(&format!("PRAGMA key = \"x'{key}'\";"))?;
+		}
+		connection
+			.batch_execute("SELECT count(*) FROM sqlite_master;")
+			.context("The database can't be read with this key")?;
+		Ok(connection)
+	}
+
+	pub fn establish_connection() -> Result<DatabaseConnection> {
+		Self::pool()?.get().context("Error connecting to database")
+	}
+
+	/// Runs the migrations the database is missing, taking a snapshot of it
+	/// first if it already had data.
+	pub fn ensure_migrations_up_to_date() -> Result<()> {
+		let mut connection = Self::establish_connection()?;
+		let pending = connection
+			.pending_migrations(MIGRATIONS)
+			.map_err(|err| anyhow!(err))?;
+		let applied = connection
+			.applied_migrations()
+			.map_err(|err| anyhow!(err))?;
+		if !pending.is_empty() && !applied.is_empty() {
+			Self::create_backup()
+				.context("Failed to back up the database before migrating it")?;
+		}
+		for migration in pending {
+			// The rows are counted with the schema the migration starts from.
+			if !applied.is_empty()
+				&& migration.name().version().to_string() == CONSTRAINTS_MIGRATION
+			{
+				Self::log_rows_out_of_constraints(&mut connection)?;
+			}
+			if let Err(err) = connection.run_migration(migration.as_ref()) {
 				tracing::error!("{err}");
-				Err(anyhow!(err))
-			},
+				return Err(anyhow!(err));
+			}
+		}
+		Ok(())
+	}
+
+	/// The migration that adds the constraints moves or resets the rows that
+	/// don't meet them, how many it changes is logged beforehand.
+	fn log_rows_out_of_constraints(
+		connection: &mut SqliteConnection,
+	) -> Result<()> {
+		let count = |connection: &mut SqliteConnection, query: &str| {
+			sql_query(query)
+				.get_result::<Count>(connection)
+				.map(|row| row.count)
+		};
+		let orphans = count(
+			connection,
+			"SELECT count(*) AS count FROM tasks
+			WHERE (parent_task IS NULL AND parent NOT IN (SELECT id_list FROM lists))
+				OR parent_task NOT IN (SELECT id_task FROM tasks)",
+		)?;
+		if orphans > 0 {
+			tracing::warn!(
+				"{orphans} tasks have lost their list or their parent task, they are kept as top level tasks or in a list of recovered tasks."
+			);
+		}
+		let out_of_range = count(
+			connection,
+			"SELECT count(*) AS count FROM tasks
+			WHERE status NOT BETWEEN 0 AND 4 OR priority NOT BETWEEN 0 AND 2",
+		)?;
+		if out_of_range > 0 {
+			tracing::warn!(
+				"{out_of_range} tasks have an unknown status or priority, they are reset to not started and low."
+			);
 		}
+		Ok(())
 	}
 }
@@ -0,0 +1,11 @@
+use diesel::{Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::task_dependencies;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = task_dependencies)]
+pub struct QueryableTaskDependency {
+	pub id_task: String,
+	pub id_blocking_task: String,
+}
@@ -0,0 +1,34 @@
+use std::str::FromStr;
+
+use diesel::{Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{models::group::Group, schema::list_groups, service::Service};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = list_groups)]
+pub struct QueryableGroup {
+	pub id_group: String,
+	pub name: String,
+	pub service: String,
+}
+
+impl From<QueryableGroup> for Group {
+	fn from(value: QueryableGroup) -> Self {
+		Group {
+			id: value.id_group,
+			name: value.name,
+			service: Service::from_str(&value.service).unwrap_or_default(),
+		}
+	}
+}
+
+impl From<Group> for QueryableGroup {
+	fn from(group: Group) -> Self {
+		Self {
+			id_group: group.id,
+			name: group.name,
+			service: group.service.as_ref().to_string(),
+		}
+	}
+}
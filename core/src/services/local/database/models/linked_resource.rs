@@ -0,0 +1,41 @@
+use diesel::{Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	models::linked_resource::LinkedResource, schema::linked_resources,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = linked_resources)]
+pub struct QueryableLinkedResource {
+	pub id_linked_resource: String,
+	pub id_task: String,
+	pub web_url: String,
+	pub display_name: Option<String>,
+	pub application_name: Option<String>,
+	pub position: i32,
+}
+
+impl QueryableLinkedResource {
+	pub fn new(task_id: &str, resource: &LinkedResource, position: i32) -> Self {
+		Self {
+			id_linked_resource: resource.id.clone(),
+			id_task: task_id.to_string(),
+			web_url: resource.web_url.clone(),
+			display_name: resource.display_name.clone(),
+			application_name: resource.application_name.clone(),
+			position,
+		}
+	}
+}
+
+impl From<QueryableLinkedResource> for LinkedResource {
+	fn from(value: QueryableLinkedResource) -> Self {
+		LinkedResource {
+			id: value.id_linked_resource,
+			web_url: value.web_url,
+			display_name: value.display_name,
+			application_name: value.application_name,
+		}
+	}
+}
@@ -1,8 +1,13 @@
+use chrono::NaiveDateTime;
 use diesel::{Insertable, Queryable};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{models::list::List, schema::lists, service::Service};
+use crate::{
+	models::{list::List, list_settings::ListSettings},
+	schema::{lists, remote_list_settings},
+	service::Service,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
 #[diesel(table_name = lists)]
@@ -11,6 +16,46 @@ pub struct QueryableList {
 	pub name: String,
 	pub description: String,
 	pub icon_name: Option<String>,
+	pub deletion_date: Option<NaiveDateTime>,
+	pub id_group: Option<String>,
+	pub color: Option<String>,
+	pub sort_order: i32,
+	pub show_completed: bool,
+	pub grouping: i32,
+}
+
+/// The view settings of a list kept by a service that has no place for them.
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = remote_list_settings)]
+pub struct QueryableListSettings {
+	pub id_list: String,
+	pub color: Option<String>,
+	pub sort_order: i32,
+	pub show_completed: bool,
+	pub grouping: i32,
+}
+
+impl QueryableListSettings {
+	pub fn new(list_id: &str, settings: &ListSettings) -> Self {
+		Self {
+			id_list: list_id.to_string(),
+			color: settings.color.clone(),
+			sort_order: settings.sort_order.into(),
+			show_completed: settings.show_completed,
+			grouping: settings.grouping.into(),
+		}
+	}
+}
+
+impl From<QueryableListSettings> for ListSettings {
+	fn from(value: QueryableListSettings) -> Self {
+		ListSettings {
+			color: value.color,
+			sort_order: value.sort_order.into(),
+			show_completed: value.show_completed,
+			grouping: value.grouping.into(),
+		}
+	}
 }
 
 impl QueryableList {
@@ -24,6 +69,12 @@ impl QueryableList {
 			name: display_name.to_string(),
 			description: description.to_string(),
 			icon_name,
+			deletion_date: None,
+			id_group: None,
+			color: None,
+			sort_order: 0,
+			show_completed: true,
+			grouping: 0,
 		}
 	}
 }
@@ -36,6 +87,16 @@ impl From<QueryableList> for List {
 			service: Service::Computer,
 			icon: value.icon_name,
 			description: value.description,
+			group: value.id_group,
+			settings: ListSettings {
+				color: value.color,
+				sort_order: value.sort_order.into(),
+				show_completed: value.show_completed,
+				grouping: value.grouping.into(),
+			},
+			is_owner: true,
+			is_shared: false,
+			deletion_date: value.deletion_date.map(|ndt| ndt.and_utc()),
 		}
 	}
 }
@@ -47,6 +108,12 @@ impl From<List> for QueryableList {
 			name: list.name,
 			description: list.description,
 			icon_name: list.icon,
+			deletion_date: list.deletion_date.map(|dt| dt.naive_utc()),
+			id_group: list.group,
+			color: list.settings.color,
+			sort_order: list.settings.sort_order.into(),
+			show_completed: list.settings.show_completed,
+			grouping: list.settings.grouping.into(),
 		}
 	}
 }
@@ -1,3 +1,17 @@
+pub mod attachment;
+
+pub mod dependency;
+
+pub mod group;
+
+pub mod linked_resource;
+
 pub mod list;
 
+pub mod tag;
+
 pub mod task;
+
+pub mod task_change;
+
+pub mod time_entry;
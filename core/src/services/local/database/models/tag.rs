@@ -0,0 +1,39 @@
+use diesel::{Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	models::tag::Tag,
+	schema::{tags, task_tags},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = tags)]
+pub struct QueryableTag {
+	pub id_tag: String,
+	pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = task_tags)]
+pub struct QueryableTaskTag {
+	pub id_task: String,
+	pub id_tag: String,
+}
+
+impl From<QueryableTag> for Tag {
+	fn from(value: QueryableTag) -> Self {
+		Tag {
+			id: value.id_tag,
+			name: value.name,
+		}
+	}
+}
+
+impl From<Tag> for QueryableTag {
+	fn from(tag: Tag) -> Self {
+		Self {
+			id_tag: tag.id,
+			name: tag.name,
+		}
+	}
+}
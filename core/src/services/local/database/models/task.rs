@@ -1,17 +1,17 @@
 use chrono::{NaiveDateTime, Utc};
-use diesel::{Insertable, Queryable};
+use diesel::{AsChangeset, Insertable, Queryable};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
-	models::{
-		priority::Priority, recurrence::Recurrence, status::Status, task::Task,
-	},
+	models::{priority::Priority, status::Status, task::Task},
 	schema::tasks,
 };
 
-#[derive(Debug, Clone, Insertable, Queryable, Serialize, Deserialize)]
-#[diesel(table_name = tasks)]
+#[derive(
+	Debug, Clone, Insertable, Queryable, AsChangeset, Serialize, Deserialize,
+)]
+#[diesel(table_name = tasks, treat_none_as_null = true)]
 pub struct QueryableTask {
 	pub id_task: String,
 	pub parent: String,
@@ -20,21 +20,22 @@ pub struct QueryableTask {
 	pub today: bool,
 	pub status: i32,
 	pub priority: i32,
-	pub sub_tasks: String,
-	pub tags: String,
 	pub notes: Option<String>,
 	pub completion_date: Option<NaiveDateTime>,
 	pub deletion_date: Option<NaiveDateTime>,
 	pub due_date: Option<NaiveDateTime>,
 	pub reminder_date: Option<NaiveDateTime>,
-	pub recurrence: String,
+	pub recurrence: Option<String>,
 	pub created_date_time: NaiveDateTime,
 	pub last_modified_date_time: NaiveDateTime,
+	pub parent_task: Option<String>,
+	pub position: i32,
+	pub start_date: Option<NaiveDateTime>,
+	pub estimated_minutes: Option<i64>,
 }
 
 impl QueryableTask {
 	pub fn new(title: String, parent: String) -> Self {
-		let empty_vec: Vec<String> = vec![];
 		Self {
 			id_task: Uuid::new_v4().to_string(),
 			parent,
@@ -44,15 +45,17 @@ impl QueryableTask {
 			notes: None,
 			status: Status::NotStarted as i32,
 			priority: Priority::Low as i32,
-			sub_tasks: serde_json::to_string(&empty_vec).unwrap(),
-			tags: serde_json::to_string(&empty_vec).unwrap(),
 			completion_date: None,
 			deletion_date: None,
 			due_date: None,
 			reminder_date: None,
-			recurrence: String::new(),
+			recurrence: None,
 			created_date_time: Utc::now().naive_utc(),
 			last_modified_date_time: Utc::now().naive_utc(),
+			parent_task: None,
+			position: 0,
+			start_date: None,
+			estimated_minutes: None,
 		}
 	}
 }
@@ -68,15 +71,19 @@ impl From<Task> for QueryableTask {
 			notes: value.notes,
 			status: value.status.into(),
 			priority: value.priority.into(),
-			sub_tasks: serde_json::to_string(&value.sub_tasks).unwrap(),
-			tags: serde_json::to_string(&value.tags).unwrap(),
-			completion_date: value.completion_date.map(|dt| dt.naive_local()),
-			deletion_date: value.deletion_date.map(|dt| dt.naive_local()),
-			due_date: value.due_date.map(|dt| dt.naive_local()),
-			reminder_date: value.reminder_date.map(|dt| dt.naive_local()),
-			recurrence: value.recurrence.to_string(),
-			created_date_time: value.created_date_time.naive_local(),
-			last_modified_date_time: value.last_modified_date_time.naive_local(),
+			completion_date: value.completion_date.map(|dt| dt.naive_utc()),
+			deletion_date: value.deletion_date.map(|dt| dt.naive_utc()),
+			due_date: value.due_date.map(|dt| dt.naive_utc()),
+			reminder_date: value.reminder_date.map(|dt| dt.naive_utc()),
+			recurrence: value
+				.recurrence
+				.map(|recurrence| serde_json::to_string(&recurrence).unwrap()),
+			created_date_time: value.created_date_time.naive_utc(),
+			last_modified_date_time: value.last_modified_date_time.naive_utc(),
+			parent_task: None,
+			position: 0,
+			start_date: value.start_date.map(|dt| dt.naive_utc()),
+			estimated_minutes: value.estimated_minutes,
 		}
 	}
 }
@@ -90,15 +97,24 @@ impl From<QueryableTask> for Task {
 			favorite: value.favorite,
 			today: value.today,
 			notes: value.notes,
+			notes_html: None,
 			status: value.status.into(),
 			priority: value.priority.into(),
-			sub_tasks: serde_json::from_str(&value.sub_tasks).unwrap(),
-			tags: serde_json::from_str(&value.tags).unwrap(),
+			sub_tasks: vec![],
+			tags: vec![],
+			attachments: vec![],
+			linked_resources: vec![],
+			blocked_by: vec![],
 			completion_date: value.completion_date.map(|ndt| ndt.and_utc()),
 			deletion_date: value.deletion_date.map(|ndt| ndt.and_utc()),
+			start_date: value.start_date.map(|ndt| ndt.and_utc()),
 			due_date: value.due_date.map(|ndt| ndt.and_utc()),
 			reminder_date: value.reminder_date.map(|ndt| ndt.and_utc()),
-			recurrence: Recurrence::from_string(value.recurrence),
+			recurrence: value
+				.recurrence
+				.and_then(|recurrence| serde_json::from_str(&recurrence).ok()),
+			estimated_minutes: value.estimated_minutes,
+			time_entries: vec![],
 			created_date_time: value.created_date_time.and_utc(),
 			last_modified_date_time: value.last_modified_date_time.and_utc(),
 		}
@@ -0,0 +1,33 @@
+use std::str::FromStr;
+
+use anyhow::{Context, Error};
+use chrono::NaiveDateTime;
+use diesel::Queryable;
+
+use crate::models::task_change::{TaskChange, TaskField};
+
+#[derive(Debug, Clone, Queryable)]
+pub struct QueryableTaskChange {
+	pub id_change: String,
+	pub id_task: String,
+	pub field: String,
+	pub old_value: Option<String>,
+	pub new_value: Option<String>,
+	pub changed_date_time: NaiveDateTime,
+}
+
+impl TryFrom<QueryableTaskChange> for TaskChange {
+	type Error = Error;
+
+	fn try_from(value: QueryableTaskChange) -> Result<Self, Self::Error> {
+		Ok(TaskChange {
+			field: TaskField::from_str(&value.field)
+				.with_context(|| format!("Unknown task field {}", value.field))?,
+			id: value.id_change,
+			task_id: value.id_task,
+			old_value: value.old_value,
+			new_value: value.new_value,
+			changed_date_time: value.changed_date_time.and_utc(),
+		})
+	}
+}
@@ -0,0 +1,35 @@
+use chrono::NaiveDateTime;
+use diesel::{Insertable, Queryable};
+use serde::{Deserialize, Serialize};
+
+use crate::{models::time_entry::TimeEntry, schema::time_entries};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = time_entries)]
+pub struct QueryableTimeEntry {
+	pub id_entry: String,
+	pub id_task: String,
+	pub start_date: NaiveDateTime,
+	pub end_date: Option<NaiveDateTime>,
+}
+
+impl QueryableTimeEntry {
+	pub fn new(task_id: &str, entry: &TimeEntry) -> Self {
+		Self {
+			id_entry: entry.id.clone(),
+			id_task: task_id.to_string(),
+			start_date: entry.start.naive_utc(),
+			end_date: entry.end.map(|date| date.naive_utc()),
+		}
+	}
+}
+
+impl From<QueryableTimeEntry> for TimeEntry {
+	fn from(value: QueryableTimeEntry) -> Self {
+		TimeEntry {
+			id: value.id_entry,
+			start: value.start_date.and_utc(),
+			end: value.end_date.map(|date| date.and_utc()),
+		}
+	}
+}
@@ -1,32 +1,723 @@
-use std::pin::Pin;
+use std::{
+	collections::{HashMap, HashSet},
+	fs,
+	path::{Component, Path, PathBuf},
+	pin::Pin,
+};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::{
+	sql_query,
+	sql_types::{Integer, Text},
+	Connection, ExpressionMethods, QueryDsl, QueryableByName, RunQueryDsl,
+	SqliteConnection,
+};
 use futures::Stream;
+use libset::{Config, FileType};
 use url::Url;
 
 use crate::{
-	models::{list::List, task::Task},
+	models::{
+		attachment::Attachment, dependency::Dependency, group::Group,
+		linked_resource::LinkedResource, list::List, placement::Placement,
+		search::SearchResult, status::Status, tag::Tag, task::Task,
+		task_change::TaskChange, time_entry::TimeEntry,
+	},
+	schema::lists::deletion_date as list_deletion_date,
 	schema::lists::dsl::lists,
 	schema::lists::*,
+	schema::tasks::deletion_date,
 	schema::tasks::dsl::tasks,
 	schema::tasks::*,
+	schema::{
+		attachments, linked_resources, list_groups, tags, task_dependencies,
+		task_history, task_tags, time_entries,
+	},
+	service::Service,
+	services::microsoft::service::APP_ID,
 	task_service::TodoProvider,
 };
 
 use super::database::{
-	models::{list::QueryableList, task::QueryableTask},
+	models::{
+		attachment::QueryableAttachment,
+		dependency::QueryableTaskDependency,
+		group::QueryableGroup,
+		linked_resource::QueryableLinkedResource,
+		list::QueryableList,
+		tag::{QueryableTag, QueryableTaskTag},
+		task::QueryableTask,
+		task_change::QueryableTaskChange,
+		time_entry::QueryableTimeEntry,
+	},
 	Database,
 };
 
+/// How many lists, and how many tasks, a search returns at most.
+const SEARCH_LIMIT: i32 = 100;
+
+/// SQLite limits the number of parameters in a statement, so rows are looked
+/// up by id in batches of this size.
+const IDS_PER_QUERY: usize = 500;
+
 #[derive(Debug, Clone, Copy)]
 pub struct ComputerStorage;
 
+#[derive(QueryableByName)]
+struct SearchMatch {
+	#[diesel(sql_type = Text)]
+	id: String,
+}
+
 impl ComputerStorage {
 	pub(crate) fn new() -> Self {
 		Self
 	}
+
+	/// Attached files are kept in the app data directory, one folder per
+	/// attachment so that the file keeps its original name. Names and ids can
+	/// come from a shared list, so they are never used as paths.
+	pub(crate) fn attachment_path(attachment: &Attachment) -> Result<PathBuf> {
+		let folder_name = encode_file_name(&attachment.id);
+		let folder = Config::new(APP_ID, 1, Some("attachments"))?
+			.path(&folder_name, FileType::Plain)?;
+		let file_name = Path::new(&attachment.name)
+			.file_name()
+			.map(|file_name| file_name.to_os_string())
+			.unwrap_or_else(|| folder_name.into());
+		let path = folder.join(file_name);
+		let inside = folder.parent().is_some_and(|attachments_folder| {
+			path.strip_prefix(attachments_folder).is_ok_and(|relative| {
+				relative.components().count() == 2
+					&& relative
+						.components()
+						.all(|component| matches!(component, Component::Normal(_)))
+			})
+		});
+		if !inside {
+			bail!(
+				"The attachment {} would be saved outside of the attachments folder.",
+				attachment.name
+			)
+		}
+		fs::create_dir_all(&folder)?;
+		Ok(path)
+	}
+
+	/// Groups are kept in the local database for every service, since not
+	/// every service has them.
+	pub(crate) fn read_service_groups(service: Service) -> Result<Vec<Group>> {
+		let results = list_groups::table
+			.filter(list_groups::service.eq(service.as_ref()))
+			.order(list_groups::name)
+			.load::<QueryableGroup>(&mut Database::establish_connection()?)?;
+
+		Ok(results.into_iter().map(|group| group.into()).collect())
+	}
+
+	pub(crate) fn create_service_group(group: Group) -> Result<Group> {
+		let group: QueryableGroup = group.into();
+		diesel::insert_into(list_groups::table)
+			.values(&group)
+			.execute(&mut Database::establish_connection()?)
+			.context("Failed to create group.")?;
+
+		Ok(group.into())
+	}
+
+	pub(crate) fn update_service_group(group: Group) -> Result<()> {
+		diesel::update(
+			list_groups::table.filter(list_groups::id_group.eq(group.id)),
+		)
+		.set(list_groups::name.eq(group.name))
+		.execute(&mut Database::establish_connection()?)
+		.context("Failed to update group.")?;
+
+		Ok(())
+	}
+
+	pub(crate) fn delete_service_group(id: String) -> Result<()> {
+		diesel::delete(list_groups::table.filter(list_groups::id_group.eq(id)))
+			.execute(&mut Database::establish_connection()?)
+			.context("Failed to delete group.")?;
+
+		Ok(())
+	}
+
+	/// Converts the queried rows into tasks, attaching the tags, files, links,
+	/// dependencies, time entries and sub-tasks of each task.
+	fn into_tasks(
+		connection: &mut SqliteConnection,
+		task_rows: Vec<QueryableTask>,
+	) -> Result<Vec<Task>> {
+		let mut sub_tasks_by_task: HashMap<String, Vec<QueryableTask>> =
+			HashMap::new();
+		let mut ids: Vec<String> =
+			task_rows.iter().map(|row| row.id_task.clone()).collect();
+		let mut parents = ids.clone();
+		while !parents.is_empty() {
+			let mut children = vec![];
+			for chunk in parents.chunks(IDS_PER_QUERY) {
+				children.extend(
+					tasks
+						.filter(parent_task.eq_any(chunk))
+						.order(position)
+						.load::<QueryableTask>(connection)?,
+				);
+			}
+			parents = children.iter().map(|row| row.id_task.clone()).collect();
+			ids.extend(parents.iter().cloned());
+			for row in children {
+				if let Some(parent_id) = row.parent_task.clone() {
+					sub_tasks_by_task.entry(parent_id).or_default().push(row)
+				}
+			}
+		}
+
+		let mut tags_by_task: HashMap<String, Vec<String>> = HashMap::new();
+		for chunk in ids.chunks(IDS_PER_QUERY) {
+			task_tags::table
+				.inner_join(tags::table)
+				.filter(task_tags::id_task.eq_any(chunk))
+				.select((task_tags::id_task, tags::name))
+				.load::<(String, String)>(connection)?
+				.into_iter()
+				.for_each(|(task_id, tag)| {
+					tags_by_task.entry(task_id).or_default().push(tag)
+				});
+		}
+
+		let mut attachments_by_task: HashMap<String, Vec<Attachment>> =
+			HashMap::new();
+		for chunk in ids.chunks(IDS_PER_QUERY) {
+			attachments::table
+				.filter(attachments::id_task.eq_any(chunk))
+				.order(attachments::created_date_time)
+				.load::<QueryableAttachment>(connection)?
+				.into_iter()
+				.for_each(|row| {
+					attachments_by_task
+						.entry(row.id_task.clone())
+						.or_default()
+						.push(row.into())
+				});
+		}
+
+		let mut linked_resources_by_task: HashMap<String, Vec<LinkedResource>> =
+			HashMap::new();
+		for chunk in ids.chunks(IDS_PER_QUERY) {
+			linked_resources::table
+				.filter(linked_resources::id_task.eq_any(chunk))
+				.order(linked_resources::position)
+				.load::<QueryableLinkedResource>(connection)?
+				.into_iter()
+				.for_each(|row| {
+					linked_resources_by_task
+						.entry(row.id_task.clone())
+						.or_default()
+						.push(row.into())
+				});
+		}
+
+		let mut dependencies_by_task: HashMap<String, Vec<Dependency>> =
+			HashMap::new();
+		for chunk in ids.chunks(IDS_PER_QUERY) {
+			task_dependencies::table
+				.inner_join(tasks)
+				.filter(task_dependencies::id_task.eq_any(chunk))
+				.filter(deletion_date.is_null())
+				.order(position)
+				.select((task_dependencies::id_task, id_task, title, status))
+				.load::<(String, String, String, i32)>(connection)?
+				.into_iter()
+				.for_each(
+					|(task_id, blocking_task_id, blocking_title, blocking_status)| {
+						dependencies_by_task
+							.entry(task_id)
+							.or_default()
+							.push(Dependency {
+								task_id: blocking_task_id,
+								title: blocking_title,
+								completed: Status::from(blocking_status) == Status::Completed,
+							})
+					},
+				);
+		}
+
+		let mut time_entries_by_task: HashMap<String, Vec<TimeEntry>> =
+			HashMap::new();
+		for chunk in ids.chunks(IDS_PER_QUERY) {
+			time_entries::table
+				.filter(time_entries::id_task.eq_any(chunk))
+				.order(time_entries::start_date)
+				.load::<QueryableTimeEntry>(connection)?
+				.into_iter()
+				.for_each(|row| {
+					time_entries_by_task
+						.entry(row.id_task.clone())
+						.or_default()
+						.push(row.into())
+				});
+		}
+
+		Ok(
+			task_rows
+				.into_iter()
+				.map(|row| {
+					Self::build_task(
+						row,
+						&mut tags_by_task,
+						&mut attachments_by_task,
+						&mut linked_resources_by_task,
+						&mut dependencies_by_task,
+						&mut time_entries_by_task,
+						&mut sub_tasks_by_task,
+					)
+				})
+				.collect(),
+		)
+	}
+
+	fn build_task(
+		row: QueryableTask,
+		tags_by_task: &mut HashMap<String, Vec<String>>,
+		attachments_by_task: &mut HashMap<String, Vec<Attachment>>,
+		linked_resources_by_task: &mut HashMap<String, Vec<LinkedResource>>,
+		dependencies_by_task: &mut HashMap<String, Vec<Dependency>>,
+		time_entries_by_task: &mut HashMap<String, Vec<TimeEntry>>,
+		sub_tasks_by_task: &mut HashMap<String, Vec<QueryableTask>>,
+	) -> Task {
+		let task_tags = tags_by_task.remove(&row.id_task).unwrap_or_default();
+		let task_attachments =
+			attachments_by_task.remove(&row.id_task).unwrap_or_default();
+		let task_linked_resources = linked_resources_by_task
+			.remove(&row.id_task)
+			.unwrap_or_default();
+		let task_dependencies = dependencies_by_task
+			.remove(&row.id_task)
+			.unwrap_or_default();
+		let task_time_entries = time_entries_by_task
+			.remove(&row.id_task)
+			.unwrap_or_default();
+		let sub_task_rows =
+			sub_tasks_by_task.remove(&row.id_task).unwrap_or_default();
+		let mut task: Task = row.into();
+		task.tags = task_tags;
+		task.attachments = task_attachments;
+		task.linked_resources = task_linked_resources;
+		task.blocked_by = task_dependencies;
+		task.time_entries = task_time_entries;
+		task.sub_tasks = sub_task_rows
+			.into_iter()
+			.map(|row| {
+				Self::build_task(
+					row,
+					tags_by_task,
+					attachments_by_task,
+					linked_resources_by_task,
+					dependencies_by_task,
+					time_entries_by_task,
+					sub_tasks_by_task,
+				)
+			})
+			.collect();
+		task
+	}
+
+	/// Returns the ids of every sub-task below a task, at any depth.
+	fn descendants(
+		connection: &mut SqliteConnection,
+		task_id: &str,
+	) -> Result<Vec<String>> {
+		let mut descendants = vec![];
+		let mut parents = vec![task_id.to_string()];
+		while !parents.is_empty() {
+			parents = tasks
+				.filter(parent_task.eq_any(parents))
+				.select(id_task)
+				.load::<String>(connection)?;
+			descendants.extend(parents.clone());
+		}
+		Ok(descendants)
+	}
+
+	/// Whether a task has to wait for another one, either directly or through
+	/// the tasks it depends on.
+	fn depends_on(
+		connection: &mut SqliteConnection,
+		task_id: &str,
+		other_id: &str,
+	) -> Result<bool> {
+		let mut visited = vec![];
+		let mut pending = vec![task_id.to_string()];
+		while let Some(current) = pending.pop() {
+			if current == other_id {
+				return Ok(true);
+			}
+			if visited.contains(&current) {
+				continue;
+			}
+			pending.extend(
+				task_dependencies::table
+					.filter(task_dependencies::id_task.eq(&current))
+					.select(task_dependencies::id_blocking_task)
+					.load::<String>(connection)?,
+			);
+			visited.push(current);
+		}
+		Ok(false)
+	}
+
+	fn deleted_list_ids(
+		connection: &mut SqliteConnection,
+	) -> Result<Vec<String>> {
+		Ok(
+			lists
+				.filter(list_deletion_date.is_not_null())
+				.select(id_list)
+				.load::<String>(connection)?,
+		)
+	}
+
+	/// Inserts a task at the end of its list, along with everything that
+	/// belongs to it.
+	fn insert_task(connection: &mut SqliteConnection, task: &Task) -> Result<()> {
+		let mut queryable_task: QueryableTask = task.clone().into();
+		queryable_task.position = Self::next_position(connection, &task.parent)?;
+		diesel::insert_into(tasks)
+			.values(&queryable_task)
+			.execute(connection)?;
+		Self::save_sub_tasks(connection, task)?;
+		Self::save_time_entries(connection, task)?;
+		Self::save_linked_resources(connection, task)?;
+		Self::save_tags(connection, &task.id, &task.tags)
+	}
+
+	/// Saves the changes made to a task. Completing a recurring task creates
+	/// its next occurrence.
+	fn save_task(
+		connection: &mut SqliteConnection,
+		mut task: Task,
+	) -> Result<Task> {
+		let previous_status: Status = tasks
+			.find(task.id.clone())
+			.select(status)
+			.first::<i32>(connection)
+			.context("Failed to fetch task.")?
+			.into();
+
+		let mut next_occurrence = None;
+		if task.status == Status::Completed && previous_status != Status::Completed
+		{
+			if task.completion_date.is_none() {
+				task.completion_date = Some(Utc::now());
+			}
+			next_occurrence = task.next_occurrence();
+			if next_occurrence.is_some() {
+				task.recurrence = None;
+			}
+		}
+
+		let original_task = task.clone();
+		let queryable_task: QueryableTask = task.into();
+
+		if let Some(next_occurrence) = next_occurrence {
+			let mut next_row = QueryableTask::from(next_occurrence.clone());
+			next_row.position =
+				Self::next_position(connection, &next_occurrence.parent)?;
+			diesel::insert_into(tasks)
+				.values(&next_row)
+				.execute(connection)
+				.context("Failed to create the next occurrence.")?;
+			Self::save_sub_tasks(connection, &next_occurrence)?;
+			Self::save_linked_resources(connection, &next_occurrence)?;
+			Self::save_tags(connection, &next_occurrence.id, &next_occurrence.tags)?;
+		}
+
+		Self::save_sub_tasks(connection, &original_task)?;
+		Self::save_time_entries(connection, &original_task)?;
+		Self::save_linked_resources(connection, &original_task)?;
+
+		Self::save_tags(connection, &queryable_task.id_task, &original_task.tags)?;
+
+		diesel::update(tasks.filter(id_task.eq(queryable_task.id_task.clone())))
+			.set((
+				id_task.eq(queryable_task.id_task),
+				parent.eq(queryable_task.parent),
+				title.eq(queryable_task.title),
+				favorite.eq(queryable_task.favorite),
+				today.eq(queryable_task.today),
+				status.eq(queryable_task.status),
+				priority.eq(queryable_task.priority),
+				notes.eq(queryable_task.notes),
+				completion_date.eq(queryable_task.completion_date),
+				deletion_date.eq(queryable_task.deletion_date),
+				start_date.eq(queryable_task.start_date),
+				estimated_minutes.eq(queryable_task.estimated_minutes),
+				due_date.eq(queryable_task.due_date),
+				reminder_date.eq(queryable_task.reminder_date),
+				recurrence.eq(queryable_task.recurrence),
+				created_date_time.eq(queryable_task.created_date_time),
+				last_modified_date_time.eq(queryable_task.last_modified_date_time),
+			))
+			.execute(connection)
+			.context("Failed to update task.")?;
+
+		Ok(original_task)
+	}
+
+	/// Moves a task to the trash, or removes it for good if it already was,
+	/// see [`Self::purge_tasks`].
+	fn remove_task(
+		connection: &mut SqliteConnection,
+		task_id: String,
+	) -> Result<Vec<String>> {
+		let deleted: Option<NaiveDateTime> = tasks
+			.find(task_id.clone())
+			.select(deletion_date)
+			.first(connection)
+			.context("Failed to fetch task.")?;
+
+		if deleted.is_some() {
+			Self::purge_tasks(connection, vec![task_id])
+		} else {
+			diesel::update(tasks.filter(id_task.eq(task_id)))
+				.set(deletion_date.eq(Some(Utc::now().naive_utc())))
+				.execute(connection)
+				.context("Failed to move task to the trash.")?;
+			Ok(vec![])
+		}
+	}
+
+	/// Turns what the user typed into an FTS5 query that matches every word,
+	/// including words that only start with it.
+	fn match_expression(query: &str) -> Option<String> {
+		let words: Vec<String> = query
+			.split_whitespace()
+			.map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+			.collect();
+		(!words.is_empty()).then(|| words.join(" "))
+	}
+
+	/// Ids of the rows of the full-text indexes that match a query, best
+	/// matches first.
+	fn search_index(
+		connection: &mut SqliteConnection,
+		query: &str,
+		expression: &str,
+	) -> Result<Vec<String>> {
+		Ok(
+			sql_query(query)
+				.bind::<Text, _>(expression)
+				.bind::<Integer, _>(SEARCH_LIMIT)
+				.load::<SearchMatch>(connection)?
+				.into_iter()
+				.map(|result| result.id)
+				.collect(),
+		)
+	}
+
+	/// Returns the position that puts a new task at the end of its list.
+	fn next_position(
+		connection: &mut SqliteConnection,
+		list_id: &str,
+	) -> Result<i32> {
+		let last_position = tasks
+			.filter(parent.eq(list_id))
+			.filter(parent_task.is_null())
+			.select(diesel::dsl::max(position))
+			.first::<Option<i32>>(connection)?;
+		Ok(last_position.map_or(0, |last| last + 1))
+	}
+
+	/// Removes tasks for good, along with every sub-task below them. Returns
+	/// the attachments whose files are to be removed once the transaction
+	/// is committed.
+	fn purge_tasks(
+		connection: &mut SqliteConnection,
+		task_ids: Vec<String>,
+	) -> Result<Vec<String>> {
+		let mut removed = vec![];
+		for task_id in task_ids {
+			removed.extend(Self::descendants(connection, &task_id)?);
+			removed.push(task_id);
+		}
+		let attachment_ids = attachments::table
+			.filter(attachments::id_task.eq_any(removed.clone()))
+			.select(attachments::id_attachment)
+			.load::<String>(connection)?;
+		diesel::delete(tasks.filter(id_task.eq_any(removed)))
+			.execute(connection)?;
+		Ok(attachment_ids)
+	}
+
+	/// Removes the files of attachments whose rows are already gone, a file
+	/// that can't be removed is only logged.
+	fn remove_attachment_files(attachment_ids: Vec<String>) {
+		for attachment_id in attachment_ids {
+			if let Err(err) = Self::remove_attachment_file(&attachment_id) {
+				tracing::error!("{err}");
+			}
+		}
+	}
+
+	fn remove_attachment_file(attachment_id: &str) -> Result<()> {
+		let folder = Config::new(APP_ID, 1, Some("attachments"))?
+			.path(&encode_file_name(attachment_id), FileType::Plain)?;
+		if folder.exists() {
+			fs::remove_dir_all(folder)
+				.context("Failed to remove the attached file.")?;
+		}
+		Ok(())
+	}
+
+	/// Inserts or updates a sub-task row of a task and the sub-tasks below it.
+	fn save_sub_task(
+		connection: &mut SqliteConnection,
+		task: &Task,
+		sub_task: &Task,
+		index: i32,
+	) -> Result<()> {
+		let mut row: QueryableTask = sub_task.clone().into();
+		row.parent = task.parent.clone();
+		row.parent_task = Some(task.id.clone());
+		row.position = index;
+
+		diesel::insert_into(tasks)
+			.values(&row)
+			.on_conflict(id_task)
+			.do_update()
+			.set(&row)
+			.execute(connection)
+			.context("Failed to save sub-task.")?;
+
+		let sub_task = Task {
+			parent: row.parent,
+			..sub_task.clone()
+		};
+		for (index, child) in sub_task.sub_tasks.iter().enumerate() {
+			Self::save_sub_task(connection, &sub_task, child, index as i32)?;
+		}
+		Ok(())
+	}
+
+	/// Brings the sub-task rows of a task in line with its `sub_tasks`,
+	/// removing the ones that are no longer there.
+	fn save_sub_tasks(
+		connection: &mut SqliteConnection,
+		task: &Task,
+	) -> Result<()> {
+		Self::remove_missing_sub_tasks(connection, task)?;
+		for (index, sub_task) in task.sub_tasks.iter().enumerate() {
+			Self::save_sub_task(connection, task, sub_task, index as i32)?;
+		}
+		Ok(())
+	}
+
+	/// Removes the rows below a task that are no longer in its `sub_tasks`.
+	fn remove_missing_sub_tasks(
+		connection: &mut SqliteConnection,
+		task: &Task,
+	) -> Result<()> {
+		let mut kept = vec![];
+		let mut pending: Vec<&Task> = task.sub_tasks.iter().collect();
+		while let Some(sub_task) = pending.pop() {
+			kept.push(sub_task.id.clone());
+			pending.extend(sub_task.sub_tasks.iter());
+		}
+		let removed: Vec<String> = Self::descendants(connection, &task.id)?
+			.into_iter()
+			.filter(|id| !kept.contains(id))
+			.collect();
+		diesel::delete(tasks.filter(id_task.eq_any(removed)))
+			.execute(connection)?;
+		Ok(())
+	}
+
+	/// Replaces the time entries of a task.
+	fn save_time_entries(
+		connection: &mut SqliteConnection,
+		task: &Task,
+	) -> Result<()> {
+		diesel::delete(
+			time_entries::table.filter(time_entries::id_task.eq(&task.id)),
+		)
+		.execute(connection)?;
+
+		let rows: Vec<QueryableTimeEntry> = task
+			.time_entries
+			.iter()
+			.map(|entry| QueryableTimeEntry::new(&task.id, entry))
+			.collect();
+		diesel::insert_into(time_entries::table)
+			.values(&rows)
+			.execute(connection)
+			.context("Failed to save the tracked time.")?;
+		Ok(())
+	}
+
+	/// Replaces the links of a task, keeping the order they were added in.
+	fn save_linked_resources(
+		connection: &mut SqliteConnection,
+		task: &Task,
+	) -> Result<()> {
+		diesel::delete(
+			linked_resources::table.filter(linked_resources::id_task.eq(&task.id)),
+		)
+		.execute(connection)?;
+
+		let rows: Vec<QueryableLinkedResource> = task
+			.linked_resources
+			.iter()
+			.enumerate()
+			.map(|(index, resource)| {
+				QueryableLinkedResource::new(&task.id, resource, index as i32)
+			})
+			.collect();
+		diesel::insert_into(linked_resources::table)
+			.values(&rows)
+			.execute(connection)
+			.context("Failed to save the links.")?;
+		Ok(())
+	}
+
+	/// Replaces the tags of a task, creating the tags that don't exist yet.
+	fn save_tags(
+		connection: &mut SqliteConnection,
+		task_id: &str,
+		names: &[String],
+	) -> Result<()> {
+		diesel::delete(task_tags::table.filter(task_tags::id_task.eq(task_id)))
+			.execute(connection)?;
+
+		for tag_name in names {
+			let tag_id = match tags::table
+				.filter(tags::name.eq(tag_name))
+				.select(tags::id_tag)
+				.first::<String>(connection)
+			{
+				Ok(tag_id) => tag_id,
+				Err(diesel::NotFound) => {
+					let tag: QueryableTag = Tag::new(tag_name).into();
+					diesel::insert_into(tags::table)
+						.values(&tag)
+						.execute(connection)?;
+					tag.id_tag
+				},
+				Err(err) => return Err(err.into()),
+			};
+			diesel::insert_or_ignore_into(task_tags::table)
+				.values(&QueryableTaskTag {
+					id_task: task_id.to_string(),
+					id_tag: tag_id,
+				})
+				.execute(connection)?;
+		}
+
+		Ok(())
+	}
 }
 
 #[async_trait]
@@ -52,27 +743,30 @@ impl TodoProvider for ComputerStorage {
 	}
 
 	async fn read_tasks(&mut self) -> Result<Vec<Task>> {
-		let task_list: Vec<Task> = tasks
-			.load::<QueryableTask>(&mut Database::establish_connection()?)?
-			.iter()
-			.map(|t| t.clone().into())
-			.collect();
+		let mut connection = Database::establish_connection()?;
+		let task_list = tasks
+			.filter(parent_task.is_null())
+			.filter(deletion_date.is_null())
+			.filter(parent.ne_all(Self::deleted_list_ids(&mut connection)?))
+			.order((parent, position))
+			.load::<QueryableTask>(&mut connection)?;
 
-		Ok(task_list)
+		Self::into_tasks(&mut connection, task_list)
 	}
 
 	async fn read_tasks_from_list(
 		&mut self,
 		parent_list: String,
 	) -> Result<Vec<Task>> {
-		let response: Vec<Task> = tasks
+		let mut connection = Database::establish_connection()?;
+		let response = tasks
 			.filter(parent.eq(parent_list))
-			.load::<QueryableTask>(&mut Database::establish_connection()?)?
-			.iter()
-			.map(|t| t.clone().into())
-			.collect();
+			.filter(parent_task.is_null())
+			.filter(deletion_date.is_null())
+			.order(position)
+			.load::<QueryableTask>(&mut connection)?;
 
-		Ok(response)
+		Self::into_tasks(&mut connection, response)
 	}
 
 	async fn get_tasks(
@@ -87,68 +781,334 @@ impl TodoProvider for ComputerStorage {
 		_task_list_id: String,
 		task_id: String,
 	) -> Result<Task> {
+		let mut connection = Database::establish_connection()?;
 		let task: QueryableTask = tasks
 			.find(task_id)
-			.first(&mut Database::establish_connection()?)
+			.first(&mut connection)
 			.context("Failed to fetch list of tasks.")?;
 
-		Ok(task.into())
+		Self::into_tasks(&mut connection, vec![task])?
+			.pop()
+			.context("Failed to fetch task.")
+	}
+
+	async fn read_task_history(
+		&mut self,
+		_task_list_id: String,
+		task_id: String,
+	) -> Result<Vec<TaskChange>> {
+		task_history::table
+			.filter(task_history::id_task.eq(task_id))
+			.order(task_history::changed_date_time.desc())
+			.load::<QueryableTaskChange>(&mut Database::establish_connection()?)
+			.context("Failed to fetch the history of the task.")?
+			.into_iter()
+			.map(TaskChange::try_from)
+			.collect()
 	}
 
 	async fn create_task(&mut self, task: Task) -> Result<()> {
-		let queryable_task: QueryableTask = task.into();
+		Database::establish_connection()?.transaction::<_, anyhow::Error, _>(
+			|connection| Self::insert_task(connection, &task),
+		)
+	}
 
-		diesel::insert_into(tasks)
-			.values(&queryable_task)
-			.execute(&mut Database::establish_connection()?)?;
+	async fn update_task(&mut self, task: Task) -> Result<Task> {
+		Database::establish_connection()?.transaction::<_, anyhow::Error, _>(
+			|connection| Self::save_task(connection, task),
+		)
+	}
+
+	async fn delete_task(
+		&mut self,
+		_list_id: String,
+		task_id: String,
+	) -> Result<()> {
+		let attachment_ids = Database::establish_connection()?
+			.transaction::<_, anyhow::Error, _>(|connection| {
+				Self::remove_task(connection, task_id)
+			})?;
+		Self::remove_attachment_files(attachment_ids);
+		Ok(())
+	}
+
+	async fn create_tasks(&mut self, new_tasks: Vec<Task>) -> Result<()> {
+		Database::establish_connection()?
+			.transaction::<_, anyhow::Error, _>(|connection| {
+				for task in &new_tasks {
+					Self::insert_task(connection, task)?;
+				}
+				Ok(())
+			})
+			.context("Failed to create the tasks, no changes were made.")
+	}
+
+	async fn update_tasks(&mut self, changed: Vec<Task>) -> Result<Vec<Task>> {
+		Database::establish_connection()?
+			.transaction::<_, anyhow::Error, _>(|connection| {
+				changed
+					.into_iter()
+					.map(|task| Self::save_task(connection, task))
+					.collect()
+			})
+			.context("Failed to update the tasks, no changes were made.")
+	}
 
+	async fn delete_tasks(&mut self, removed: Vec<Task>) -> Result<()> {
+		let attachment_ids = Database::establish_connection()?
+			.transaction::<_, anyhow::Error, _>(|connection| {
+				let mut attachment_ids = vec![];
+				for task in removed {
+					attachment_ids.extend(Self::remove_task(connection, task.id)?);
+				}
+				Ok(attachment_ids)
+			})
+			.context("Failed to delete the tasks, no changes were made.")?;
+		Self::remove_attachment_files(attachment_ids);
 		Ok(())
 	}
 
-	async fn update_task(&mut self, task: Task) -> Result<Task> {
-		let original_task = task.clone();
-		let queryable_task: QueryableTask = task.into();
+	async fn reorder_task(
+		&mut self,
+		_list_id: String,
+		task_id: String,
+		placement: Placement,
+	) -> Result<()> {
+		Database::establish_connection()?.transaction::<_, anyhow::Error, _>(
+			|connection| {
+				let (list_id, parent_id) = tasks
+					.find(task_id.clone())
+					.select((parent, parent_task))
+					.first::<(String, Option<String>)>(connection)
+					.context("Failed to fetch task.")?;
 
-		diesel::update(tasks.filter(id_task.eq(queryable_task.id_task.clone())))
-			.set((
-				id_task.eq(queryable_task.id_task),
-				parent.eq(queryable_task.parent),
-				title.eq(queryable_task.title),
-				favorite.eq(queryable_task.favorite),
-				today.eq(queryable_task.today),
-				status.eq(queryable_task.status),
-				priority.eq(queryable_task.priority),
-				sub_tasks.eq(queryable_task.sub_tasks),
-				tags.eq(queryable_task.tags),
-				notes.eq(queryable_task.notes),
-				completion_date.eq(queryable_task.completion_date),
-				deletion_date.eq(queryable_task.deletion_date),
-				due_date.eq(queryable_task.due_date),
-				reminder_date.eq(queryable_task.reminder_date),
-				recurrence.eq(queryable_task.recurrence),
-				created_date_time.eq(queryable_task.created_date_time),
-				last_modified_date_time.eq(queryable_task.last_modified_date_time),
-			))
-			.execute(&mut Database::establish_connection()?)
-			.context("Failed to update task.")?;
+				let mut siblings = match parent_id {
+					Some(parent_id) => tasks
+						.filter(parent_task.eq(parent_id))
+						.filter(deletion_date.is_null())
+						.order(position)
+						.select(id_task)
+						.load::<String>(connection)?,
+					None => tasks
+						.filter(parent.eq(list_id))
+						.filter(parent_task.is_null())
+						.filter(deletion_date.is_null())
+						.order(position)
+						.select(id_task)
+						.load::<String>(connection)?,
+				};
+				siblings.retain(|id| id != &task_id);
+				let index = placement
+					.position(&siblings)
+					.context("The task to move it next to is not among its siblings.")?;
+				siblings.insert(index, task_id);
 
-		Ok(original_task)
+				for (index, sibling) in siblings.into_iter().enumerate() {
+					diesel::update(tasks.filter(id_task.eq(sibling)))
+						.set(position.eq(index as i32))
+						.execute(connection)?;
+				}
+				Ok(())
+			},
+		)
 	}
 
-	async fn delete_task(
+	async fn create_sub_task(
+		&mut self,
+		_list_id: String,
+		task_id: String,
+		sub_task: Task,
+	) -> Result<Task> {
+		let mut connection = Database::establish_connection()?;
+		let parent_task_row: QueryableTask = tasks
+			.find(task_id.clone())
+			.first(&mut connection)
+			.context("Failed to fetch task.")?;
+		let last_position = tasks
+			.filter(parent_task.eq(task_id))
+			.select(diesel::dsl::max(position))
+			.first::<Option<i32>>(&mut connection)?;
+
+		connection.transaction::<_, anyhow::Error, _>(|connection| {
+			Self::save_sub_task(
+				connection,
+				&parent_task_row.into(),
+				&sub_task,
+				last_position.map_or(0, |last| last + 1),
+			)
+		})?;
+
+		Ok(sub_task)
+	}
+
+	async fn update_sub_task(
 		&mut self,
 		_list_id: String,
 		task_id: String,
+		sub_task: Task,
 	) -> Result<()> {
-		diesel::delete(tasks.filter(id_task.eq(task_id)))
-			.execute(&mut Database::establish_connection()?)?;
+		let mut connection = Database::establish_connection()?;
+		let parent_task_row: QueryableTask = tasks
+			.find(task_id)
+			.first(&mut connection)
+			.context("Failed to fetch task.")?;
+		let current_position = tasks
+			.find(sub_task.id.clone())
+			.select(position)
+			.first::<i32>(&mut connection)
+			.context("Failed to fetch sub-task.")?;
+
+		connection.transaction::<_, anyhow::Error, _>(|connection| {
+			Self::remove_missing_sub_tasks(connection, &sub_task)?;
+			Self::save_sub_task(
+				connection,
+				&parent_task_row.into(),
+				&sub_task,
+				current_position,
+			)
+		})?;
 
 		Ok(())
 	}
 
+	async fn delete_sub_task(
+		&mut self,
+		_list_id: String,
+		_task_id: String,
+		sub_task_id: String,
+	) -> Result<()> {
+		let attachment_ids = Database::establish_connection()?
+			.transaction::<_, anyhow::Error, _>(|connection| {
+				Self::purge_tasks(connection, vec![sub_task_id])
+			})?;
+		Self::remove_attachment_files(attachment_ids);
+		Ok(())
+	}
+
+	async fn add_dependency(
+		&mut self,
+		_list_id: String,
+		task_id: String,
+		blocking_task_id: String,
+	) -> Result<Dependency> {
+		if task_id == blocking_task_id {
+			bail!("A task can't depend on itself.")
+		}
+
+		Database::establish_connection()?.transaction::<_, anyhow::Error, _>(
+			|connection| {
+				let (blocking_title, blocking_status) = tasks
+					.find(blocking_task_id.clone())
+					.select((title, status))
+					.first::<(String, i32)>(connection)
+					.context("Failed to fetch task.")?;
+				if Self::depends_on(connection, &blocking_task_id, &task_id)? {
+					bail!("This task can't wait for a task that is waiting for it.")
+				}
+				diesel::insert_or_ignore_into(task_dependencies::table)
+					.values(&QueryableTaskDependency {
+						id_task: task_id,
+						id_blocking_task: blocking_task_id.clone(),
+					})
+					.execute(connection)
+					.context("Failed to add the dependency.")?;
+
+				Ok(Dependency {
+					task_id: blocking_task_id,
+					title: blocking_title,
+					completed: Status::from(blocking_status) == Status::Completed,
+				})
+			},
+		)
+	}
+
+	async fn remove_dependency(
+		&mut self,
+		_list_id: String,
+		task_id: String,
+		blocking_task_id: String,
+	) -> Result<()> {
+		diesel::delete(
+			task_dependencies::table
+				.filter(task_dependencies::id_task.eq(task_id))
+				.filter(task_dependencies::id_blocking_task.eq(blocking_task_id)),
+		)
+		.execute(&mut Database::establish_connection()?)
+		.context("Failed to remove the dependency.")?;
+		Ok(())
+	}
+
+	async fn read_attachments(
+		&mut self,
+		_list_id: String,
+		task_id: String,
+	) -> Result<Vec<Attachment>> {
+		let results = attachments::table
+			.filter(attachments::id_task.eq(task_id))
+			.order(attachments::created_date_time)
+			.load::<QueryableAttachment>(&mut Database::establish_connection()?)?;
+
+		Ok(results.into_iter().map(|row| row.into()).collect())
+	}
+
+	async fn create_attachment(
+		&mut self,
+		_list_id: String,
+		task_id: String,
+		attachment: Attachment,
+		content: Vec<u8>,
+	) -> Result<Attachment> {
+		let attachment = Attachment {
+			task_id,
+			size: content.len() as i64,
+			..attachment
+		};
+		fs::write(Self::attachment_path(&attachment)?, content)
+			.context("Failed to store the attached file.")?;
+
+		let row: QueryableAttachment = attachment.clone().into();
+		if let Err(err) = diesel::insert_into(attachments::table)
+			.values(&row)
+			.execute(&mut Database::establish_connection()?)
+		{
+			Self::remove_attachment_file(&attachment.id)?;
+			bail!("Failed to attach the file: {err}")
+		}
+
+		Ok(attachment)
+	}
+
+	async fn read_attachment_content(
+		&mut self,
+		_list_id: String,
+		_task_id: String,
+		attachment: Attachment,
+	) -> Result<PathBuf> {
+		let path = Self::attachment_path(&attachment)?;
+		if !path.exists() {
+			bail!("The attached file {} is missing.", attachment.name)
+		}
+		Ok(path)
+	}
+
+	async fn delete_attachment(
+		&mut self,
+		_list_id: String,
+		_task_id: String,
+		attachment_id: String,
+	) -> Result<()> {
+		diesel::delete(
+			attachments::table.filter(attachments::id_attachment.eq(&attachment_id)),
+		)
+		.execute(&mut Database::establish_connection()?)
+		.context("Failed to remove attachment.")?;
+		Self::remove_attachment_file(&attachment_id)
+	}
+
 	async fn read_lists(&mut self) -> Result<Vec<List>> {
-		let results =
-			lists.load::<QueryableList>(&mut Database::establish_connection()?)?;
+		let results = lists
+			.filter(list_deletion_date.is_null())
+			.load::<QueryableList>(&mut Database::establish_connection()?)?;
 
 		let results: Vec<List> = results.iter().map(|t| t.clone().into()).collect();
 		Ok(results)
@@ -181,7 +1141,16 @@ impl TodoProvider for ComputerStorage {
 		let list: QueryableList = list.into();
 
 		diesel::update(lists.filter(id_list.eq(list.id_list.clone())))
-			.set((name.eq(list.name.clone()), icon_name.eq(list.icon_name)))
+			.set((
+				name.eq(list.name.clone()),
+				description.eq(list.description),
+				icon_name.eq(list.icon_name),
+				id_group.eq(list.id_group),
+				color.eq(list.color),
+				sort_order.eq(list.sort_order),
+				show_completed.eq(list.show_completed),
+				grouping.eq(list.grouping),
+			))
 			.execute(&mut Database::establish_connection()?)
 			.context("Failed to update list.")?;
 
@@ -189,8 +1158,247 @@ impl TodoProvider for ComputerStorage {
 	}
 
 	async fn delete_list(&mut self, id: String) -> Result<()> {
-		diesel::delete(lists.filter(id_list.eq(id)))
+		let mut connection = Database::establish_connection()?;
+		let deleted: Option<NaiveDateTime> = lists
+			.find(id.clone())
+			.select(list_deletion_date)
+			.first(&mut connection)
+			.context("Failed to fetch list.")?;
+
+		if deleted.is_some() {
+			let attachment_ids =
+				connection.transaction::<_, anyhow::Error, _>(|connection| {
+					let list_tasks = tasks
+						.filter(parent.eq(id.clone()))
+						.select(id_task)
+						.load::<String>(connection)?;
+					let attachment_ids = Self::purge_tasks(connection, list_tasks)?;
+					diesel::delete(lists.filter(id_list.eq(id))).execute(connection)?;
+					Ok(attachment_ids)
+				})?;
+			Self::remove_attachment_files(attachment_ids);
+		} else {
+			diesel::update(lists.filter(id_list.eq(id)))
+				.set(list_deletion_date.eq(Some(Utc::now().naive_utc())))
+				.execute(&mut connection)
+				.context("Failed to move list to the trash.")?;
+		}
+		Ok(())
+	}
+
+	async fn read_groups(&mut self) -> Result<Vec<Group>> {
+		Self::read_service_groups(Service::Computer)
+	}
+
+	async fn create_group(&mut self, group: Group) -> Result<Group> {
+		Self::create_service_group(group)
+	}
+
+	async fn update_group(&mut self, group: Group) -> Result<()> {
+		Self::update_service_group(group)
+	}
+
+	async fn delete_group(&mut self, id: String) -> Result<()> {
+		Self::delete_service_group(id)
+	}
+
+	async fn read_tags(&mut self) -> Result<Vec<Tag>> {
+		let results = tags::table
+			.order(tags::name)
+			.load::<QueryableTag>(&mut Database::establish_connection()?)?;
+
+		Ok(results.into_iter().map(|tag| tag.into()).collect())
+	}
+
+	async fn search(&mut self, query: String) -> Result<Vec<SearchResult>> {
+		let Some(expression) = Self::match_expression(&query) else {
+			return Ok(vec![]);
+		};
+		let mut connection = Database::establish_connection()?;
+		let mut results = vec![];
+
+		let list_ids = Self::search_index(
+			&mut connection,
+			"SELECT lists.id_list AS id
+			FROM list_search
+				INNER JOIN lists ON lists.rowid = list_search.rowid
+			WHERE list_search MATCH ? AND lists.deletion_date IS NULL
+			ORDER BY list_search.rank
+			LIMIT ?",
+			&expression,
+		)?;
+		for id in list_ids {
+			results.push(SearchResult::list(self.read_list(id).await?));
+		}
+
+		// Sub-tasks are shown inside their task, so a match in a sub-task, at
+		// any depth, finds the top level task it belongs to.
+		let mut task_ids = Self::search_index(
+			&mut connection,
+			"WITH RECURSIVE matches(id, parent_id, rank) AS (
+				SELECT tasks.id_task, tasks.parent_task, task_search.rank
+				FROM task_search
+					INNER JOIN tasks ON tasks.rowid = task_search.rowid
+				WHERE task_search MATCH ? AND tasks.deletion_date IS NULL
+				UNION
+				SELECT tasks.id_task, tasks.parent_task, matches.rank
+				FROM matches
+					INNER JOIN tasks ON tasks.id_task = matches.parent_id
+			)
+			SELECT id FROM matches
+			WHERE parent_id IS NULL
+			ORDER BY rank
+			LIMIT ?",
+			&expression,
+		)?;
+		let mut seen = HashSet::new();
+		task_ids.retain(|id| seen.insert(id.clone()));
+		let task_rows = tasks
+			.filter(id_task.eq_any(&task_ids))
+			.filter(deletion_date.is_null())
+			.filter(parent.ne_all(Self::deleted_list_ids(&mut connection)?))
+			.load::<QueryableTask>(&mut connection)?;
+		let mut found = Self::into_tasks(&mut connection, task_rows)?;
+		found.sort_by_key(|task| task_ids.iter().position(|id| id == &task.id));
+
+		let mut parents: HashMap<String, List> = HashMap::new();
+		for task in found {
+			if !parents.contains_key(&task.parent) {
+				let list = self.read_list(task.parent.clone()).await?;
+				parents.insert(task.parent.clone(), list);
+			}
+			results.push(SearchResult::task(parents[&task.parent].clone(), task));
+		}
+
+		Ok(results)
+	}
+
+	async fn read_tasks_with_tag(&mut self, tag: String) -> Result<Vec<Task>> {
+		let mut connection = Database::establish_connection()?;
+		let task_ids = task_tags::table
+			.inner_join(tags::table)
+			.filter(tags::name.eq(tag))
+			.select(task_tags::id_task)
+			.load::<String>(&mut connection)?;
+		let response = tasks
+			.filter(id_task.eq_any(task_ids))
+			.filter(deletion_date.is_null())
+			.filter(parent.ne_all(Self::deleted_list_ids(&mut connection)?))
+			.load::<QueryableTask>(&mut connection)?;
+
+		Self::into_tasks(&mut connection, response)
+	}
+
+	async fn create_tag(&mut self, tag: Tag) -> Result<Tag> {
+		let tag: QueryableTag = tag.into();
+
+		diesel::insert_into(tags::table)
+			.values(&tag)
+			.execute(&mut Database::establish_connection()?)
+			.context("Failed to create tag.")?;
+
+		Ok(tag.into())
+	}
+
+	async fn update_tag(&mut self, tag: Tag) -> Result<()> {
+		diesel::update(tags::table.filter(tags::id_tag.eq(tag.id)))
+			.set(tags::name.eq(tag.name))
+			.execute(&mut Database::establish_connection()?)
+			.context("Failed to update tag.")?;
+
+		Ok(())
+	}
+
+	async fn delete_tag(&mut self, id: String) -> Result<()> {
+		diesel::delete(tags::table.filter(tags::id_tag.eq(id)))
 			.execute(&mut Database::establish_connection()?)?;
 		Ok(())
 	}
+
+	async fn read_deleted_tasks(&mut self) -> Result<Vec<Task>> {
+		let mut connection = Database::establish_connection()?;
+		let response = tasks
+			.filter(parent_task.is_null())
+			.filter(deletion_date.is_not_null())
+			.order(deletion_date.desc())
+			.load::<QueryableTask>(&mut connection)?;
+
+		Self::into_tasks(&mut connection, response)
+	}
+
+	async fn read_deleted_lists(&mut self) -> Result<Vec<List>> {
+		let results = lists
+			.filter(list_deletion_date.is_not_null())
+			.order(list_deletion_date.desc())
+			.load::<QueryableList>(&mut Database::establish_connection()?)?;
+
+		Ok(results.into_iter().map(|list| list.into()).collect())
+	}
+
+	async fn restore_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+	) -> Result<()> {
+		Database::establish_connection()?.transaction::<_, anyhow::Error, _>(
+			|connection| {
+				diesel::update(tasks.filter(id_task.eq(task_id)))
+					.set(deletion_date.eq(None::<NaiveDateTime>))
+					.execute(connection)
+					.context("Failed to restore task.")?;
+				diesel::update(lists.filter(id_list.eq(list_id)))
+					.set(list_deletion_date.eq(None::<NaiveDateTime>))
+					.execute(connection)
+					.context("Failed to restore the list of the task.")?;
+				Ok(())
+			},
+		)
+	}
+
+	async fn restore_list(&mut self, id: String) -> Result<()> {
+		diesel::update(lists.filter(id_list.eq(id)))
+			.set(list_deletion_date.eq(None::<NaiveDateTime>))
+			.execute(&mut Database::establish_connection()?)
+			.context("Failed to restore list.")?;
+		Ok(())
+	}
+
+	async fn purge_deleted(&mut self, before: DateTime<Utc>) -> Result<()> {
+		let attachment_ids = Database::establish_connection()?
+			.transaction::<_, anyhow::Error, _>(|connection| {
+				let expired_tasks = tasks
+					.filter(deletion_date.lt(before.naive_utc()))
+					.select(id_task)
+					.load::<String>(connection)?;
+				let mut attachment_ids = Self::purge_tasks(connection, expired_tasks)?;
+				let expired_lists = lists
+					.filter(list_deletion_date.lt(before.naive_utc()))
+					.select(id_list)
+					.load::<String>(connection)?;
+				let list_tasks = tasks
+					.filter(parent.eq_any(expired_lists.clone()))
+					.select(id_task)
+					.load::<String>(connection)?;
+				attachment_ids.extend(Self::purge_tasks(connection, list_tasks)?);
+				diesel::delete(lists.filter(id_list.eq_any(expired_lists)))
+					.execute(connection)?;
+				Ok(attachment_ids)
+			})?;
+		Self::remove_attachment_files(attachment_ids);
+		Ok(())
+	}
+}
+
+/// Escapes everything but letters, digits, `-` and `_`, so that the name can
+/// only be a single file name.
+fn encode_file_name(text: &str) -> String {
+	text
+		.bytes()
+		.map(|byte| match byte {
+			b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => {
+				(byte as char).to_string()
+			},
+			_ => format!("%{byte:02X}"),
+		})
+		.collect()
 }
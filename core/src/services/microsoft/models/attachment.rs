@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskFileAttachment {
+	#[serde(rename = "@odata.type")]
+	pub odata_type: String,
+	#[serde(skip_serializing)]
+	pub id: String,
+	pub name: String,
+	pub content_type: String,
+	#[serde(skip_serializing)]
+	pub size: i64,
+	#[serde(skip_serializing)]
+	pub last_modified_date_time: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub content_bytes: Option<String>,
+}
+
+impl TaskFileAttachment {
+	pub const ODATA_TYPE: &'static str = "#microsoft.graph.taskFileAttachment";
+
+	/// Files above this size have to be uploaded through an upload session
+	/// instead of being sent inline.
+	pub const MAX_INLINE_SIZE: i64 = 3 * 1024 * 1024;
+}
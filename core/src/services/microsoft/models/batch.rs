@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The body of a JSON `$batch` request.
+#[derive(Serialize, Debug, Default)]
+pub struct Batch {
+	pub requests: Vec<BatchRequest>,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRequest {
+	pub id: String,
+	pub method: String,
+	pub url: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub body: Option<Value>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub headers: Option<Value>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub depends_on: Vec<String>,
+}
+
+impl BatchRequest {
+	pub fn new(method: &str, url: String, body: Option<Value>) -> Self {
+		Self {
+			method: method.to_string(),
+			url,
+			headers: body
+				.is_some()
+				.then(|| serde_json::json!({ "Content-Type": "application/json" })),
+			body,
+			..Default::default()
+		}
+	}
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct BatchResponses {
+	pub responses: Vec<BatchResponse>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct BatchResponse {
+	pub id: String,
+	pub status: u16,
+	#[serde(default)]
+	pub body: Option<Value>,
+}
+
+impl BatchResponse {
+	pub fn succeeded(&self) -> bool {
+		(200..300).contains(&self.status)
+	}
+
+	/// The message Graph sent back with a failed request.
+	pub fn error(&self) -> String {
+		self
+			.body
+			.as_ref()
+			.and_then(|body| body["error"]["message"].as_str())
+			.map(str::to_string)
+			.unwrap_or_else(|| format!("status {}", self.status))
+	}
+}
@@ -1,6 +1,9 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
+use crate::time_zone;
+
 #[derive(
 	Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord,
 )]
@@ -19,15 +22,121 @@ impl From<DateTimeTimeZone> for DateTime<Utc> {
 				})
 				.expect("Failed to parse date string");
 
-		DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc)
+		let zone =
+			time_zone::parse_time_zone(&date.time_zone).unwrap_or_else(|| {
+				tracing::error!("Unknown time zone {}, using UTC.", date.time_zone);
+				Tz::UTC
+			});
+		time_zone::from_local(datetime, zone)
 	}
 }
 
 impl From<DateTime<Utc>> for DateTimeTimeZone {
+	/// Dates are sent in the user's zone so that Graph, which only keeps the
+	/// day of due dates, lands on the same day the user picked.
 	fn from(date: DateTime<Utc>) -> Self {
+		let mut zone = time_zone::user_time_zone();
+		let local = date.with_timezone(&zone).naive_local();
+		// A time repeated when the clocks go back can't be told apart by its
+		// wall clock alone, so it is sent in UTC instead.
+		if zone.from_local_datetime(&local).single().is_none() {
+			zone = Tz::UTC;
+		}
 		Self {
-			date_time: date.format("%Y-%m-%dT%H:%M:%S").to_string(),
-			time_zone: date.timezone().to_string(),
+			date_time: date
+				.with_timezone(&zone)
+				.format("%Y-%m-%dT%H:%M:%S")
+				.to_string(),
+			time_zone: time_zone::windows_time_zone(zone).to_string(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::Duration;
+
+	use super::*;
+
+	fn graph_date(date_time: &str, time_zone: &str) -> DateTimeTimeZone {
+		DateTimeTimeZone {
+			date_time: date_time.to_string(),
+			time_zone: time_zone.to_string(),
+		}
+	}
+
+	fn utc(text: &str) -> DateTime<Utc> {
+		NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M")
+			.unwrap()
+			.and_utc()
+	}
+
+	// The user's zone is shared, so everything that sets it is in one test.
+	#[test]
+	fn dates_round_trip_through_graph() {
+		time_zone::set_user_time_zone(Some("America/Los_Angeles"));
+
+		let date =
+			graph_date("2026-07-01T09:00:00.0000000", "Pacific Standard Time");
+		let instant: DateTime<Utc> = date.into();
+		assert_eq!(instant, utc("2026-07-01 16:00"));
+		assert_eq!(
+			DateTimeTimeZone::from(instant),
+			graph_date("2026-07-01T09:00:00", "Pacific Standard Time")
+		);
+
+		// 02:30 doesn't exist when the clocks go forward, it becomes 03:30.
+		let date = graph_date("2026-03-08T02:30:00", "Pacific Standard Time");
+		let instant: DateTime<Utc> = date.into();
+		assert_eq!(instant, utc("2026-03-08 10:30"));
+		assert_eq!(
+			DateTimeTimeZone::from(instant),
+			graph_date("2026-03-08T03:30:00", "Pacific Standard Time")
+		);
+
+		// 01:30 happens twice when the clocks go back, the first one is meant
+		// and the second one is sent in UTC to keep them apart.
+		let date = graph_date("2026-11-01T01:30:00", "Pacific Standard Time");
+		let first: DateTime<Utc> = date.into();
+		assert_eq!(first, utc("2026-11-01 08:30"));
+		let second = first + Duration::hours(1);
+		assert_eq!(
+			DateTimeTimeZone::from(second),
+			graph_date("2026-11-01T09:30:00", "UTC")
+		);
+		assert_eq!(
+			DateTime::<Utc>::from(DateTimeTimeZone::from(second)),
+			second
+		);
+
+		// Zones missing from the table go by their IANA name.
+		time_zone::set_user_time_zone(Some("Europe/Madrid"));
+		let instant = utc("2026-07-01 07:00");
+		let date = DateTimeTimeZone::from(instant);
+		assert_eq!(date, graph_date("2026-07-01T09:00:00", "Europe/Madrid"));
+		assert_eq!(DateTime::<Utc>::from(date), instant);
+
+		// Unknown zones are read as UTC.
+		let date = graph_date("2026-10-25T02:30:00", "Mars Standard Time");
+		assert_eq!(DateTime::<Utc>::from(date), utc("2026-10-25 02:30"));
+
+		// Every instant around both transitions comes back unchanged.
+		for zone in ["America/Los_Angeles", "Europe/Madrid"] {
+			time_zone::set_user_time_zone(Some(zone));
+			for day in [
+				"2026-03-08 00:00",
+				"2026-03-29 00:00",
+				"2026-10-25 00:00",
+				"2026-11-01 00:00",
+			] {
+				for quarter in 0..(4 * 36) {
+					let instant = utc(day) + Duration::minutes(15 * quarter);
+					let date = DateTimeTimeZone::from(instant);
+					assert_eq!(DateTime::<Utc>::from(date.clone()), instant, "{date:?}");
+				}
+			}
 		}
+
+		time_zone::set_user_time_zone(None);
 	}
 }
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskLinkedResource {
+	#[serde(skip_serializing)]
+	pub id: String,
+	pub web_url: Option<String>,
+	pub display_name: Option<String>,
+	pub application_name: Option<String>,
+}
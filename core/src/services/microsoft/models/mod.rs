@@ -1,8 +1,11 @@
+pub mod attachment;
+pub mod batch;
 pub mod body;
 pub mod checklist_item;
 pub mod collection;
 pub mod date_time_zone;
 pub mod importance;
+pub mod linked_resource;
 pub mod list;
 pub mod recurrence;
 pub mod status;
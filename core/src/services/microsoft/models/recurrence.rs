@@ -15,11 +15,11 @@ pub struct TaskRecurrence {
 #[serde(rename_all = "camelCase")]
 pub struct TaskRecurrenceRange {
 	#[serde(rename = "type")]
-	recurrence_type: RecurrenceRangeType,
-	start_date: Option<String>,
-	end_date: Option<String>,
-	recurrence_time_zone: String,
-	number_of_occurrences: i32,
+	pub recurrence_type: RecurrenceRangeType,
+	pub start_date: Option<String>,
+	pub end_date: Option<String>,
+	pub recurrence_time_zone: String,
+	pub number_of_occurrences: i32,
 }
 
 #[derive(
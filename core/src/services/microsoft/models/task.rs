@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use super::{
 	body::ItemBody, checklist_item::ChecklistItem,
 	date_time_zone::DateTimeTimeZone, importance::TaskImportance,
-	recurrence::TaskRecurrence, status::TaskStatus,
+	linked_resource::TaskLinkedResource, recurrence::TaskRecurrence,
+	status::TaskStatus,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -21,8 +23,31 @@ pub struct TodoTask {
 	pub status: TaskStatus,
 	pub has_attachments: bool,
 	pub checklist_items: Option<Vec<ChecklistItem>>,
+	/// Graph only takes links along with a task when it is created, updates
+	/// leave this empty and save them on their own.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub linked_resources: Option<Vec<TaskLinkedResource>>,
 	pub created_date_time: String,
 	pub last_modified_date_time: String,
 	pub reminder_date_time: Option<DateTimeTimeZone>,
 	pub start_date_time: Option<DateTimeTimeZone>,
 }
+
+impl TodoTask {
+	/// The task as a body to create it with, without the fields Graph sets
+	/// on its own and refuses to be sent.
+	pub fn into_create_body(self) -> Value {
+		let mut body = serde_json::json!(self);
+		if let Some(fields) = body.as_object_mut() {
+			for field in [
+				"id",
+				"createdDateTime",
+				"lastModifiedDateTime",
+				"hasAttachments",
+			] {
+				fields.remove(field);
+			}
+		}
+		body
+	}
+}
@@ -1,18 +1,43 @@
-use std::pin::Pin;
+use std::{collections::HashMap, fs, path::PathBuf, pin::Pin};
 
+use crate::models::attachment::Attachment;
+use crate::models::dependency::Dependency;
+use crate::models::group::Group;
+use crate::models::linked_resource::LinkedResource;
 use crate::models::list::List;
+use crate::models::list_settings::ListSettings;
+use crate::models::placement::Placement;
+use crate::models::search::SearchResult;
+use crate::models::tag::Tag;
 use crate::models::task::Task;
+use crate::models::task_change::TaskChange;
+use crate::schema::{
+	remote_list_groups, remote_list_settings, remote_task_positions,
+};
+use crate::service::Service;
+use crate::services::local::database::{
+	models::list::QueryableListSettings, Database,
+};
+use crate::services::local::service::ComputerStorage;
 use crate::services::microsoft::models::{
-	checklist_item::ChecklistItem, collection::Collection, list::TodoTaskList,
+	attachment::TaskFileAttachment,
+	batch::{Batch, BatchRequest, BatchResponse, BatchResponses},
+	checklist_item::ChecklistItem,
+	collection::Collection,
+	linked_resource::TaskLinkedResource,
+	list::TodoTaskList,
 	task::TodoTask,
 };
 use crate::task_service::TodoProvider;
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Utc};
+use diesel::{Connection, ExpressionMethods, QueryDsl, RunQueryDsl};
 use futures::{Stream, StreamExt};
 use graph_rs_sdk::{
 	oauth::{AccessToken, OAuth},
-	Graph,
+	Graph, ODataQuery,
 };
 use reqwest::StatusCode;
 use url::Url;
@@ -21,6 +46,9 @@ pub const APP_ID: &str = "dev.edfloreshz.Done";
 const CLIENT_ID: &str = "75d8509b-cf9b-4245-9550-1e5f1d7c66e4";
 const REDIRECT_URI: &str = "done://msft";
 
+/// Graph takes at most this many requests in a single `$batch`.
+const BATCH_LIMIT: usize = 20;
+
 #[derive(Debug, Clone)]
 
 pub struct MicrosoftService {
@@ -44,6 +72,16 @@ impl MicrosoftService {
 		}
 	}
 
+	/// Creates a deleted task again to roll a batch back, Graph gives it a
+	/// new id.
+	fn restore_task_request(list_id: &str, task: TodoTask) -> BatchRequest {
+		BatchRequest::new(
+			"POST",
+			format!("/me/todo/lists/{list_id}/tasks"),
+			Some(task.into_create_body()),
+		)
+	}
+
 	fn oauth_client() -> OAuth {
 		let mut oauth = OAuth::new();
 		oauth
@@ -115,6 +153,314 @@ impl MicrosoftService {
 		}
 	}
 
+	/// Microsoft To Do has no tag endpoint, tags are the categories of the tasks,
+	/// so every operation on tags has to go through the tasks of every list.
+	async fn read_all_tasks(&mut self) -> Result<Vec<Task>> {
+		let mut tasks = vec![];
+		for list in self.read_lists().await? {
+			tasks.extend(self.read_tasks_from_list(list.id).await?);
+		}
+		Ok(tasks)
+	}
+
+	/// Graph has no ordering for tasks, so the positions set by the user are
+	/// kept in the local database.
+	fn local_positions(list_id: &str) -> Result<HashMap<String, i32>> {
+		Ok(
+			remote_task_positions::table
+				.filter(remote_task_positions::id_list.eq(list_id))
+				.select((
+					remote_task_positions::id_task,
+					remote_task_positions::position,
+				))
+				.load::<(String, i32)>(&mut Database::establish_connection()?)?
+				.into_iter()
+				.collect(),
+		)
+	}
+
+	/// Graph does not expose the groups of Microsoft To Do, so the group each
+	/// list is filed under is kept in the local database.
+	fn local_groups() -> Result<HashMap<String, String>> {
+		Ok(
+			remote_list_groups::table
+				.select((remote_list_groups::id_list, remote_list_groups::id_group))
+				.load::<(String, String)>(&mut Database::establish_connection()?)?
+				.into_iter()
+				.collect(),
+		)
+	}
+
+	fn save_local_group(list: &List) -> Result<()> {
+		let mut connection = Database::establish_connection()?;
+		match &list.group {
+			Some(group) => diesel::replace_into(remote_list_groups::table)
+				.values((
+					remote_list_groups::id_list.eq(&list.id),
+					remote_list_groups::id_group.eq(group),
+				))
+				.execute(&mut connection),
+			None => diesel::delete(
+				remote_list_groups::table
+					.filter(remote_list_groups::id_list.eq(&list.id)),
+			)
+			.execute(&mut connection),
+		}
+		.context("Failed to save the group of the list.")?;
+		Ok(())
+	}
+
+	/// The color, sort order and the rest of the view settings of a list are
+	/// not part of Graph either, they are kept locally like the groups.
+	fn local_settings() -> Result<HashMap<String, ListSettings>> {
+		Ok(
+			remote_list_settings::table
+				.load::<QueryableListSettings>(&mut Database::establish_connection()?)?
+				.into_iter()
+				.map(|settings| (settings.id_list.clone(), settings.into()))
+				.collect(),
+		)
+	}
+
+	fn save_local_settings(list: &List) -> Result<()> {
+		diesel::replace_into(remote_list_settings::table)
+			.values(QueryableListSettings::new(&list.id, &list.settings))
+			.execute(&mut Database::establish_connection()?)
+			.context("Failed to save the settings of the list.")?;
+		Ok(())
+	}
+
+	/// The task payload only says whether a task has attachments, their
+	/// metadata has to be fetched separately.
+	async fn read_task_attachments(
+		client: &Graph,
+		list_id: &str,
+		task_id: &str,
+	) -> Result<Vec<Attachment>> {
+		let response = client
+			.me()
+			.todo()
+			.list(list_id)
+			.task(task_id)
+			.list_attachments()
+			.send()
+			.await?;
+		let collection: Collection<TaskFileAttachment> =
+			response.error_for_status()?.json().await?;
+		Ok(
+			collection
+				.value
+				.into_iter()
+				.map(|attachment| Attachment {
+					task_id: task_id.to_string(),
+					..attachment.into()
+				})
+				.collect(),
+		)
+	}
+
+	/// Links are a collection of their own in Graph, so they are compared by
+	/// address and only the ones added or removed are sent.
+	async fn update_linked_resources(
+		&self,
+		list_id: &str,
+		task_id: &str,
+		linked_resources: &[LinkedResource],
+	) -> Result<Vec<LinkedResource>> {
+		let response = self
+			.client
+			.me()
+			.todo()
+			.list(list_id)
+			.task(task_id)
+			.list_linked_resources()
+			.send()
+			.await?;
+		let remote: Collection<TaskLinkedResource> =
+			response.error_for_status()?.json().await?;
+
+		let mut saved = vec![];
+		for resource in remote.value {
+			if linked_resources
+				.iter()
+				.any(|local| Some(&local.web_url) == resource.web_url.as_ref())
+			{
+				saved.push(resource.into());
+				continue;
+			}
+			let response = self
+				.client
+				.me()
+				.todo()
+				.list(list_id)
+				.task(task_id)
+				.delete_linked_resources(&resource.id)
+				.send()
+				.await?;
+			if let Err(err) = response.error_for_status() {
+				bail!("An error ocurred while removing a link: {err}")
+			}
+		}
+
+		for resource in linked_resources {
+			if saved
+				.iter()
+				.any(|remote: &LinkedResource| remote.web_url == resource.web_url)
+			{
+				continue;
+			}
+			let response = self
+				.client
+				.me()
+				.todo()
+				.list(list_id)
+				.task(task_id)
+				.create_linked_resources(&serde_json::json!(TaskLinkedResource::from(
+					resource.clone()
+				)))
+				.send()
+				.await?;
+			match response.error_for_status() {
+				Ok(response) => {
+					let created: TaskLinkedResource = response.json().await?;
+					saved.push(created.into());
+				},
+				Err(err) => bail!("An error ocurred while adding a link: {err}"),
+			}
+		}
+		Ok(saved)
+	}
+
+	/// Converts a task from Graph, fetching its attachments if it has any.
+	async fn into_task(
+		client: &Graph,
+		list_id: &str,
+		todo_task: TodoTask,
+	) -> Task {
+		let has_attachments = todo_task.has_attachments;
+		let mut task: Task = todo_task.into();
+		task.parent = list_id.to_string();
+		if has_attachments {
+			match Self::read_task_attachments(client, list_id, &task.id).await {
+				Ok(attachments) => task.attachments = attachments,
+				Err(err) => {
+					tracing::error!("There was an error getting the attachments: {}", err)
+				},
+			}
+		}
+		task
+	}
+
+	/// Sends requests through Graph's JSON `$batch` endpoint. Every request
+	/// waits for the one before it, so the responses stop at the first one
+	/// that failed.
+	async fn send_batch(
+		&mut self,
+		requests: Vec<BatchRequest>,
+	) -> Result<Vec<BatchResponse>> {
+		self.refresh_token().await?;
+		let mut responses = vec![];
+		for chunk in requests.chunks(BATCH_LIMIT) {
+			let mut batch = Batch::default();
+			for (index, request) in chunk.iter().cloned().enumerate() {
+				batch.requests.push(BatchRequest {
+					id: (index + 1).to_string(),
+					depends_on: if index > 0 {
+						vec![index.to_string()]
+					} else {
+						vec![]
+					},
+					..request
+				});
+			}
+			let response =
+				self.client.batch(&serde_json::json!(batch)).send().await?;
+			let mut batch: BatchResponses =
+				response.error_for_status()?.json().await?;
+			batch.responses.sort_by_key(|response| {
+				response.id.parse::<usize>().unwrap_or_default()
+			});
+			let failed = batch
+				.responses
+				.iter()
+				.position(|response| !response.succeeded());
+			match failed {
+				Some(index) => {
+					responses.extend(batch.responses.into_iter().take(index + 1));
+					break;
+				},
+				None => responses.extend(batch.responses),
+			}
+		}
+		Ok(responses)
+	}
+
+	/// Graph does not run batches in a transaction, so when a request fails
+	/// the ones that succeeded before it are undone with the requests `undo`
+	/// returns for them.
+	async fn run_batch(
+		&mut self,
+		requests: Vec<BatchRequest>,
+		undo: impl Fn(usize, &BatchResponse) -> Option<BatchRequest>,
+	) -> Result<Vec<BatchResponse>> {
+		let responses = self.send_batch(requests).await?;
+		let Some(failed) = responses.iter().find(|response| !response.succeeded())
+		else {
+			return Ok(responses);
+		};
+		let error = failed.error();
+		let rollback: Vec<BatchRequest> = responses
+			.iter()
+			.enumerate()
+			.filter(|(_, response)| response.succeeded())
+			.filter_map(|(index, response)| undo(index, response))
+			.rev()
+			.collect();
+		if !rollback.is_empty()
+			&& self
+				.send_batch(rollback)
+				.await?
+				.iter()
+				.any(|response| !response.succeeded())
+		{
+			bail!("{error}. Some of the changes could not be undone.")
+		}
+		bail!("{error}. No changes were made.")
+	}
+
+	/// Fetches the current version of tasks, along with their sub-tasks and
+	/// links, before a batch changes them.
+	async fn read_batch_tasks(
+		&mut self,
+		tasks: &[Task],
+	) -> Result<Vec<TodoTask>> {
+		let requests = tasks
+			.iter()
+			.map(|task| {
+				BatchRequest::new(
+					"GET",
+					format!(
+						"/me/todo/lists/{}/tasks/{}?$expand=checklistItems,linkedResources",
+						task.parent, task.id
+					),
+					None,
+				)
+			})
+			.collect();
+		let responses = self.send_batch(requests).await?;
+		if let Some(failed) =
+			responses.iter().find(|response| !response.succeeded())
+		{
+			bail!("{}. No changes were made.", failed.error())
+		}
+		responses
+			.into_iter()
+			.map(|response| {
+				Ok(serde_json::from_value(response.body.unwrap_or_default())?)
+			})
+			.collect()
+	}
+
 	pub async fn update_check_list_items(
 		&self,
 		todo_task_list_id: &str,
@@ -194,20 +540,18 @@ impl TodoProvider for MicrosoftService {
 			.list(parent_list.clone())
 			.tasks()
 			.list_tasks()
+			.expand(&["linkedResources"])
 			.send()
 			.await?;
 		let collection: Collection<TodoTask> = response.json().await?;
-		Ok(
-			collection
-				.value
-				.iter()
-				.map(|task| {
-					let mut task: Task = task.clone().into();
-					task.parent = parent_list.clone();
-					task
-				})
-				.collect(),
-		)
+		let positions = Self::local_positions(&parent_list)?;
+		let mut tasks: Vec<Task> = vec![];
+		for todo_task in collection.value {
+			tasks.push(Self::into_task(&self.client, &parent_list, todo_task).await);
+		}
+		tasks
+			.sort_by_key(|task| positions.get(&task.id).copied().unwrap_or(i32::MAX));
+		Ok(tasks)
 	}
 
 	async fn get_tasks(
@@ -215,6 +559,7 @@ impl TodoProvider for MicrosoftService {
 		parent_list: String,
 	) -> Result<Pin<Box<dyn Stream<Item = Task> + Send>>> {
 		self.refresh_token().await?;
+		let client = self.client.clone();
 		let mut stream = self
 			.client
 			.me()
@@ -222,27 +567,35 @@ impl TodoProvider for MicrosoftService {
 			.list(parent_list.clone())
 			.tasks()
 			.list_tasks()
+			.expand(&["linkedResources"])
 			.paging()
 			.stream::<serde_json::Value>()?
-			.filter_map(|response| async move {
-				match response {
-					Ok(response) => {
-						let value = response.into_body().ok()?;
-						let tasks: Vec<serde_json::Value> =
-							value["value"].as_array().cloned()?;
+			.filter_map(move |response| {
+				let client = client.clone();
+				let parent_list = parent_list.clone();
+				async move {
+					match response {
+						Ok(response) => {
+							let value = response.into_body().ok()?;
+							let tasks: Vec<serde_json::Value> =
+								value["value"].as_array().cloned()?;
 
-						let task_list = tasks
-							.iter()
-							.flat_map(|t| serde_json::from_value(t.clone()).ok())
-							.flat_map(|t: TodoTask| Some(t.into()))
-							.collect::<Vec<Task>>();
+							let mut task_list = vec![];
+							for todo_task in tasks.iter().flat_map(|t| {
+								serde_json::from_value::<TodoTask>(t.clone()).ok()
+							}) {
+								task_list.push(
+									Self::into_task(&client, &parent_list, todo_task).await,
+								);
+							}
 
-						Some(task_list)
-					},
-					Err(err) => {
-						tracing::error!("There was an error getting the tasks: {}", err);
-						None
-					},
+							Some(task_list)
+						},
+						Err(err) => {
+							tracing::error!("There was an error getting the tasks: {}", err);
+							None
+						},
+					}
 				}
 			})
 			.flat_map(futures::stream::iter)
@@ -264,12 +617,19 @@ impl TodoProvider for MicrosoftService {
 			.list(task_list_id.clone())
 			.task(task_id)
 			.get_tasks()
+			.expand(&["linkedResources"])
 			.send()
 			.await?;
 		let task: TodoTask = response.json().await?;
-		let mut task: Task = task.clone().into();
-		task.parent = task_list_id;
-		Ok(task)
+		Ok(Self::into_task(&self.client, &task_list_id, task).await)
+	}
+
+	async fn read_task_history(
+		&mut self,
+		_task_list_id: String,
+		_task_id: String,
+	) -> Result<Vec<TaskChange>> {
+		Ok(vec![])
 	}
 
 	async fn create_task(&mut self, task: Task) -> Result<()> {
@@ -303,6 +663,10 @@ impl TodoProvider for MicrosoftService {
 			)
 			.await?;
 		todo_task.checklist_items = None;
+		todo_task.linked_resources = None;
+		let linked_resources = self
+			.update_linked_resources(&task.parent, &task.id, &task.linked_resources)
+			.await?;
 		println!("{}", serde_json::json!(todo_task));
 		let response = self
 			.client
@@ -318,7 +682,10 @@ impl TodoProvider for MicrosoftService {
 		match response.error_for_status() {
 			Ok(response) => {
 				let task: TodoTask = response.json().await?;
-				Ok(task.into())
+				Ok(Task {
+					linked_resources,
+					..task.into()
+				})
 			},
 			Err(err) => {
 				bail!("An error ocurred while updating the list: {err}")
@@ -348,18 +715,359 @@ impl TodoProvider for MicrosoftService {
 		}
 	}
 
+	async fn create_tasks(&mut self, tasks: Vec<Task>) -> Result<()> {
+		let requests = tasks
+			.iter()
+			.map(|task| {
+				BatchRequest::new(
+					"POST",
+					format!("/me/todo/lists/{}/tasks", task.parent),
+					Some(serde_json::json!(TodoTask::from(task.clone()))),
+				)
+			})
+			.collect();
+		self
+			.run_batch(requests, |index, response| {
+				let id = response.body.as_ref()?["id"].as_str()?;
+				Some(BatchRequest::new(
+					"DELETE",
+					format!("/me/todo/lists/{}/tasks/{id}", tasks[index].parent),
+					None,
+				))
+			})
+			.await?;
+		Ok(())
+	}
+
+	/// Sub-tasks and links are not part of the batch, they are kept as they
+	/// are on Graph.
+	async fn update_tasks(&mut self, tasks: Vec<Task>) -> Result<Vec<Task>> {
+		let originals = self.read_batch_tasks(&tasks).await?;
+		let requests = tasks
+			.iter()
+			.map(|task| {
+				let mut todo_task: TodoTask = task.clone().into();
+				todo_task.checklist_items = None;
+				todo_task.linked_resources = None;
+				BatchRequest::new(
+					"PATCH",
+					format!("/me/todo/lists/{}/tasks/{}", task.parent, task.id),
+					Some(serde_json::json!(todo_task)),
+				)
+			})
+			.collect();
+		let responses = self
+			.run_batch(requests, |index, _| {
+				let mut original = originals[index].clone();
+				original.checklist_items = None;
+				original.linked_resources = None;
+				Some(BatchRequest::new(
+					"PATCH",
+					format!(
+						"/me/todo/lists/{}/tasks/{}",
+						tasks[index].parent, original.id
+					),
+					Some(serde_json::json!(original)),
+				))
+			})
+			.await?;
+
+		let mut updated = vec![];
+		for ((task, original), response) in
+			tasks.into_iter().zip(originals).zip(responses)
+		{
+			let todo_task: TodoTask =
+				serde_json::from_value(response.body.unwrap_or_default())?;
+			let original: Task = original.into();
+			updated.push(Task {
+				parent: task.parent,
+				attachments: task.attachments,
+				sub_tasks: original.sub_tasks,
+				linked_resources: original.linked_resources,
+				..todo_task.into()
+			});
+		}
+		Ok(updated)
+	}
+
+	/// Graph has no trash, deleted tasks are brought back as new tasks if the
+	/// batch has to be undone.
+	async fn delete_tasks(&mut self, tasks: Vec<Task>) -> Result<()> {
+		let originals = self.read_batch_tasks(&tasks).await?;
+		let requests = tasks
+			.iter()
+			.map(|task| {
+				BatchRequest::new(
+					"DELETE",
+					format!("/me/todo/lists/{}/tasks/{}", task.parent, task.id),
+					None,
+				)
+			})
+			.collect();
+		self
+			.run_batch(requests, |index, _| {
+				Some(Self::restore_task_request(
+					&tasks[index].parent,
+					originals[index].clone(),
+				))
+			})
+			.await?;
+		Ok(())
+	}
+
+	async fn reorder_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		placement: Placement,
+	) -> Result<()> {
+		let mut task_ids: Vec<String> = self
+			.read_tasks_from_list(list_id.clone())
+			.await?
+			.into_iter()
+			.map(|task| task.id)
+			.collect();
+		if !task_ids.contains(&task_id) {
+			bail!("Microsoft To Do can only reorder the tasks of a list.")
+		}
+		task_ids.retain(|id| id != &task_id);
+		let index = placement
+			.position(&task_ids)
+			.context("The task to move it next to is not in the list.")?;
+		task_ids.insert(index, task_id);
+
+		Database::establish_connection()?
+			.transaction::<_, anyhow::Error, _>(|connection| {
+				diesel::delete(
+					remote_task_positions::table
+						.filter(remote_task_positions::id_list.eq(list_id.clone())),
+				)
+				.execute(connection)?;
+				for (index, id) in task_ids.into_iter().enumerate() {
+					diesel::insert_into(remote_task_positions::table)
+						.values((
+							remote_task_positions::id_task.eq(id),
+							remote_task_positions::id_list.eq(list_id.clone()),
+							remote_task_positions::position.eq(index as i32),
+						))
+						.execute(connection)?;
+				}
+				Ok(())
+			})
+			.context("Failed to save the order of the tasks.")
+	}
+
+	async fn create_sub_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		sub_task: Task,
+	) -> Result<Task> {
+		self.refresh_token().await?;
+		let checklist_item: ChecklistItem = sub_task.into();
+		let response = self
+			.client
+			.me()
+			.todo()
+			.list(list_id)
+			.task(task_id)
+			.create_checklist_items(&serde_json::json!(checklist_item))
+			.send()
+			.await?;
+
+		match response.error_for_status() {
+			Ok(response) => {
+				let checklist_item: ChecklistItem = response.json().await?;
+				Ok(checklist_item.into())
+			},
+			Err(err) => {
+				bail!("An error ocurred while creating the sub-task: {err}")
+			},
+		}
+	}
+
+	async fn update_sub_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		sub_task: Task,
+	) -> Result<()> {
+		self.refresh_token().await?;
+		self
+			.update_check_list_items(&list_id, &task_id, &Some(vec![sub_task.into()]))
+			.await
+	}
+
+	async fn delete_sub_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		sub_task_id: String,
+	) -> Result<()> {
+		self.refresh_token().await?;
+		let response = self
+			.client
+			.me()
+			.todo()
+			.list(list_id)
+			.task(task_id)
+			.delete_checklist_items(sub_task_id)
+			.send()
+			.await?;
+
+		if response.status() == StatusCode::NO_CONTENT {
+			Ok(())
+		} else {
+			bail!("An error ocurred while deleting the sub-task.")
+		}
+	}
+
+	async fn add_dependency(
+		&mut self,
+		_list_id: String,
+		_task_id: String,
+		_blocking_task_id: String,
+	) -> Result<Dependency> {
+		bail!("Microsoft To Do does not support task dependencies.")
+	}
+
+	async fn remove_dependency(
+		&mut self,
+		_list_id: String,
+		_task_id: String,
+		_blocking_task_id: String,
+	) -> Result<()> {
+		bail!("Microsoft To Do does not support task dependencies.")
+	}
+
+	async fn read_attachments(
+		&mut self,
+		list_id: String,
+		task_id: String,
+	) -> Result<Vec<Attachment>> {
+		self.refresh_token().await?;
+		Self::read_task_attachments(&self.client, &list_id, &task_id).await
+	}
+
+	async fn create_attachment(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		attachment: Attachment,
+		content: Vec<u8>,
+	) -> Result<Attachment> {
+		self.refresh_token().await?;
+		if content.len() as i64 > TaskFileAttachment::MAX_INLINE_SIZE {
+			bail!("Microsoft To Do only accepts attachments of up to 3 MB.")
+		}
+		let mut file_attachment: TaskFileAttachment = attachment.into();
+		file_attachment.content_bytes = Some(STANDARD.encode(&content));
+		let response = self
+			.client
+			.me()
+			.todo()
+			.list(list_id)
+			.task(task_id.clone())
+			.create_attachments(&serde_json::json!(file_attachment))
+			.send()
+			.await?;
+
+		match response.error_for_status() {
+			Ok(response) => {
+				let file_attachment: TaskFileAttachment = response.json().await?;
+				Ok(Attachment {
+					task_id,
+					..file_attachment.into()
+				})
+			},
+			Err(err) => {
+				bail!("An error ocurred while uploading the attachment: {err}")
+			},
+		}
+	}
+
+	async fn read_attachment_content(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		attachment: Attachment,
+	) -> Result<PathBuf> {
+		let path = ComputerStorage::attachment_path(&attachment)?;
+		if path.exists() {
+			return Ok(path);
+		}
+
+		self.refresh_token().await?;
+		let response = self
+			.client
+			.me()
+			.todo()
+			.list(list_id)
+			.task(task_id)
+			.get_attachments(attachment.id.clone())
+			.send()
+			.await?;
+		let file_attachment: TaskFileAttachment =
+			response.error_for_status()?.json().await?;
+		let content = STANDARD
+			.decode(file_attachment.content_bytes.unwrap_or_default())
+			.context("The attachment content is not valid.")?;
+		fs::write(&path, content)
+			.context("Failed to save the downloaded attachment.")?;
+		Ok(path)
+	}
+
+	async fn delete_attachment(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		attachment_id: String,
+	) -> Result<()> {
+		self.refresh_token().await?;
+		let response = self
+			.client
+			.me()
+			.todo()
+			.list(list_id)
+			.task(task_id)
+			.delete_attachments(attachment_id)
+			.send()
+			.await?;
+
+		if response.status() == StatusCode::NO_CONTENT {
+			Ok(())
+		} else {
+			bail!("An error ocurred while deleting the attachment.")
+		}
+	}
+
 	async fn read_lists(&mut self) -> Result<Vec<List>> {
 		self.refresh_token().await?;
 		let response = self.client.me().todo().lists().list_lists().send().await?;
 
 		let lists: Collection<TodoTaskList> = response.json().await?;
-		Ok(lists.value.iter().map(|t| t.clone().into()).collect())
+		let groups = Self::local_groups()?;
+		let mut settings = Self::local_settings()?;
+		Ok(
+			lists
+				.value
+				.into_iter()
+				.map(|list| {
+					let mut list: List = list.into();
+					list.group = groups.get(&list.id).cloned();
+					list.settings = settings.remove(&list.id).unwrap_or_default();
+					list
+				})
+				.collect(),
+		)
 	}
 
 	async fn get_lists(
 		&mut self,
 	) -> Result<Pin<Box<dyn Stream<Item = List> + Send>>> {
 		self.refresh_token().await?;
+		let groups = Self::local_groups()?;
+		let mut settings = Self::local_settings()?;
 		let mut stream = self
 			.client
 			.me()
@@ -390,6 +1098,11 @@ impl TodoProvider for MicrosoftService {
 				}
 			})
 			.flat_map(futures::stream::iter)
+			.map(move |mut list: List| {
+				list.group = groups.get(&list.id).cloned();
+				list.settings = settings.remove(&list.id).unwrap_or_default();
+				list
+			})
 			.boxed();
 
 		Ok(stream)
@@ -399,7 +1112,11 @@ impl TodoProvider for MicrosoftService {
 		self.refresh_token().await?;
 		let response = self.client.me().todo().list(id).get_lists().send().await?;
 		let list: TodoTaskList = response.json().await?;
-		Ok(list.into())
+		let mut list: List = list.into();
+		list.group = Self::local_groups()?.get(&list.id).cloned();
+		list.settings =
+			Self::local_settings()?.remove(&list.id).unwrap_or_default();
+		Ok(list)
 	}
 
 	async fn create_list(&mut self, list: List) -> Result<List> {
@@ -425,6 +1142,17 @@ impl TodoProvider for MicrosoftService {
 
 	async fn update_list(&mut self, list: List) -> Result<()> {
 		self.refresh_token().await?;
+		if !list.is_owner {
+			let current = self.read_list(list.id.clone()).await?;
+			if current.name != list.name || current.icon != list.icon {
+				bail!("Only the owner of a shared list can rename it.")
+			}
+			Self::save_local_group(&list)?;
+			Self::save_local_settings(&list)?;
+			return Ok(());
+		}
+		Self::save_local_group(&list)?;
+		Self::save_local_settings(&list)?;
 		let list: TodoTaskList = list.into();
 		let response = self
 			.client
@@ -443,7 +1171,9 @@ impl TodoProvider for MicrosoftService {
 	}
 
 	async fn delete_list(&mut self, id: String) -> Result<()> {
-		self.refresh_token().await?;
+		if !self.read_list(id.clone()).await?.is_owner {
+			bail!("Only the owner of a shared list can delete it.")
+		}
 		let response = self
 			.client
 			.me()
@@ -458,4 +1188,194 @@ impl TodoProvider for MicrosoftService {
 			Err(err) => bail!("An error ocurred while deleting the list: {err}"),
 		}
 	}
+
+	async fn read_groups(&mut self) -> Result<Vec<Group>> {
+		ComputerStorage::read_service_groups(Service::Microsoft)
+	}
+
+	async fn create_group(&mut self, group: Group) -> Result<Group> {
+		ComputerStorage::create_service_group(Group {
+			service: Service::Microsoft,
+			..group
+		})
+	}
+
+	async fn update_group(&mut self, group: Group) -> Result<()> {
+		ComputerStorage::update_service_group(group)
+	}
+
+	async fn delete_group(&mut self, id: String) -> Result<()> {
+		ComputerStorage::delete_service_group(id)
+	}
+
+	async fn read_tags(&mut self) -> Result<Vec<Tag>> {
+		let mut names: Vec<String> = self
+			.read_all_tasks()
+			.await?
+			.into_iter()
+			.flat_map(|task| task.tags)
+			.collect();
+		names.sort();
+		names.dedup();
+		Ok(
+			names
+				.into_iter()
+				.map(|name| Tag {
+					id: name.clone(),
+					name,
+				})
+				.collect(),
+		)
+	}
+
+	/// Graph does not support `$search` on To Do tasks, so tasks are matched
+	/// with a `$filter` on their title and categories. Lists are matched here.
+	async fn search(&mut self, query: String) -> Result<Vec<SearchResult>> {
+		let words: Vec<&str> = query.split_whitespace().collect();
+		if words.is_empty() {
+			return Ok(vec![]);
+		}
+		let filter = words
+			.iter()
+			.map(|word| {
+				let word = word.replace('\'', "''");
+				format!("(contains(title,'{word}') or categories/any(c:c eq '{word}'))")
+			})
+			.collect::<Vec<String>>()
+			.join(" and ");
+
+		let lists = self.read_lists().await?;
+		let mut results: Vec<SearchResult> = lists
+			.iter()
+			.filter(|list| {
+				let text = format!("{} {}", list.name, list.description).to_lowercase();
+				words.iter().all(|word| text.contains(&word.to_lowercase()))
+			})
+			.map(|list| SearchResult::list(list.clone()))
+			.collect();
+
+		// Every list is searched at once, a request per list would otherwise
+		// make the results wait for each other.
+		let client = &self.client;
+		let filter = &filter;
+		let task_results =
+			futures::future::try_join_all(lists.iter().map(|list| async move {
+				let response = client
+					.me()
+					.todo()
+					.list(list.id.clone())
+					.tasks()
+					.list_tasks()
+					.filter(&[filter])
+					.expand(&["linkedResources"])
+					.send()
+					.await?;
+				let collection: Collection<TodoTask> = response.json().await?;
+				let mut results = vec![];
+				for todo_task in collection.value {
+					let task = Self::into_task(client, &list.id, todo_task).await;
+					results.push(SearchResult::task(list.clone(), task));
+				}
+				anyhow::Ok(results)
+			}))
+			.await?;
+		results.extend(task_results.into_iter().flatten());
+		Ok(results)
+	}
+
+	async fn read_tasks_with_tag(&mut self, tag: String) -> Result<Vec<Task>> {
+		Ok(
+			self
+				.read_all_tasks()
+				.await?
+				.into_iter()
+				.filter(|task| task.tags.contains(&tag))
+				.collect(),
+		)
+	}
+
+	async fn create_tag(&mut self, tag: Tag) -> Result<Tag> {
+		Ok(Tag {
+			id: tag.name.clone(),
+			name: tag.name,
+		})
+	}
+
+	async fn update_tag(&mut self, tag: Tag) -> Result<()> {
+		for mut task in self.read_tasks_with_tag(tag.id.clone()).await? {
+			task.tags.retain(|name| name != &tag.id);
+			task.tags.push(tag.name.clone());
+			self.update_task(task).await?;
+		}
+		Ok(())
+	}
+
+	async fn delete_tag(&mut self, id: String) -> Result<()> {
+		for mut task in self.read_tasks_with_tag(id.clone()).await? {
+			task.tags.retain(|name| name != &id);
+			self.update_task(task).await?;
+		}
+		Ok(())
+	}
+
+	async fn read_deleted_tasks(&mut self) -> Result<Vec<Task>> {
+		Ok(vec![])
+	}
+
+	async fn read_deleted_lists(&mut self) -> Result<Vec<List>> {
+		Ok(vec![])
+	}
+
+	async fn restore_task(
+		&mut self,
+		_list_id: String,
+		_task_id: String,
+	) -> Result<()> {
+		bail!("Microsoft To Do does not keep deleted tasks.")
+	}
+
+	async fn restore_list(&mut self, _id: String) -> Result<()> {
+		bail!("Microsoft To Do does not keep deleted lists.")
+	}
+
+	async fn purge_deleted(&mut self, _before: DateTime<Utc>) -> Result<()> {
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn restored_tasks_are_sent_without_read_only_fields() {
+		let task = TodoTask {
+			id: "AAMkAGI2".into(),
+			title: "Water the plants".into(),
+			created_date_time: "2024-01-01T09:00:00Z".into(),
+			last_modified_date_time: "2024-01-02T09:00:00Z".into(),
+			checklist_items: Some(vec![ChecklistItem {
+				id: "AAMkAGI3".into(),
+				display_name: "Fill the can".into(),
+				..Default::default()
+			}]),
+			..Default::default()
+		};
+
+		let request = MicrosoftService::restore_task_request("list", task);
+		assert_eq!(request.method, "POST");
+		assert_eq!(request.url, "/me/todo/lists/list/tasks");
+		let body = request.body.unwrap();
+		for field in [
+			"id",
+			"createdDateTime",
+			"lastModifiedDateTime",
+			"hasAttachments",
+		] {
+			assert!(body.get(field).is_none(), "{field} was sent");
+		}
+		assert_eq!(body["title"], "Water the plants");
+		assert_eq!(body["checklistItems"][0]["displayName"], "Fill the can");
+		assert!(body["checklistItems"][0].get("id").is_none());
+	}
 }
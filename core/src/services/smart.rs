@@ -1,11 +1,16 @@
-use std::pin::Pin;
+use std::{path::PathBuf, pin::Pin};
 
 use crate::{
-	models::{list::List, task::Task},
+	models::{
+		attachment::Attachment, dependency::Dependency, group::Group, list::List,
+		placement::Placement, search::SearchResult, tag::Tag, task::Task,
+		task_change::TaskChange,
+	},
 	task_service::TodoProvider,
 };
-use anyhow::Result;
+use anyhow::{bail, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use futures::Stream;
 use url::Url;
 
@@ -67,6 +72,14 @@ impl TodoProvider for Smart {
 		Ok(Task::default())
 	}
 
+	async fn read_task_history(
+		&mut self,
+		task_list_id: String,
+		task_id: String,
+	) -> Result<Vec<TaskChange>> {
+		Ok(vec![])
+	}
+
 	async fn create_task(&mut self, task: Task) -> Result<()> {
 		Ok(())
 	}
@@ -83,6 +96,108 @@ impl TodoProvider for Smart {
 		Ok(())
 	}
 
+	async fn create_tasks(&mut self, tasks: Vec<Task>) -> Result<()> {
+		Ok(())
+	}
+
+	async fn update_tasks(&mut self, tasks: Vec<Task>) -> Result<Vec<Task>> {
+		Ok(tasks)
+	}
+
+	async fn delete_tasks(&mut self, tasks: Vec<Task>) -> Result<()> {
+		Ok(())
+	}
+
+	async fn reorder_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		placement: Placement,
+	) -> Result<()> {
+		Ok(())
+	}
+
+	async fn create_sub_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		sub_task: Task,
+	) -> Result<Task> {
+		Ok(sub_task)
+	}
+
+	async fn update_sub_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		sub_task: Task,
+	) -> Result<()> {
+		Ok(())
+	}
+
+	async fn delete_sub_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		sub_task_id: String,
+	) -> Result<()> {
+		Ok(())
+	}
+
+	async fn add_dependency(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		blocking_task_id: String,
+	) -> Result<Dependency> {
+		Ok(Dependency::default())
+	}
+
+	async fn remove_dependency(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		blocking_task_id: String,
+	) -> Result<()> {
+		Ok(())
+	}
+
+	async fn read_attachments(
+		&mut self,
+		list_id: String,
+		task_id: String,
+	) -> Result<Vec<Attachment>> {
+		Ok(vec![])
+	}
+
+	async fn create_attachment(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		attachment: Attachment,
+		content: Vec<u8>,
+	) -> Result<Attachment> {
+		Ok(attachment)
+	}
+
+	async fn read_attachment_content(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		attachment: Attachment,
+	) -> Result<PathBuf> {
+		bail!("Smart lists don't store attachments.")
+	}
+
+	async fn delete_attachment(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		attachment_id: String,
+	) -> Result<()> {
+		Ok(())
+	}
+
 	async fn read_lists(&mut self) -> Result<Vec<List>> {
 		Ok(vec![])
 	}
@@ -108,4 +223,68 @@ impl TodoProvider for Smart {
 	async fn delete_list(&mut self, id: String) -> Result<()> {
 		Ok(())
 	}
+
+	async fn read_groups(&mut self) -> Result<Vec<Group>> {
+		Ok(vec![])
+	}
+
+	async fn create_group(&mut self, group: Group) -> Result<Group> {
+		Ok(group)
+	}
+
+	async fn update_group(&mut self, group: Group) -> Result<()> {
+		Ok(())
+	}
+
+	async fn delete_group(&mut self, id: String) -> Result<()> {
+		Ok(())
+	}
+
+	async fn read_tags(&mut self) -> Result<Vec<Tag>> {
+		Ok(vec![])
+	}
+
+	async fn search(&mut self, query: String) -> Result<Vec<SearchResult>> {
+		Ok(vec![])
+	}
+
+	async fn read_tasks_with_tag(&mut self, tag: String) -> Result<Vec<Task>> {
+		Ok(vec![])
+	}
+
+	async fn create_tag(&mut self, tag: Tag) -> Result<Tag> {
+		Ok(tag)
+	}
+
+	async fn update_tag(&mut self, tag: Tag) -> Result<()> {
+		Ok(())
+	}
+
+	async fn delete_tag(&mut self, id: String) -> Result<()> {
+		Ok(())
+	}
+
+	async fn read_deleted_tasks(&mut self) -> Result<Vec<Task>> {
+		Ok(vec![])
+	}
+
+	async fn read_deleted_lists(&mut self) -> Result<Vec<List>> {
+		Ok(vec![])
+	}
+
+	async fn restore_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+	) -> Result<()> {
+		Ok(())
+	}
+
+	async fn restore_list(&mut self, id: String) -> Result<()> {
+		Ok(())
+	}
+
+	async fn purge_deleted(&mut self, before: DateTime<Utc>) -> Result<()> {
+		Ok(())
+	}
 }
@@ -1,11 +1,16 @@
-use std::pin::Pin;
+use std::{path::PathBuf, pin::Pin};
 
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use futures::Stream;
 use url::Url;
 
-use crate::models::{list::List, task::Task};
+use crate::models::{
+	attachment::Attachment, dependency::Dependency, group::Group, list::List,
+	placement::Placement, search::SearchResult, tag::Tag, task::Task,
+	task_change::TaskChange,
+};
 
 #[async_trait]
 pub trait TodoProvider: Sync + Send {
@@ -46,19 +51,122 @@ pub trait TodoProvider: Sync + Send {
 		task_id: String,
 	) -> Result<Task>;
 
+	/// Reads the changes made to a task, newest first. Services that don't
+	/// keep a history return none.
+	async fn read_task_history(
+		&mut self,
+		task_list_id: String,
+		task_id: String,
+	) -> Result<Vec<TaskChange>>;
+
 	/// Creates a single task.
 	async fn create_task(&mut self, task: Task) -> Result<()>;
 
 	/// Updates a single task.
 	async fn update_task(&mut self, task: Task) -> Result<Task>;
 
-	/// Deltes a single task.
+	/// Deltes a single task, moving it to the trash if the service has one.
+	/// Deleting a task that is already in the trash removes it for good.
 	async fn delete_task(
 		&mut self,
 		list_id: String,
 		task_id: String,
 	) -> Result<()>;
 
+	/// Creates several tasks at once. If one of them can't be created, none
+	/// of them is.
+	async fn create_tasks(&mut self, tasks: Vec<Task>) -> Result<()>;
+
+	/// Updates several tasks at once. If one of them can't be updated, none
+	/// of them is.
+	async fn update_tasks(&mut self, tasks: Vec<Task>) -> Result<Vec<Task>>;
+
+	/// Deletes several tasks at once. If one of them can't be deleted, none
+	/// of them is.
+	async fn delete_tasks(&mut self, tasks: Vec<Task>) -> Result<()>;
+
+	/// Moves a task, or a sub-task, next to one of its siblings.
+	async fn reorder_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		placement: Placement,
+	) -> Result<()>;
+
+	/// Appends a sub-task to a task.
+	async fn create_sub_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		sub_task: Task,
+	) -> Result<Task>;
+
+	/// Updates a single sub-task of a task.
+	async fn update_sub_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		sub_task: Task,
+	) -> Result<()>;
+
+	/// Deletes a single sub-task from a task.
+	async fn delete_sub_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		sub_task_id: String,
+	) -> Result<()>;
+
+	/// Makes a task wait for another one to be completed. Fails if the other
+	/// task already waits, directly or not, for the first one.
+	async fn add_dependency(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		blocking_task_id: String,
+	) -> Result<Dependency>;
+
+	/// Stops a task from waiting for another one.
+	async fn remove_dependency(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		blocking_task_id: String,
+	) -> Result<()>;
+
+	/// Read the files attached to a task.
+	async fn read_attachments(
+		&mut self,
+		list_id: String,
+		task_id: String,
+	) -> Result<Vec<Attachment>>;
+
+	/// Attaches a file to a task.
+	async fn create_attachment(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		attachment: Attachment,
+		content: Vec<u8>,
+	) -> Result<Attachment>;
+
+	/// Returns the path of a local copy of an attached file, downloading it
+	/// first if the service keeps it elsewhere.
+	async fn read_attachment_content(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		attachment: Attachment,
+	) -> Result<PathBuf>;
+
+	/// Removes a file from a task.
+	async fn delete_attachment(
+		&mut self,
+		list_id: String,
+		task_id: String,
+		attachment_id: String,
+	) -> Result<()>;
+
 	/// Read all the lists from a service.
 	async fn read_lists(&mut self) -> Result<Vec<List>>;
 
@@ -76,6 +184,57 @@ pub trait TodoProvider: Sync + Send {
 	/// Updates a single task list.
 	async fn update_list(&mut self, list: List) -> Result<()>;
 
-	/// Deletes a single task list.
+	/// Deletes a single task list, moving it to the trash if the service has
+	/// one. Deleting a list that is already in the trash removes it for good.
 	async fn delete_list(&mut self, id: String) -> Result<()>;
+
+	/// Read the groups task lists can be filed under.
+	async fn read_groups(&mut self) -> Result<Vec<Group>>;
+
+	/// Creates a single group of task lists.
+	async fn create_group(&mut self, group: Group) -> Result<Group>;
+
+	/// Renames a single group of task lists.
+	async fn update_group(&mut self, group: Group) -> Result<()>;
+
+	/// Deletes a single group, leaving its task lists ungrouped.
+	async fn delete_group(&mut self, id: String) -> Result<()>;
+
+	/// Read all the tags from a service.
+	async fn read_tags(&mut self) -> Result<Vec<Tag>>;
+
+	/// Finds the task lists and tasks whose name, notes or tags contain every
+	/// word of a query, best matches first.
+	async fn search(&mut self, query: String) -> Result<Vec<SearchResult>>;
+
+	/// Read all the tasks tagged with a tag, regardless of parent list.
+	async fn read_tasks_with_tag(&mut self, tag: String) -> Result<Vec<Task>>;
+
+	/// Creates a single tag.
+	async fn create_tag(&mut self, tag: Tag) -> Result<Tag>;
+
+	/// Renames a single tag.
+	async fn update_tag(&mut self, tag: Tag) -> Result<()>;
+
+	/// Deletes a single tag and removes it from its tasks.
+	async fn delete_tag(&mut self, id: String) -> Result<()>;
+
+	/// Read the tasks that are in the trash.
+	async fn read_deleted_tasks(&mut self) -> Result<Vec<Task>>;
+
+	/// Read the task lists that are in the trash.
+	async fn read_deleted_lists(&mut self) -> Result<Vec<List>>;
+
+	/// Takes a single task out of the trash.
+	async fn restore_task(
+		&mut self,
+		list_id: String,
+		task_id: String,
+	) -> Result<()>;
+
+	/// Takes a single task list out of the trash.
+	async fn restore_list(&mut self, id: String) -> Result<()>;
+
+	/// Removes for good everything that was moved to the trash before a date.
+	async fn purge_deleted(&mut self, before: DateTime<Utc>) -> Result<()>;
 }
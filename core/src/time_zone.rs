@@ -0,0 +1,312 @@
+use std::sync::RwLock;
+
+use chrono::{
+	DateTime, Duration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc,
+};
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+/// The zone dates are shown and entered in, `None` follows the system.
+static USER_TIME_ZONE: RwLock<Option<Tz>> = RwLock::new(None);
+
+/// Windows zone names, as sent by Graph, and the IANA zone they stand for
+/// according to the CLDR `windowsZones` mapping.
+const WINDOWS_TIME_ZONES: &[(&str, &str)] = &[
+	("Dateline Standard Time", "Etc/GMT+12"),
+	("UTC-11", "Etc/GMT+11"),
+	("Aleutian Standard Time", "America/Adak"),
+	("Hawaiian Standard Time", "Pacific/Honolulu"),
+	("Marquesas Standard Time", "Pacific/Marquesas"),
+	("Alaskan Standard Time", "America/Anchorage"),
+	("UTC-09", "Etc/GMT+9"),
+	("Pacific Standard Time (Mexico)", "America/Tijuana"),
+	("UTC-08", "Etc/GMT+8"),
+	("Pacific Standard Time", "America/Los_Angeles"),
+	("US Mountain Standard Time", "America/Phoenix"),
+	("Mountain Standard Time (Mexico)", "America/Mazatlan"),
+	("Mountain Standard Time", "America/Denver"),
+	("Yukon Standard Time", "America/Whitehorse"),
+	("Central America Standard Time", "America/Guatemala"),
+	("Central Standard Time", "America/Chicago"),
+	("Easter Island Standard Time", "Pacific/Easter"),
+	("Central Standard Time (Mexico)", "America/Mexico_City"),
+	("Canada Central Standard Time", "America/Regina"),
+	("SA Pacific Standard Time", "America/Bogota"),
+	("Eastern Standard Time (Mexico)", "America/Cancun"),
+	("Eastern Standard Time", "America/New_York"),
+	("Haiti Standard Time", "America/Port-au-Prince"),
+	("Cuba Standard Time", "America/Havana"),
+	("US Eastern Standard Time", "America/Indiana/Indianapolis"),
+	("Turks And Caicos Standard Time", "America/Grand_Turk"),
+	("Paraguay Standard Time", "America/Asuncion"),
+	("Atlantic Standard Time", "America/Halifax"),
+	("Venezuela Standard Time", "America/Caracas"),
+	("Central Brazilian Standard Time", "America/Cuiaba"),
+	("SA Western Standard Time", "America/La_Paz"),
+	("Pacific SA Standard Time", "America/Santiago"),
+	("Newfoundland Standard Time", "America/St_Johns"),
+	("Tocantins Standard Time", "America/Araguaina"),
+	("E. South America Standard Time", "America/Sao_Paulo"),
+	("SA Eastern Standard Time", "America/Cayenne"),
+	("Argentina Standard Time", "America/Argentina/Buenos_Aires"),
+	("Greenland Standard Time", "America/Nuuk"),
+	("Montevideo Standard Time", "America/Montevideo"),
+	("Magallanes Standard Time", "America/Punta_Arenas"),
+	("Saint Pierre Standard Time", "America/Miquelon"),
+	("Bahia Standard Time", "America/Bahia"),
+	("UTC-02", "Etc/GMT+2"),
+	("Azores Standard Time", "Atlantic/Azores"),
+	("Cape Verde Standard Time", "Atlantic/Cape_Verde"),
+	("UTC", "Etc/UTC"),
+	("GMT Standard Time", "Europe/London"),
+	("Greenwich Standard Time", "Atlantic/Reykjavik"),
+	("Sao Tome Standard Time", "Africa/Sao_Tome"),
+	("Morocco Standard Time", "Africa/Casablanca"),
+	("W. Europe Standard Time", "Europe/Berlin"),
+	("Central Europe Standard Time", "Europe/Budapest"),
+	("Romance Standard Time", "Europe/Paris"),
+	("Central European Standard Time", "Europe/Warsaw"),
+	("W. Central Africa Standard Time", "Africa/Lagos"),
+	("Jordan Standard Time", "Asia/Amman"),
+	("GTB Standard Time", "Europe/Bucharest"),
+	("Middle East Standard Time", "Asia/Beirut"),
+	("Egypt Standard Time", "Africa/Cairo"),
+	("E. Europe Standard Time", "Europe/Chisinau"),
+	("Syria Standard Time", "Asia/Damascus"),
+	("West Bank Standard Time", "Asia/Hebron"),
+	("South Africa Standard Time", "Africa/Johannesburg"),
+	("FLE Standard Time", "Europe/Kiev"),
+	("Israel Standard Time", "Asia/Jerusalem"),
+	("South Sudan Standard Time", "Africa/Juba"),
+	("Kaliningrad Standard Time", "Europe/Kaliningrad"),
+	("Sudan Standard Time", "Africa/Khartoum"),
+	("Libya Standard Time", "Africa/Tripoli"),
+	("Namibia Standard Time", "Africa/Windhoek"),
+	("Arabic Standard Time", "Asia/Baghdad"),
+	("Turkey Standard Time", "Europe/Istanbul"),
+	("Arab Standard Time", "Asia/Riyadh"),
+	("Belarus Standard Time", "Europe/Minsk"),
+	("Russian Standard Time", "Europe/Moscow"),
+	("E. Africa Standard Time", "Africa/Nairobi"),
+	("Volgograd Standard Time", "Europe/Volgograd"),
+	("Iran Standard Time", "Asia/Tehran"),
+	("Arabian Standard Time", "Asia/Dubai"),
+	("Astrakhan Standard Time", "Europe/Astrakhan"),
+	("Azerbaijan Standard Time", "Asia/Baku"),
+	("Russia Time Zone 3", "Europe/Samara"),
+	("Mauritius Standard Time", "Indian/Mauritius"),
+	("Saratov Standard Time", "Europe/Saratov"),
+	("Georgian Standard Time", "Asia/Tbilisi"),
+	("Caucasus Standard Time", "Asia/Yerevan"),
+	("Afghanistan Standard Time", "Asia/Kabul"),
+	("West Asia Standard Time", "Asia/Tashkent"),
+	("Ekaterinburg Standard Time", "Asia/Yekaterinburg"),
+	("Pakistan Standard Time", "Asia/Karachi"),
+	("Qyzylorda Standard Time", "Asia/Qyzylorda"),
+	("India Standard Time", "Asia/Kolkata"),
+	("Sri Lanka Standard Time", "Asia/Colombo"),
+	("Nepal Standard Time", "Asia/Kathmandu"),
+	("Central Asia Standard Time", "Asia/Almaty"),
+	("Bangladesh Standard Time", "Asia/Dhaka"),
+	("Omsk Standard Time", "Asia/Omsk"),
+	("Myanmar Standard Time", "Asia/Yangon"),
+	("SE Asia Standard Time", "Asia/Bangkok"),
+	("Altai Standard Time", "Asia/Barnaul"),
+	("W. Mongolia Standard Time", "Asia/Hovd"),
+	("North Asia Standard Time", "Asia/Krasnoyarsk"),
+	("N. Central Asia Standard Time", "Asia/Novosibirsk"),
+	("Tomsk Standard Time", "Asia/Tomsk"),
+	("China Standard Time", "Asia/Shanghai"),
+	("North Asia East Standard Time", "Asia/Irkutsk"),
+	("Singapore Standard Time", "Asia/Singapore"),
+	("W. Australia Standard Time", "Australia/Perth"),
+	("Taipei Standard Time", "Asia/Taipei"),
+	("Ulaanbaatar Standard Time", "Asia/Ulaanbaatar"),
+	("Aus Central W. Standard Time", "Australia/Eucla"),
+	("Transbaikal Standard Time", "Asia/Chita"),
+	("Tokyo Standard Time", "Asia/Tokyo"),
+	("North Korea Standard Time", "Asia/Pyongyang"),
+	("Korea Standard Time", "Asia/Seoul"),
+	("Yakutsk Standard Time", "Asia/Yakutsk"),
+	("Cen. Australia Standard Time", "Australia/Adelaide"),
+	("AUS Central Standard Time", "Australia/Darwin"),
+	("E. Australia Standard Time", "Australia/Brisbane"),
+	("AUS Eastern Standard Time", "Australia/Sydney"),
+	("West Pacific Standard Time", "Pacific/Port_Moresby"),
+	("Tasmania Standard Time", "Australia/Hobart"),
+	("Vladivostok Standard Time", "Asia/Vladivostok"),
+	("Lord Howe Standard Time", "Australia/Lord_Howe"),
+	("Bougainville Standard Time", "Pacific/Bougainville"),
+	("Russia Time Zone 10", "Asia/Srednekolymsk"),
+	("Magadan Standard Time", "Asia/Magadan"),
+	("Norfolk Standard Time", "Pacific/Norfolk"),
+	("Sakhalin Standard Time", "Asia/Sakhalin"),
+	("Central Pacific Standard Time", "Pacific/Guadalcanal"),
+	("Russia Time Zone 11", "Asia/Kamchatka"),
+	("New Zealand Standard Time", "Pacific/Auckland"),
+	("UTC+12", "Etc/GMT-12"),
+	("Fiji Standard Time", "Pacific/Fiji"),
+	("Chatham Islands Standard Time", "Pacific/Chatham"),
+	("UTC+13", "Etc/GMT-13"),
+	("Tonga Standard Time", "Pacific/Tongatapu"),
+	("Samoa Standard Time", "Pacific/Apia"),
+	("Line Islands Standard Time", "Pacific/Kiritimati"),
+];
+
+/// Sets the zone dates are shown and entered in, `None` follows the system.
+pub fn set_user_time_zone(name: Option<&str>) {
+	let zone = name.and_then(parse_time_zone);
+	match USER_TIME_ZONE.write() {
+		Ok(mut user_time_zone) => *user_time_zone = zone,
+		Err(err) => tracing::error!("Failed to set the time zone: {err}"),
+	}
+}
+
+/// The zone dates are shown and entered in.
+pub fn user_time_zone() -> Tz {
+	USER_TIME_ZONE
+		.read()
+		.ok()
+		.and_then(|user_time_zone| *user_time_zone)
+		.unwrap_or_else(system_time_zone)
+}
+
+pub fn system_time_zone() -> Tz {
+	iana_time_zone::get_timezone()
+		.ok()
+		.and_then(|name| name.parse().ok())
+		.unwrap_or(Tz::UTC)
+}
+
+/// Every IANA zone name, for the user to pick from.
+pub fn time_zones() -> Vec<&'static str> {
+	TZ_VARIANTS.iter().map(|zone| zone.name()).collect()
+}
+
+/// Resolves a zone name, either an IANA name or a Windows one.
+pub fn parse_time_zone(name: &str) -> Option<Tz> {
+	name.parse().ok().or_else(|| {
+		WINDOWS_TIME_ZONES
+			.iter()
+			.find(|(windows_name, _)| windows_name.eq_ignore_ascii_case(name))
+			.and_then(|(_, iana_name)| iana_name.parse().ok())
+	})
+}
+
+/// The name Graph knows a zone by, the Windows name when there is one.
+pub fn windows_time_zone(zone: Tz) -> &'static str {
+	WINDOWS_TIME_ZONES
+		.iter()
+		.find(|(_, iana_name)| *iana_name == zone.name())
+		.map(|(windows_name, _)| *windows_name)
+		.unwrap_or(zone.name())
+}
+
+/// Turns a wall clock time in a zone into an instant. Times repeated when
+/// the clocks go back resolve to the first occurrence and times skipped when
+/// they go forward are moved past the gap.
+pub fn from_local(date_time: NaiveDateTime, zone: Tz) -> DateTime<Utc> {
+	match zone.from_local_datetime(&date_time) {
+		LocalResult::Single(date_time) => date_time.with_timezone(&Utc),
+		LocalResult::Ambiguous(earliest, _) => earliest.with_timezone(&Utc),
+		LocalResult::None => zone
+			.from_local_datetime(&(date_time + Duration::hours(1)))
+			.earliest()
+			.map(|date_time| date_time.with_timezone(&Utc))
+			.unwrap_or(date_time.and_utc()),
+	}
+}
+
+/// An instant as shown to the user.
+pub fn to_local(date_time: DateTime<Utc>) -> DateTime<Tz> {
+	date_time.with_timezone(&user_time_zone())
+}
+
+/// The day an instant falls on for the user.
+pub fn local_date(date_time: DateTime<Utc>) -> NaiveDate {
+	to_local(date_time).date_naive()
+}
+
+/// The instant a day starts for the user.
+pub fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+	from_local(date.and_hms_opt(0, 0, 0).unwrap(), user_time_zone())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn date_time(text: &str) -> NaiveDateTime {
+		NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").unwrap()
+	}
+
+	fn utc(text: &str) -> DateTime<Utc> {
+		date_time(text).and_utc()
+	}
+
+	#[test]
+	fn windows_and_iana_names_resolve_to_the_same_zone() {
+		assert_eq!(
+			parse_time_zone("Pacific Standard Time"),
+			Some(Tz::America__Los_Angeles)
+		);
+		assert_eq!(
+			parse_time_zone("America/Los_Angeles"),
+			Some(Tz::America__Los_Angeles)
+		);
+		assert_eq!(
+			windows_time_zone(Tz::America__Los_Angeles),
+			"Pacific Standard Time"
+		);
+	}
+
+	#[test]
+	fn zones_missing_from_the_table_keep_their_iana_name() {
+		assert_eq!(parse_time_zone("Mars Standard Time"), None);
+		assert_eq!(windows_time_zone(Tz::Europe__Madrid), "Europe/Madrid");
+		assert_eq!(parse_time_zone("Europe/Madrid"), Some(Tz::Europe__Madrid));
+	}
+
+	#[test]
+	fn from_local_moves_skipped_times_past_the_gap() {
+		let zone = parse_time_zone("Pacific Standard Time").unwrap();
+		// Clocks go from 02:00 to 03:00 on the 8th of March 2026.
+		assert_eq!(
+			from_local(date_time("2026-03-08 02:30"), zone),
+			utc("2026-03-08 10:30")
+		);
+		assert_eq!(
+			from_local(date_time("2026-03-08 03:30"), zone),
+			utc("2026-03-08 10:30")
+		);
+	}
+
+	#[test]
+	fn from_local_picks_the_first_of_repeated_times() {
+		let zone = parse_time_zone("America/Los_Angeles").unwrap();
+		// Clocks go from 02:00 back to 01:00 on the 1st of November 2026.
+		assert_eq!(
+			from_local(date_time("2026-11-01 01:30"), zone),
+			utc("2026-11-01 08:30")
+		);
+	}
+
+	#[test]
+	fn from_local_round_trips_around_transitions() {
+		let zone = parse_time_zone("Pacific Standard Time").unwrap();
+		for day in ["2026-03-08 00:00", "2026-11-01 00:00"] {
+			let start = utc(day);
+			for quarter in 0..(4 * 24) {
+				let instant = start + Duration::minutes(15 * quarter);
+				let local = instant.with_timezone(&zone);
+				// The second of two repeated times can't be told apart by its
+				// wall clock, the first one always can.
+				if zone.from_local_datetime(&local.naive_local()).earliest()
+					!= Some(local)
+				{
+					continue;
+				}
+				assert_eq!(from_local(local.naive_local(), zone), instant);
+			}
+		}
+	}
+}
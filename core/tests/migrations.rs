@@ -0,0 +1,263 @@
+//! Runs the embedded migrations against a new database and against one that
+//! already holds data from before the schema constraints were added.
+
+use std::{
+	path::{Path, PathBuf},
+	sync::Mutex,
+};
+
+use core_done::{
+	models::{
+		priority::Priority,
+		recurrence::{Day, Frequency},
+		status::Status,
+	},
+	service::Service,
+	services::local::database::{Database, DatabaseConnection, MIGRATIONS},
+};
+use diesel::{
+	connection::SimpleConnection,
+	migration::MigrationSource,
+	sql_query,
+	sql_types::{BigInt, Text},
+	sqlite::Sqlite,
+	QueryableByName, RunQueryDsl,
+};
+use diesel_migrations::MigrationHarness;
+
+/// The migration that turns the sub-tasks kept as JSON into task rows.
+const SUB_TASK_ROWS_MIGRATION: &str = "2026-10-18-110000_sub_task_rows";
+
+/// The migration that rebuilds the tables with foreign keys and checks.
+const CONSTRAINTS_MIGRATION: &str = "2026-10-18-220000_schema_constraints";
+
+/// The database location is shared by the whole process.
+static DATABASE: Mutex<()> = Mutex::new(());
+
+#[derive(QueryableByName)]
+struct Count {
+	#[diesel(sql_type = BigInt)]
+	count: i64,
+}
+
+/// Each test gets its own folder, which also holds the backup taken before
+/// migrating.
+fn database_path(name: &str) -> PathBuf {
+	std::env::temp_dir()
+		.join(format!("done-migrations-{name}-{}", std::process::id()))
+		.join("done.db")
+}
+
+fn remove_database(path: &Path) {
+	Database::close();
+	if let Some(dir) = path.parent() {
+		let _ = std::fs::remove_dir_all(dir);
+		std::fs::create_dir_all(dir).unwrap();
+	}
+}
+
+/// Brings the database to the schema it had before `name` was added.
+fn run_migrations_before(connection: &mut DatabaseConnection, name: &str) {
+	// Creates the table that keeps track of the applied migrations.
+	connection.applied_migrations().unwrap();
+	let mut migrations =
+		MigrationSource::<Sqlite>::migrations(&MIGRATIONS).unwrap();
+	migrations.sort_by_key(|migration| migration.name().to_string());
+	for migration in migrations
+		.iter()
+		.filter(|migration| migration.name().to_string().as_str() < name)
+	{
+		connection.run_migration(migration.as_ref()).unwrap();
+	}
+}
+
+fn count(query: &str) -> i64 {
+	sql_query(query)
+		.get_result::<Count>(&mut Database::establish_connection().unwrap())
+		.unwrap()
+		.count
+}
+
+#[test]
+fn migrations_run_on_an_empty_database() {
+	let _lock = DATABASE.lock().unwrap_or_else(|err| err.into_inner());
+	let path = database_path("empty");
+	remove_database(&path);
+	Database::set_database_path(Some(path.clone()));
+
+	Database::ensure_migrations_up_to_date().unwrap();
+
+	let mut connection = Database::establish_connection().unwrap();
+	assert!(!connection.has_pending_migration(MIGRATIONS).unwrap());
+	connection.revert_all_migrations(MIGRATIONS).unwrap();
+	connection.run_pending_migrations(MIGRATIONS).unwrap();
+	drop(connection);
+
+	assert_eq!(count("SELECT count(*) AS count FROM tasks"), 0);
+	assert_eq!(count("SELECT count(*) AS count FROM lists"), 0);
+	Database::close();
+	let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn migrations_run_on_a_populated_database() {
+	let _lock = DATABASE.lock().unwrap_or_else(|err| err.into_inner());
+	let path = database_path("populated");
+	remove_database(&path);
+	Database::set_database_path(Some(path.clone()));
+
+	let mut connection = Database::establish_connection().unwrap();
+	run_migrations_before(&mut connection, CONSTRAINTS_MIGRATION);
+
+	// Rows the old schema let through: a task in a missing list along with
+	// its sub-task, a sub-task whose parent is gone and values outside the
+	// range of their enums.
+	connection
+		.batch_execute(
+			"PRAGMA foreign_keys = OFF;
+			INSERT INTO lists (id_list, name, description) VALUES ('list', 'List', NULL);
+			INSERT INTO tags (id_tag, name) VALUES ('tag', 'tag');
+			INSERT INTO tasks (id_task, parent, title, status, priority)
+			VALUES ('task', 'list', 'Task', 1, 2),
+			       ('invalid', 'list', 'Invalid', 9, 7),
+			       ('orphan', 'missing', 'Orphan', 0, 0);
+			INSERT INTO tasks (id_task, parent, title, parent_task)
+			VALUES ('sub-task', 'list', 'Sub-task', 'task'),
+			       ('orphan-sub-task', 'missing', 'Orphan sub-task', 'orphan'),
+			       ('lost', 'list', 'Lost', 'missing');
+			INSERT INTO task_tags (id_task, id_tag)
+			VALUES ('task', 'tag'), ('orphan', 'tag');
+			PRAGMA foreign_keys = ON;",
+		)
+		.unwrap();
+	drop(connection);
+
+	Database::ensure_migrations_up_to_date().unwrap();
+
+	// Nothing is lost, orphans are moved to a list of recovered tasks and
+	// sub-tasks without a parent become top level tasks.
+	assert_eq!(count("SELECT count(*) AS count FROM tasks"), 6);
+	assert_eq!(count("SELECT count(*) AS count FROM task_tags"), 2);
+	assert_eq!(
+		count(
+			"SELECT count(*) AS count FROM tasks
+			WHERE parent = 'recovered-tasks'
+				AND id_task IN ('orphan', 'orphan-sub-task')"
+		),
+		2
+	);
+	assert_eq!(
+		count(
+			"SELECT count(*) AS count FROM tasks
+			WHERE id_task = 'orphan-sub-task' AND parent_task = 'orphan'"
+		),
+		1
+	);
+	assert_eq!(
+		count(
+			"SELECT count(*) AS count FROM tasks
+			WHERE id_task = 'lost' AND parent = 'list' AND parent_task IS NULL"
+		),
+		1
+	);
+	assert_eq!(
+		count(
+			"SELECT count(*) AS count FROM list_search
+			WHERE list_search MATCH 'recovered'"
+		),
+		1
+	);
+	assert_eq!(
+		count(
+			"SELECT count(*) AS count FROM tasks
+			WHERE id_task = 'invalid' AND status = 0 AND priority = 0"
+		),
+		1
+	);
+	assert_eq!(
+		count(
+			"SELECT count(*) AS count FROM task_search WHERE task_search MATCH 'tag'"
+		),
+		2
+	);
+	assert_eq!(
+		count("SELECT count(*) AS count FROM pragma_foreign_key_check"),
+		0
+	);
+
+	let mut connection = Database::establish_connection().unwrap();
+	assert!(connection
+		.batch_execute(
+			"INSERT INTO tasks (id_task, parent, title) VALUES ('new', 'missing', 'New')"
+		)
+		.is_err());
+	assert!(connection
+		.batch_execute("UPDATE tasks SET status = 5 WHERE id_task = 'task'")
+		.is_err());
+	connection
+		.batch_execute("DELETE FROM lists WHERE id_list = 'list'")
+		.unwrap();
+	drop(connection);
+
+	assert_eq!(count("SELECT count(*) AS count FROM tasks"), 2);
+	assert_eq!(count("SELECT count(*) AS count FROM task_tags"), 1);
+	Database::close();
+	let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[tokio::test]
+async fn sub_tasks_kept_as_json_become_task_rows() {
+	let _lock = DATABASE.lock().unwrap_or_else(|err| err.into_inner());
+	let path = database_path("sub-tasks");
+	remove_database(&path);
+	Database::set_database_path(Some(path.clone()));
+
+	let mut connection = Database::establish_connection().unwrap();
+	run_migrations_before(&mut connection, SUB_TASK_ROWS_MIGRATION);
+	// The sub-tasks as the app wrote them before they had rows of their own.
+	let sub_tasks = r#"[{"id":"sub-task","parent":"list","title":"Buy soil","favorite":false,"today":false,"status":"Completed","priority":"High","sub_tasks":[{"id":"nested","parent":"list","title":"Pick a bag","favorite":false,"today":false,"status":"NotStarted","priority":"Low","sub_tasks":[],"tags":["errands"],"notes":null,"completion_date":null,"deletion_date":null,"due_date":null,"reminder_date":null,"recurrence":{"monday":false,"tuesday":false,"wednesday":false,"thursday":false,"friday":false,"saturday":false,"sunday":false},"created_date_time":"2023-05-20T10:00:00.123456789Z","last_modified_date_time":"2023-05-20T10:00:00.123456789Z"}],"tags":["garden","errands"],"notes":"Peat free","completion_date":"2023-05-21T08:30:00Z","deletion_date":null,"due_date":null,"reminder_date":null,"recurrence":{"monday":true,"tuesday":false,"wednesday":true,"thursday":false,"friday":false,"saturday":false,"sunday":false},"created_date_time":"2023-05-20T10:00:00Z","last_modified_date_time":"2023-05-21T08:30:00Z"}]"#;
+	sql_query(
+		"INSERT INTO lists (id_list, name, description) VALUES ('list', 'List', NULL)",
+	)
+	.execute(&mut connection)
+	.unwrap();
+	sql_query(
+		"INSERT INTO tasks (id_task, parent, title, sub_tasks)
+		VALUES ('task', 'list', 'Repot the plants', ?)",
+	)
+	.bind::<Text, _>(sub_tasks)
+	.execute(&mut connection)
+	.unwrap();
+	drop(connection);
+
+	Database::ensure_migrations_up_to_date().unwrap();
+
+	let task = Service::Computer
+		.get_service()
+		.read_task("list".into(), "task".into())
+		.await
+		.unwrap();
+	assert_eq!(task.sub_tasks.len(), 1);
+	let sub_task = &task.sub_tasks[0];
+	assert_eq!(sub_task.id, "sub-task");
+	assert_eq!(sub_task.title, "Buy soil");
+	assert_eq!(sub_task.notes.as_deref(), Some("Peat free"));
+	assert_eq!(sub_task.status, Status::Completed);
+	assert_eq!(sub_task.priority, Priority::High);
+	let mut tags = sub_task.tags.clone();
+	tags.sort();
+	assert_eq!(tags, ["errands", "garden"]);
+	let recurrence = sub_task.recurrence.as_ref().unwrap();
+	assert_eq!(recurrence.frequency, Frequency::Weekly);
+	assert_eq!(recurrence.days_of_week, [Day::Monday, Day::Wednesday]);
+
+	assert_eq!(sub_task.sub_tasks.len(), 1);
+	let nested = &sub_task.sub_tasks[0];
+	assert_eq!(nested.id, "nested");
+	assert_eq!(nested.tags, ["errands"]);
+	assert_eq!(nested.recurrence, None);
+
+	assert_eq!(count("SELECT count(*) AS count FROM tags"), 2);
+	Database::close();
+	let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
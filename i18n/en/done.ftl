@@ -18,6 +18,46 @@ rename = Rename
 
 # New list dialog
 list-name = List name
+list-settings = Settings
+list-description = Description
+list-color = Accent color
+no-color = No color
+clear-color = Remove the accent color
+sort-order = Sort by
+sort-manual = Manual order
+sort-title = Title
+sort-due-date = Due date
+sort-priority = Priority
+sort-creation-date = Creation date
+group-by = Group by
+group-none = No grouping
+group-status = Status
+group-priority = Priority
+group-due-date = Due date
+show-completed = Show completed tasks
+
+# List sections
+priority-section = { $priority } priority
+overdue = Overdue
+upcoming = Upcoming
+no-due-date = No due date
+reorder-sorted-list = Switch the list to manual order to drag tasks around
+
+# Shared lists
+shared-list = Shared with others
+shared-with-you = Shared with you
+shared-list-rename = Only the owner of this list can rename it.
+
+# List groups
+move-to-group = Move to group
+move-to-group-desc = Pick a group or name a new one.
+new-group-name = New group name
+move = Move
+no-group = No group
+collapse-group = Collapse group
+expand-group = Expand group
+rename-group = Click to rename the group
+ungroup = Ungroup lists
 
 # Welcome
 welcome-title = To-do lists reimagined
@@ -26,6 +66,9 @@ welcome-body = Done is a user-friendly app that allows you to effortlessly conso
 
 # Main menu
 menu = Menu
+list-actions = List actions
+complete-all = Complete all tasks
+delete-completed = Delete completed tasks
 preferences = Preferences
 keyboard-shortcuts = Keyboard shortcuts
 about-done = About Done
@@ -41,9 +84,17 @@ starred = Starred
 starred-desc = Starred tasks
 next-7-days = Next 7 Days
 next-7-days-desc = Tasks due the next 7 days
+actionable = Ready
+actionable-desc = Tasks that can be worked on right now
 add-new-task-list = Add new task list
 completed-list = Completed
 completed-list-desc = Tasks that have been completed
+tag-desc = Tasks with this tag across all lists
+trash = Trash
+trash-desc = Deleted tasks and lists
+deleted-lists = Deleted lists
+restore = Restore
+delete-forever = Delete forever
 
 # Middle pane
 empty-middle-tittle = This is looking quite empty.
@@ -65,6 +116,38 @@ color-scheme-description = Set the color scheme of the app
 color-scheme-light = Light
 color-scheme-dark = Dark
 color-scheme-default = System
+trash-retention = Empty trash after
+trash-retention-description = Deleted items older than this are removed for good
+backups = Backups
+backup-interval = Back up automatically
+backup-interval-description = A snapshot of your tasks is kept on this device
+daily = Daily
+weekly = Weekly
+back-up-now = Back up now
+restore-backup = Restore a backup
+restore-backup-description = The current data is backed up before it is replaced
+backup-restored = Backup restored
+security = Security
+encrypt-database = Encrypt tasks
+encrypt-database-description = The key is kept in the system keyring, tasks can't be read without it
+database-encrypted = Your tasks are now encrypted
+database-decrypted = Your tasks are no longer encrypted
+profiles = Profiles
+profiles-description = Each profile has its own tasks and services, start the app with --profile to open one
+new-profile = New profile
+profile-in-use = In use
+remove-profile = Remove profile
+profile-option = Open the profile with this name, for this run only
+database-location = Database location
+default-location = App data folder
+choose-location = Choose a folder
+one-week = One week
+one-month = One month
+three-months = Three months
+date-time = Date and time
+time-zone = Time zone
+time-zone-description = Dates and reminders are shown in this zone
+system-time-zone = System
 services = Services
 install = Install
 remove = Remove
@@ -73,6 +156,9 @@ save = Save
 cancel = Cancel
 back = Back
 search = Search
+search-placeholder = Search tasks, notes, tags and lists
+search-instructions = Find tasks in every service
+no-results = No results
 
 # Services
 msft-todo = Microsoft To Do
@@ -82,7 +168,16 @@ msft-todo-description = To Do gives you focus, from work to play
 details = Details
 title = Title
 favorite-task = Favorite task
+links = Links
+add-link = Paste a link
+remove-link = Remove link
 notes = Notes
+no-notes = No notes yet
+edit-notes = Edit notes
+save-notes = Save notes
+markdown-supported = Markdown is supported
+tags = Tags
+tags-desc = Separate tags with commas
 add-tags = Add tags...
 remove-tag = Remove tag
 remove-task = Remove task
@@ -94,6 +189,12 @@ today-tooltip = Add to Today
 completed = Completed
 completed-desc = Sets the completion status
 completed-tooltip = Complete task
+status = Status
+status-desc = Set the progress of this task
+not-started = Not started
+in-progress = In progress
+waiting = Waiting on others
+deferred = Deferred
 importance = Importance
 importance-desc = Set the importance for this task
 low = Low
@@ -114,12 +215,39 @@ tomorrow = Tomorrow
 set-day-tomorrow = Set date to tomorrow
 none = None
 unset = Unset date
+start-date = Start date
+set-start-date = Hide this task from smart lists until this date
 due-date = Due date
 set-due-date = Set the due date for this task
 no-date-set = No date set
 sub-tasks = Sub tasks
 add-sub-task = Add sub-task
 remove-sub-task = Remove sub-task
+blocked-by = Blocked by
+blocked-tooltip = Waiting for other tasks
+add-dependency = Wait for a task
+choose-dependency = Choose a task
+remove-dependency = Stop waiting for this task
+no-dependency-candidates = There are no other open tasks in this list
+history = History
+no-history = No changes yet
+moved-to-list = Moved to another list
+moved-to-trash = Moved to the trash
+restored-from-trash = Restored from the trash
+yes = Yes
+no = No
+nothing-to-undo = Nothing to undo
+nothing-to-redo = Nothing to redo
+attachments = Attachments
+add-attachment = Add attachment
+open-attachment = Open attachment
+remove-attachment = Remove attachment
+estimate = Estimated time
+estimate-desc = How many minutes this task should take
+time-tracked = Time tracked
+start-timer = Start timer
+stop-timer = Stop timer
+time-summary = Planned { $planned } · Spent { $spent }
 monday = Monday
 tuesday = Tuesday
 wednesday = Wednesday
@@ -134,6 +262,40 @@ thu = Thu
 fri = Fri
 sat = Sat
 sun = Sun
+repeat = Repeat
+never = Never
+daily = Daily
+weekly = Weekly
+monthly = Monthly, on a day
+monthly-relative = Monthly, on a weekday
+yearly = Yearly, on a date
+yearly-relative = Yearly, on a weekday
+repeat-every = Repeat every
+repeat-every-desc = Days, weeks, months or years between occurrences
+day-of-month = Day of the month
+week-of-month = Week of the month
+first = First
+second = Second
+third = Third
+fourth = Fourth
+last = Last
+month = Month
+january = January
+february = February
+march = March
+april = April
+may = May
+june = June
+july = July
+august = August
+september = September
+october = October
+november = November
+december = December
+ends = Ends
+ends-on-date = On a date
+ends-after = After a number of occurrences
+occurrences = Occurrences
 
 # Translator credits
 translator-credits = Eduardo Flores <edfloreshz@gmail.com> \n Sergio Varela <sergiovg01@outlook.com> \n Jürgen Benvenuti <gastornis@posteo.org> \n Sabri Ünal<libreajans@gmail.com> \n Isabella Breder \n albanobattistella
\ No newline at end of file
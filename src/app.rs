@@ -4,15 +4,18 @@ pub mod models;
 use std::str::FromStr;
 
 use adw::glib::Propagation;
-use core_done::service::Service;
+use core_done::{service::Service, services::local::database::Database};
 use relm4::{
 	actions::{ActionGroupName, RelmAction, RelmActionGroup},
 	adw,
-	adw::prelude::{AdwApplicationWindowExt, NavigationPageExt},
+	adw::prelude::{
+		AdwApplicationWindowExt, NavigationPageExt, PreferencesGroupExt,
+	},
 	component::{
 		AsyncComponent, AsyncComponentController, AsyncComponentParts,
 		AsyncController,
 	},
+	factory::FactoryVecDeque,
 	gtk::{
 		self,
 		prelude::{
@@ -21,9 +24,11 @@ use relm4::{
 		traits::{ApplicationWindowExt, GtkWindowExt, OrientableExt, WidgetExt},
 	},
 	loading_widgets::LoadingWidgets,
-	main_adw_application, new_action_group, new_stateless_action, view,
-	AsyncComponentSender, ComponentBuilder, ComponentController, Controller,
-	RelmWidgetExt,
+	main_adw_application, new_action_group, new_stateful_action,
+	new_stateless_action,
+	prelude::DynamicIndex,
+	view, AsyncComponentSender, ComponentBuilder, ComponentController,
+	Controller, RelmWidgetExt,
 };
 use relm4_icons::icon_name;
 
@@ -33,7 +38,8 @@ use crate::{
 			preferences::PreferencesComponentOutput,
 			task_list_sidebar::TaskListSidebarOutput,
 		},
-		config::{info::PROFILE, setup},
+		config::{info::PROFILE, profiles, setup},
+		factories::backup::{BackupModel, BackupOutput},
 	},
 	fl,
 };
@@ -41,8 +47,8 @@ use crate::{
 use self::{
 	components::{
 		about_dialog::AboutDialog,
-		content::{ContentInput, ContentModel},
-		preferences::PreferencesComponentModel,
+		content::{ContentInput, ContentModel, ContentOutput},
+		preferences::{PreferencesComponentInput, PreferencesComponentModel},
 		task_list_sidebar::{TaskListSidebarInput, TaskListSidebarModel},
 	},
 	models::sidebar_list::SidebarList,
@@ -55,12 +61,17 @@ new_stateless_action!(pub(super) ShortcutsAction, WindowActionGroup, "show-help-
 new_stateless_action!(AboutAction, WindowActionGroup, "about");
 new_stateless_action!(PreferencesAction, WindowActionGroup, "preferences");
 new_stateless_action!(QuitAction, WindowActionGroup, "quit");
+new_stateful_action!(pub(super) ProfileAction, WindowActionGroup, "profile", String, String);
+
+/// Seconds between checks for whether an automatic backup is due.
+const BACKUP_CHECK_INTERVAL: u32 = 60 * 60;
 
 pub struct Done {
 	task_list_sidebar_controller: AsyncController<TaskListSidebarModel>,
 	content_controller: AsyncController<ContentModel>,
 	about_dialog: Controller<AboutDialog>,
 	preferences: AsyncController<PreferencesComponentModel>,
+	backups: FactoryVecDeque<BackupModel>,
 	startup_failed: bool,
 }
 
@@ -68,8 +79,14 @@ pub struct Done {
 pub enum AppInput {
 	ServiceDisabled(Service),
 	ListSelected(SidebarList, Service),
+	ListUpdated(SidebarList),
 	ReloadSidebar(Service),
+	ReloadTaskLists,
 	CleanContent,
+	DatabaseRestored,
+	RestoreBackup(DynamicIndex),
+	SwitchProfile(String),
+	ProfilesChanged,
 	Refresh,
 	Quit,
 }
@@ -171,6 +188,14 @@ impl AsyncComponent for Done {
 							set_wrap_mode: gtk::pango::WrapMode::Word,
 							set_justify: gtk::Justification::Center,
 						},
+						#[local_ref]
+						backup_list -> adw::PreferencesGroup {
+							set_margin_top: 20,
+							set_title: fl!("restore-backup"),
+							set_description: Some(fl!("restore-backup-description")),
+							#[watch]
+							set_visible: !model.backups.is_empty(),
+						},
 					}
 				}
 			} else {
@@ -263,8 +288,16 @@ impl AsyncComponent for Done {
 						AppInput::ListSelected(list, service)
 					},
 					TaskListSidebarOutput::CleanContent => AppInput::CleanContent,
+					TaskListSidebarOutput::ListUpdated(list) => {
+						AppInput::ListUpdated(list)
+					},
 				}),
-			content_controller: ContentModel::builder().launch(None).detach(),
+			content_controller: ContentModel::builder().launch(None).forward(
+				sender.input_sender(),
+				|message| match message {
+					ContentOutput::ReloadTaskLists => AppInput::ReloadTaskLists,
+				},
+			),
 			about_dialog,
 			preferences: PreferencesComponentModel::builder().launch(()).forward(
 				sender.input_sender(),
@@ -272,16 +305,58 @@ impl AsyncComponent for Done {
 					PreferencesComponentOutput::ServiceDisabled(service) => {
 						AppInput::ReloadSidebar(service)
 					},
+					PreferencesComponentOutput::DatabaseRestored => {
+						AppInput::DatabaseRestored
+					},
+					PreferencesComponentOutput::ProfilesChanged => {
+						AppInput::ProfilesChanged
+					},
 				},
 			),
+			backups: FactoryVecDeque::builder()
+				.launch(adw::PreferencesGroup::default())
+				.forward(sender.input_sender(), |output| match output {
+					BackupOutput::Restore(index) => AppInput::RestoreBackup(index),
+				}),
 			startup_failed: false,
 		};
 
 		match setup::init_services() {
-			Ok(_) => (),
-			Err(_) => model.startup_failed = true,
+			Ok(_) => {
+				if let Err(err) = setup::purge_trash().await {
+					tracing::error!("Failed to empty the trash: {err}");
+				}
+				if let Err(err) = setup::backup_database() {
+					tracing::error!("Failed to back up the database: {err}");
+				}
+				model
+					.preferences
+					.emit(PreferencesComponentInput::LoadBackups);
+				let preferences = model.preferences.sender().clone();
+				gtk::glib::timeout_add_seconds_local(
+					BACKUP_CHECK_INTERVAL,
+					move || {
+						if let Err(err) = setup::backup_database() {
+							tracing::error!("Failed to back up the database: {err}");
+						}
+						preferences
+							.send(PreferencesComponentInput::LoadBackups)
+							.unwrap_or_default();
+						gtk::glib::ControlFlow::Continue
+					},
+				);
+			},
+			Err(_) => {
+				model.startup_failed = true;
+				let mut guard = model.backups.guard();
+				for backup in Database::backups().unwrap_or_default() {
+					guard.push_back(backup);
+				}
+			},
 		};
 
+		let backup_list = model.backups.widget().clone();
+
 		let widgets = view_output!();
 
 		let mut actions = RelmActionGroup::<WindowActionGroup>::new();
@@ -317,10 +392,24 @@ impl AsyncComponent for Done {
 			})
 		};
 
+		let profile_action = {
+			let sender = sender.clone();
+			RelmAction::<ProfileAction>::new_stateful_with_target_value(
+				&profiles::current().name,
+				move |_, state, name| {
+					if *state != name {
+						*state = name.clone();
+						sender.input(AppInput::SwitchProfile(name));
+					}
+				},
+			)
+		};
+
 		actions.add_action(shortcuts_action);
 		actions.add_action(about_action);
 		actions.add_action(preferences_action);
 		actions.add_action(quit_action);
+		actions.add_action(profile_action);
 
 		root.insert_action_group(
 			WindowActionGroup::NAME,
@@ -338,6 +427,52 @@ impl AsyncComponent for Done {
 	) {
 		match message {
 			AppInput::Quit => main_adw_application().quit(),
+			AppInput::RestoreBackup(index) => {
+				if let Some(model) = self.backups.get(index.current_index()) {
+					match Database::restore_backup(&model.backup) {
+						Ok(_) => main_adw_application().quit(),
+						Err(err) => tracing::error!("Failed to restore the backup: {err}"),
+					}
+				}
+			},
+			AppInput::DatabaseRestored => {
+				self
+					.content_controller
+					.sender()
+					.send(ContentInput::DatabaseChanged)
+					.unwrap_or_default();
+				self
+					.task_list_sidebar_controller
+					.sender()
+					.send(TaskListSidebarInput::LoadTaskLists)
+					.unwrap_or_default();
+			},
+			AppInput::SwitchProfile(name) => match profiles::switch(&name) {
+				Ok(_) => {
+					self
+						.content_controller
+						.sender()
+						.send(ContentInput::DatabaseChanged)
+						.unwrap_or_default();
+					self
+						.task_list_sidebar_controller
+						.sender()
+						.send(TaskListSidebarInput::ProfileSwitched)
+						.unwrap_or_default();
+					self
+						.preferences
+						.emit(PreferencesComponentInput::ProfileSwitched);
+					if let Err(err) = setup::backup_database() {
+						tracing::error!("Failed to back up the database: {err}");
+					}
+				},
+				Err(err) => tracing::error!("Failed to switch profiles: {err}"),
+			},
+			AppInput::ProfilesChanged => self
+				.task_list_sidebar_controller
+				.sender()
+				.send(TaskListSidebarInput::LoadProfiles)
+				.unwrap_or_default(),
 			AppInput::Refresh => {
 				match setup::refresh() {
 					Ok(_) => main_adw_application().quit(),
@@ -351,6 +486,13 @@ impl AsyncComponent for Done {
 					.send(ContentInput::SelectList(list, service))
 					.unwrap_or_default();
 			},
+			AppInput::ListUpdated(list) => {
+				self
+					.content_controller
+					.sender()
+					.send(ContentInput::ListUpdated(list))
+					.unwrap_or_default();
+			},
 			AppInput::CleanContent => self
 				.content_controller
 				.sender()
@@ -368,6 +510,11 @@ impl AsyncComponent for Done {
 				.sender()
 				.send(TaskListSidebarInput::ReloadSidebar(service))
 				.unwrap_or_default(),
+			AppInput::ReloadTaskLists => self
+				.task_list_sidebar_controller
+				.sender()
+				.send(TaskListSidebarInput::LoadTaskLists)
+				.unwrap_or_default(),
 		}
 	}
 }
@@ -1,41 +1,94 @@
 use crate::app::components::task_input::TaskInputOutput;
-use crate::app::factories::task::{TaskInit, TaskModel, TaskOutput};
+use crate::app::factories::task::{
+	format_duration, TaskInit, TaskInput, TaskModel, TaskOutput,
+};
+use crate::app::factories::trash_list::{TrashListModel, TrashListOutput};
 use crate::app::models::sidebar_list::SidebarList;
 use crate::fl;
 
-use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use core_done::models::attachment::Attachment;
+use core_done::models::dependency::Dependency;
+use core_done::models::list_settings::{ListSettings, TaskGrouping};
+use core_done::models::placement::Placement;
+use core_done::models::priority::Priority;
 use core_done::models::status::Status;
 use core_done::models::task::Task;
 use core_done::service::Service;
+use core_done::time_zone;
 use futures::StreamExt;
+use relm4::actions::{
+	AccelsPlus, ActionGroupName, RelmAction, RelmActionGroup,
+};
 use relm4::component::{
-	AsyncComponent, AsyncComponentParts, AsyncComponentSender,
+	AsyncComponent, AsyncComponentController, AsyncComponentParts,
+	AsyncComponentSender, AsyncController,
 };
-use relm4::factory::AsyncFactoryVecDeque;
+use relm4::factory::{AsyncFactoryVecDeque, FactoryVecDeque};
 use relm4::gtk::traits::ButtonExt;
 use relm4::prelude::DynamicIndex;
 use relm4::{
 	adw,
 	adw::prelude::NavigationPageExt,
 	gtk,
-	gtk::prelude::{BoxExt, OrientableExt, WidgetExt},
+	gtk::gio,
+	gtk::prelude::{
+		BoxExt, GtkApplicationExt, ListBoxRowExt, OrientableExt, ToggleButtonExt,
+		WidgetExt,
+	},
 };
 use relm4::{
 	tokio, Component, ComponentController, Controller, JoinHandle, RelmWidgetExt,
 };
 use relm4_icons::icon_name;
 
+use super::search::{SearchInput, SearchModel, SearchOutput};
 use super::task_input::{TaskInputInput, TaskInputModel};
 use super::welcome::WelcomeComponent;
 
 pub struct ContentModel {
 	task_factory: AsyncFactoryVecDeque<TaskModel>,
+	trash_list_factory: FactoryVecDeque<TrashListModel>,
 	task_entry: Controller<TaskInputModel>,
 	welcome: Controller<WelcomeComponent>,
+	search: AsyncController<SearchModel>,
+	searching: bool,
 	state: ContentState,
 	service: Service,
 	parent_list: Option<SidebarList>,
+	time_summary: Option<String>,
 	handle: Option<JoinHandle<()>>,
+	/// The edits that can be undone, most recent last, along with the service
+	/// they were made in.
+	undo_stack: Vec<(Service, Edit)>,
+	redo_stack: Vec<(Service, Edit)>,
+}
+
+/// How many edits are kept to undo.
+const UNDO_LIMIT: usize = 50;
+
+/// A change to the tasks that can be undone and redone.
+#[derive(Debug, Clone)]
+enum Edit {
+	/// Tasks as they were before and after being changed, along with the
+	/// next occurrences created by completing them.
+	Update {
+		before: Vec<Task>,
+		after: Vec<Task>,
+		spawned: Vec<Task>,
+	},
+	/// Tasks moved to the trash.
+	Delete(Vec<Task>),
+	/// A task moved to another position in its list.
+	Reorder {
+		list_id: String,
+		task_id: String,
+		from: Placement,
+		to: Placement,
+	},
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -50,17 +103,56 @@ pub enum ContentState {
 pub enum ContentInput {
 	AddTask(Task),
 	RemoveTask(DynamicIndex),
-	UpdateTask(Task),
+	RestoreTask(DynamicIndex),
+	ReorderTask(String, DynamicIndex),
+	RestoreList(DynamicIndex),
+	PurgeList(DynamicIndex),
+	UpdateTask(Task, Task),
+	CreateSubTask(Task, Task),
+	UpdateSubTask(Task, Task),
+	RemoveSubTask(Task, String),
+	CreateAttachment(DynamicIndex, Task, Attachment, Vec<u8>),
+	OpenAttachment(Task, Attachment),
+	RemoveAttachment(Task, String),
+	ChooseDependency(DynamicIndex, Task),
+	AddDependency(DynamicIndex, Task, String),
+	RemoveDependency(Task, String),
+	LoadHistory(DynamicIndex, Task),
 	LoadTask(Task),
 	SelectList(SidebarList, Service),
+	ListUpdated(SidebarList),
 	ServiceDisabled(Service),
+	ShowSearch(bool),
+	CompleteAll,
+	DeleteCompleted,
+	Undo,
+	Redo,
 	LoadTasks(SidebarList, Service),
 	SetState(ContentState),
 	Clean,
+	/// The tasks now come from another database, the edits made to the
+	/// previous one can't be undone anymore.
+	DatabaseChanged,
 }
 
 #[derive(Debug)]
-pub enum ContentOutput {}
+pub enum ContentOutput {
+	ReloadTaskLists,
+}
+
+relm4::new_action_group!(pub(super) ContentActionGroup, "content");
+relm4::new_stateless_action!(
+	CompleteAllAction,
+	ContentActionGroup,
+	"complete-all"
+);
+relm4::new_stateless_action!(
+	DeleteCompletedAction,
+	ContentActionGroup,
+	"delete-completed"
+);
+relm4::new_stateless_action!(UndoAction, ContentActionGroup, "undo");
+relm4::new_stateless_action!(RedoAction, ContentActionGroup, "redo");
 
 #[relm4::component(pub async)]
 impl AsyncComponent for ContentModel {
@@ -69,6 +161,15 @@ impl AsyncComponent for ContentModel {
 	type Output = ContentOutput;
 	type Init = Option<Service>;
 
+	menu! {
+		list_menu: {
+			section! {
+				complete_all => CompleteAllAction,
+				delete_completed => DeleteCompletedAction,
+			}
+		}
+	}
+
 	view! {
 		#[root]
 		adw::ToolbarView {
@@ -87,10 +188,24 @@ impl AsyncComponent for ContentModel {
 					set_icon_name: icon_name::LEFT,
 					set_visible: false,
 				},
-				pack_start = &gtk::Button {
-					set_visible: false,
+				pack_start = &gtk::ToggleButton {
 					set_tooltip: fl!("search"),
 					set_icon_name: icon_name::LOUPE,
+					#[watch]
+					#[block_signal(search_toggled)]
+					set_active: model.searching,
+					connect_toggled[sender] => move |button| {
+						sender.input(ContentInput::ShowSearch(button.is_active()));
+					} @search_toggled,
+				},
+				pack_end = &gtk::MenuButton {
+					set_tooltip: fl!("list-actions"),
+					set_icon_name: icon_name::MENU,
+					set_css_classes: &["flat"],
+					#[watch]
+					set_visible: !model.searching
+						&& matches!(model.parent_list, Some(SidebarList::Custom(_))),
+					set_menu_model: Some(&list_menu),
 				},
 			},
 			#[name(overlay)]
@@ -100,13 +215,19 @@ impl AsyncComponent for ContentModel {
 				set_child = &gtk::Box {
 					gtk::Box {
 						#[watch]
-						set_visible: model.parent_list.is_none(),
+						set_visible: model.searching,
+						set_hexpand: true,
+						append: model.search.widget()
+					},
+					gtk::Box {
+						#[watch]
+						set_visible: model.parent_list.is_none() && !model.searching,
 						append: model.welcome.widget()
 					},
 					adw::Clamp {
 						gtk::Box {
 							#[watch]
-							set_visible: model.parent_list.is_some(),
+							set_visible: model.parent_list.is_some() && !model.searching,
 							set_orientation: gtk::Orientation::Vertical,
 							#[transition = "Crossfade"]
 							append = match model.state {
@@ -184,7 +305,7 @@ impl AsyncComponent for ContentModel {
 														set_margin_start: 10,
 														set_margin_end: 10,
 														#[watch]
-														set_text: model.parent_list.as_ref().unwrap().name().as_str()
+														set_markup: &model.title_markup()
 													},
 												},
 												gtk::Label {
@@ -198,6 +319,24 @@ impl AsyncComponent for ContentModel {
 													#[watch]
 													set_text: model.parent_list.as_ref().unwrap().description().as_str()
 												},
+												gtk::Label {
+													#[watch]
+													set_visible: model.time_summary.is_some(),
+													set_css_classes: &["dim-label"],
+													set_halign: gtk::Align::Start,
+													set_margin_bottom: 10,
+													set_margin_start: 10,
+													set_margin_end: 10,
+													#[watch]
+													set_text: model.time_summary.as_deref().unwrap_or_default()
+												},
+												#[local_ref]
+												trash_lists -> adw::PreferencesGroup {
+													#[watch]
+													set_visible: !model.trash_list_factory.is_empty(),
+													set_title: fl!("deleted-lists"),
+													set_margin_all: 5,
+												},
 												#[name(task_container)]
 												gtk::Stack {
 													set_transition_duration: 250,
@@ -240,9 +379,10 @@ impl AsyncComponent for ContentModel {
 															set_vexpand: true,
 															set_hexpand: true,
 															#[local_ref]
-															list_box -> adw::PreferencesGroup {
+															list_box -> gtk::ListBox {
 																set_css_classes: &["boxed-list"],
-																set_valign: gtk::Align::Fill,
+																set_selection_mode: gtk::SelectionMode::None,
+																set_valign: gtk::Align::Start,
 																set_margin_all: 5,
 															},
 														}
@@ -271,10 +411,52 @@ impl AsyncComponent for ContentModel {
 	) -> AsyncComponentParts<Self> {
 		let model = ContentModel {
 			task_factory: AsyncFactoryVecDeque::builder()
-				.launch(adw::PreferencesGroup::default())
+				.launch(gtk::ListBox::default())
 				.forward(sender.input_sender(), |output| match output {
 					TaskOutput::Remove(index) => ContentInput::RemoveTask(index),
-					TaskOutput::UpdateTask(task) => ContentInput::UpdateTask(task),
+					TaskOutput::Restore(index) => ContentInput::RestoreTask(index),
+					TaskOutput::Reorder(task_id, index) => {
+						ContentInput::ReorderTask(task_id, index)
+					},
+					TaskOutput::UpdateTask(previous, task) => {
+						ContentInput::UpdateTask(previous, task)
+					},
+					TaskOutput::CreateSubTask(task, sub_task) => {
+						ContentInput::CreateSubTask(task, sub_task)
+					},
+					TaskOutput::UpdateSubTask(task, sub_task) => {
+						ContentInput::UpdateSubTask(task, sub_task)
+					},
+					TaskOutput::RemoveSubTask(task, sub_task_id) => {
+						ContentInput::RemoveSubTask(task, sub_task_id)
+					},
+					TaskOutput::CreateAttachment(index, task, attachment, content) => {
+						ContentInput::CreateAttachment(index, task, attachment, content)
+					},
+					TaskOutput::OpenAttachment(task, attachment) => {
+						ContentInput::OpenAttachment(task, attachment)
+					},
+					TaskOutput::RemoveAttachment(task, attachment_id) => {
+						ContentInput::RemoveAttachment(task, attachment_id)
+					},
+					TaskOutput::ChooseDependency(index, task) => {
+						ContentInput::ChooseDependency(index, task)
+					},
+					TaskOutput::AddDependency(index, task, blocking_task_id) => {
+						ContentInput::AddDependency(index, task, blocking_task_id)
+					},
+					TaskOutput::RemoveDependency(task, blocking_task_id) => {
+						ContentInput::RemoveDependency(task, blocking_task_id)
+					},
+					TaskOutput::LoadHistory(index, task) => {
+						ContentInput::LoadHistory(index, task)
+					},
+				}),
+			trash_list_factory: FactoryVecDeque::builder()
+				.launch(adw::PreferencesGroup::default())
+				.forward(sender.input_sender(), |output| match output {
+					TrashListOutput::Restore(index) => ContentInput::RestoreList(index),
+					TrashListOutput::Delete(index) => ContentInput::PurgeList(index),
 				}),
 			task_entry: TaskInputModel::builder()
 				.launch(SidebarList::default())
@@ -282,16 +464,78 @@ impl AsyncComponent for ContentModel {
 					TaskInputOutput::AddTask(task) => ContentInput::AddTask(task),
 				}),
 			welcome: WelcomeComponent::builder().launch(()).detach(),
+			search: SearchModel::builder().launch(()).forward(
+				sender.input_sender(),
+				|output| match output {
+					SearchOutput::Open(list, service) => {
+						ContentInput::SelectList(list, service)
+					},
+					SearchOutput::Close => ContentInput::ShowSearch(false),
+				},
+			),
+			searching: false,
 			state: ContentState::Unselected,
 			service: Service::Smart,
 			parent_list: None,
+			time_summary: None,
 			handle: None,
+			undo_stack: vec![],
+			redo_stack: vec![],
 		};
 
 		let list_box = model.task_factory.widget();
+		let trash_lists = model.trash_list_factory.widget();
+
+		let complete_all: &str = fl!("complete-all");
+		let delete_completed: &str = fl!("delete-completed");
 
 		let widgets = view_output!();
 
+		let mut actions = RelmActionGroup::<ContentActionGroup>::new();
+
+		let complete_all_action = {
+			let sender = sender.clone();
+			RelmAction::<CompleteAllAction>::new_stateless(move |_| {
+				sender.input(ContentInput::CompleteAll)
+			})
+		};
+
+		let delete_completed_action = {
+			let sender = sender.clone();
+			RelmAction::<DeleteCompletedAction>::new_stateless(move |_| {
+				sender.input(ContentInput::DeleteCompleted)
+			})
+		};
+
+		let undo_action = {
+			let sender = sender.clone();
+			RelmAction::<UndoAction>::new_stateless(move |_| {
+				sender.input(ContentInput::Undo)
+			})
+		};
+
+		let redo_action = {
+			let sender = sender.clone();
+			RelmAction::<RedoAction>::new_stateless(move |_| {
+				sender.input(ContentInput::Redo)
+			})
+		};
+
+		actions.add_action(complete_all_action);
+		actions.add_action(delete_completed_action);
+		actions.add_action(undo_action);
+		actions.add_action(redo_action);
+
+		let app = relm4::main_adw_application();
+		app.set_accelerators_for_action::<UndoAction>(&["<Control>z"]);
+		app.set_accelerators_for_action::<RedoAction>(&["<Control><Shift>z"]);
+
+		// On the root, so that the shortcuts work wherever the focus is.
+		root.insert_action_group(
+			ContentActionGroup::NAME,
+			Some(&actions.into_action_group()),
+		);
+
 		AsyncComponentParts { model, widgets }
 	}
 
@@ -304,12 +548,22 @@ impl AsyncComponent for ContentModel {
 	) {
 		match message {
 			ContentInput::Clean => self.state = ContentState::Unselected,
+			ContentInput::DatabaseChanged => {
+				self.state = ContentState::Unselected;
+				self.undo_stack.clear();
+				self.redo_stack.clear();
+			},
 			ContentInput::SetState(state) => self.state = state,
 			ContentInput::LoadTask(task) => {
 				if let SidebarList::Custom(parent) = &self.parent_list.as_ref().unwrap()
 				{
-					let mut guard = self.task_factory.guard();
-					guard.push_back(TaskInit::new(task, parent.clone()));
+					if parent.settings.shows(&task) {
+						let index = self.sorted_index(&parent.settings, &task);
+						self
+							.task_factory
+							.guard()
+							.insert(index, TaskInit::new(task, parent.clone()));
+					}
 					self.state = ContentState::TasksLoaded;
 				}
 			},
@@ -320,10 +574,11 @@ impl AsyncComponent for ContentModel {
 					let mut service = self.service.get_service();
 					match service.create_task(task.clone()).await {
 						Ok(_) => {
+							let index = self.sorted_index(&parent.settings, &task);
 							self
 								.task_factory
 								.guard()
-								.push_back(TaskInit::new(task.clone(), parent.clone()));
+								.insert(index, TaskInit::new(task.clone(), parent.clone()));
 							self.state = ContentState::TasksLoaded;
 						},
 						Err(err) => {
@@ -336,34 +591,446 @@ impl AsyncComponent for ContentModel {
 				let mut guard = self.task_factory.guard();
 				if let Some(task) = guard.get(index.current_index()) {
 					let mut service = self.service.get_service();
+					// Only the local service keeps deleted tasks to restore them.
+					let undoable = task.parent_list.service == Service::Computer
+						&& task.task.deletion_date.is_none();
+					let removed = task.task.clone();
 					match service
 						.delete_task(task.task.clone().parent, task.task.clone().id)
 						.await
 					{
 						Ok(_) => {
 							guard.remove(index.current_index());
+							drop(guard);
+							if undoable {
+								self.record(Edit::Delete(vec![removed]));
+							}
 						},
 						Err(err) => tracing::error!("An error ocurred: {err}"),
 					}
 				}
 			},
-			ContentInput::UpdateTask(task) => {
+			ContentInput::RestoreTask(index) => {
+				let mut guard = self.task_factory.guard();
+				if let Some(task) = guard.get(index.current_index()) {
+					let mut service = self.service.get_service();
+					match service
+						.restore_task(task.task.clone().parent, task.task.clone().id)
+						.await
+					{
+						Ok(_) => {
+							guard.remove(index.current_index());
+							sender
+								.output(ContentOutput::ReloadTaskLists)
+								.unwrap_or_default();
+						},
+						Err(err) => tracing::error!("An error ocurred: {err}"),
+					}
+				}
+			},
+			ContentInput::ReorderTask(task_id, target) => {
+				if let Some(SidebarList::Custom(list)) = self.parent_list.clone() {
+					if list.settings.sorted() {
+						widgets
+							.overlay
+							.add_toast(adw::Toast::new(fl!("reorder-sorted-list")));
+					} else {
+						let mut guard = self.task_factory.guard();
+						let source = guard.iter().position(|task| {
+							task.is_some_and(|task| task.task.id == task_id)
+						});
+						let target = target.current_index();
+						let neighbour =
+							|index: usize| guard.get(index).map(|task| task.task.id.clone());
+						// Tasks are placed next to the one they are dropped on, since
+						// the tasks shown are not always all the tasks of the list.
+						let to = match source {
+							Some(source) if source < target => {
+								neighbour(target).map(Placement::After)
+							},
+							Some(source) if source > target => {
+								neighbour(target).map(Placement::Before)
+							},
+							_ => None,
+						};
+						let from = source.and_then(|source| {
+							neighbour(source + 1).map(Placement::Before).or_else(|| {
+								source
+									.checked_sub(1)
+									.and_then(neighbour)
+									.map(Placement::After)
+							})
+						});
+						if let (Some(source), Some(from), Some(to)) = (source, from, to) {
+							let mut service = self.service.get_service();
+							match service
+								.reorder_task(list.id.clone(), task_id.clone(), to.clone())
+								.await
+							{
+								Ok(_) => {
+									guard.move_to(source, target);
+									drop(guard);
+									self.record(Edit::Reorder {
+										list_id: list.id.clone(),
+										task_id,
+										from,
+										to,
+									});
+								},
+								Err(err) => tracing::error!("An error ocurred: {err}"),
+							}
+						}
+					}
+				}
+			},
+			ContentInput::RestoreList(index) => {
+				let mut guard = self.trash_list_factory.guard();
+				if let Some(trash_list) = guard.get(index.current_index()) {
+					let mut service = self.service.get_service();
+					match service.restore_list(trash_list.list.id.clone()).await {
+						Ok(_) => {
+							guard.remove(index.current_index());
+							sender
+								.output(ContentOutput::ReloadTaskLists)
+								.unwrap_or_default();
+						},
+						Err(err) => tracing::error!("An error ocurred: {err}"),
+					}
+				}
+			},
+			ContentInput::PurgeList(index) => {
+				let mut guard = self.trash_list_factory.guard();
+				if let Some(trash_list) = guard.get(index.current_index()) {
+					let mut service = self.service.get_service();
+					match service.delete_list(trash_list.list.id.clone()).await {
+						Ok(_) => {
+							guard.remove(index.current_index());
+						},
+						Err(err) => tracing::error!("An error ocurred: {err}"),
+					}
+				}
+			},
+			ContentInput::UpdateTask(previous, task) => {
 				let mut service = self.service.get_service();
-				match service.update_task(task).await {
-					Ok(task) => tracing::info!("Task {} successfully saved.", task.id),
+				let recurring = task.recurrence.is_some();
+				let changed = previous != task;
+				let known =
+					Self::known_tasks(self.service, &[previous.clone()], &[task.clone()])
+						.await;
+				match service.update_task(task.clone()).await {
+					Ok(saved) => {
+						tracing::info!("Task {} successfully saved.", saved.id);
+						if changed {
+							let spawned = Self::created_tasks(self.service, known).await;
+							self.record_update(previous, saved.clone(), spawned);
+						}
+						let task = saved;
+						let dependents: Vec<usize> = self
+							.task_factory
+							.iter()
+							.enumerate()
+							.filter_map(|(index, model)| {
+								model
+									.filter(|model| {
+										model
+											.task
+											.blocked_by
+											.iter()
+											.any(|dependency| dependency.task_id == task.id)
+									})
+									.map(|_| index)
+							})
+							.collect();
+						for index in dependents {
+							self.task_factory.send(
+								index,
+								TaskInput::SetDependencyCompleted(
+									task.id.clone(),
+									task.status == Status::Completed,
+								),
+							);
+						}
+						if let Some(SidebarList::Custom(list)) = self.parent_list.clone() {
+							self.arrange_task(&list.settings, &task);
+						}
+						if recurring && task.recurrence.is_none() {
+							if let Some(list) = self.parent_list.clone() {
+								sender.input(ContentInput::LoadTasks(list, self.service));
+							}
+						}
+					},
 					Err(err) => tracing::error!("An error ocurred: {err}"),
 				}
 			},
+			ContentInput::CreateSubTask(task, sub_task) => {
+				let mut service = self.service.get_service();
+				if let Err(err) = service
+					.create_sub_task(task.parent, task.id, sub_task)
+					.await
+				{
+					tracing::error!("An error ocurred: {err}");
+				}
+			},
+			ContentInput::UpdateSubTask(task, sub_task) => {
+				let mut service = self.service.get_service();
+				if let Err(err) = service
+					.update_sub_task(task.parent, task.id, sub_task)
+					.await
+				{
+					tracing::error!("An error ocurred: {err}");
+				}
+			},
+			ContentInput::RemoveSubTask(task, sub_task_id) => {
+				let mut service = self.service.get_service();
+				if let Err(err) = service
+					.delete_sub_task(task.parent, task.id, sub_task_id)
+					.await
+				{
+					tracing::error!("An error ocurred: {err}");
+				}
+			},
+			ContentInput::CreateAttachment(index, task, attachment, content) => {
+				let mut service = self.service.get_service();
+				match service
+					.create_attachment(task.parent, task.id, attachment, content)
+					.await
+				{
+					Ok(attachment) => self.task_factory.send(
+						index.current_index(),
+						TaskInput::AttachmentAdded(attachment),
+					),
+					Err(err) => {
+						tracing::error!("An error ocurred: {err}");
+						widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+					},
+				}
+			},
+			ContentInput::OpenAttachment(task, attachment) => {
+				let mut service = self.service.get_service();
+				match service
+					.read_attachment_content(task.parent, task.id, attachment)
+					.await
+				{
+					Ok(path) => {
+						let launcher =
+							gtk::FileLauncher::new(Some(&gio::File::for_path(path)));
+						let window = relm4::main_adw_application().active_window();
+						if let Err(err) = launcher.launch_future(window.as_ref()).await {
+							tracing::error!("An error ocurred: {err}");
+						}
+					},
+					Err(err) => {
+						tracing::error!("An error ocurred: {err}");
+						widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+					},
+				}
+			},
+			ContentInput::RemoveAttachment(task, attachment_id) => {
+				let mut service = self.service.get_service();
+				if let Err(err) = service
+					.delete_attachment(task.parent, task.id, attachment_id)
+					.await
+				{
+					tracing::error!("An error ocurred: {err}");
+				}
+			},
+			ContentInput::ChooseDependency(index, task) => {
+				let candidates: Vec<Dependency> = self
+					.task_factory
+					.iter()
+					.flatten()
+					.filter(|model| {
+						model.task.parent == task.parent
+							&& model.task.id != task.id
+							&& model.task.status != Status::Completed
+							&& !task
+								.blocked_by
+								.iter()
+								.any(|dependency| dependency.task_id == model.task.id)
+					})
+					.map(|model| Dependency {
+						task_id: model.task.id.clone(),
+						title: model.task.title.clone(),
+						completed: false,
+					})
+					.collect();
+				if candidates.is_empty() {
+					widgets
+						.overlay
+						.add_toast(adw::Toast::new(fl!("no-dependency-candidates")));
+				} else {
+					self.task_factory.send(
+						index.current_index(),
+						TaskInput::SetDependencyCandidates(candidates),
+					);
+				}
+			},
+			ContentInput::AddDependency(index, task, blocking_task_id) => {
+				let mut service = self.service.get_service();
+				match service
+					.add_dependency(task.parent, task.id, blocking_task_id)
+					.await
+				{
+					Ok(dependency) => self.task_factory.send(
+						index.current_index(),
+						TaskInput::DependencyAdded(dependency),
+					),
+					Err(err) => {
+						tracing::error!("An error ocurred: {err}");
+						widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+					},
+				}
+			},
+			ContentInput::RemoveDependency(task, blocking_task_id) => {
+				let mut service = self.service.get_service();
+				if let Err(err) = service
+					.remove_dependency(task.parent, task.id, blocking_task_id)
+					.await
+				{
+					tracing::error!("An error ocurred: {err}");
+				}
+			},
+			ContentInput::LoadHistory(index, task) => {
+				let mut service = self.service.get_service();
+				match service.read_task_history(task.parent, task.id).await {
+					Ok(changes) => self
+						.task_factory
+						.send(index.current_index(), TaskInput::SetHistory(changes)),
+					Err(err) => {
+						tracing::error!("An error ocurred: {err}");
+						widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+					},
+				}
+			},
+			ContentInput::ShowSearch(show) => {
+				self.searching = show;
+				if show {
+					self.search.emit(SearchInput::Focus);
+				}
+			},
+			ContentInput::CompleteAll | ContentInput::DeleteCompleted => {
+				if let Some(SidebarList::Custom(list)) = self.parent_list.clone() {
+					let complete = matches!(message, ContentInput::CompleteAll);
+					let mut service = self.service.get_service();
+					let result = match service.read_tasks_from_list(list.id.clone()).await
+					{
+						Ok(list_tasks) if complete => {
+							let before: Vec<Task> = list_tasks
+								.into_iter()
+								.filter(|task| task.status != Status::Completed)
+								.collect();
+							let pending: Vec<Task> = before
+								.iter()
+								.cloned()
+								.map(|mut task| {
+									task.status = Status::Completed;
+									task
+								})
+								.collect();
+							let known =
+								Self::known_tasks(self.service, &before, &pending).await;
+							match service.update_tasks(pending).await {
+								Ok(after) => Ok(Edit::Update {
+									before,
+									after,
+									spawned: Self::created_tasks(self.service, known).await,
+								}),
+								Err(err) => Err(err),
+							}
+						},
+						Ok(list_tasks) => {
+							let completed: Vec<Task> = list_tasks
+								.into_iter()
+								.filter(|task| {
+									task.status == Status::Completed
+										&& task.deletion_date.is_none()
+								})
+								.collect();
+							service
+								.delete_tasks(completed.clone())
+								.await
+								.map(|_| Edit::Delete(completed))
+						},
+						Err(err) => Err(err),
+					};
+					match result {
+						Ok(edit) => {
+							if !matches!(edit, Edit::Delete(_))
+								|| list.service == Service::Computer
+							{
+								self.record(edit);
+							}
+							sender.input(ContentInput::SelectList(
+								SidebarList::Custom(list),
+								self.service,
+							))
+						},
+						Err(err) => {
+							tracing::error!("An error ocurred: {err}");
+							widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+						},
+					}
+				}
+			},
+			ContentInput::Undo | ContentInput::Redo => {
+				let undo = matches!(message, ContentInput::Undo);
+				let edit = if undo {
+					self.undo_stack.pop()
+				} else {
+					self.redo_stack.pop()
+				};
+				match edit {
+					Some((service, edit)) => {
+						match Self::apply(service, &edit, undo).await {
+							Ok(edit) => {
+								if undo {
+									self.redo_stack.push((service, edit));
+								} else {
+									self.undo_stack.push((service, edit));
+								}
+								if let Some(list) = self.parent_list.clone() {
+									sender.input(ContentInput::LoadTasks(list, self.service));
+								}
+								sender
+									.output(ContentOutput::ReloadTaskLists)
+									.unwrap_or_default();
+							},
+							Err(err) => {
+								tracing::error!("An error ocurred: {err}");
+								widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+							},
+						}
+					},
+					None => widgets.overlay.add_toast(adw::Toast::new(if undo {
+						fl!("nothing-to-undo")
+					} else {
+						fl!("nothing-to-redo")
+					})),
+				}
+			},
 			ContentInput::SelectList(list, service) => {
+				self.searching = false;
 				self.state = ContentState::Loading;
 				if let Some(handle) = &self.handle {
 					handle.abort()
 				}
 				sender.input(ContentInput::LoadTasks(list, service));
 			},
+			ContentInput::ListUpdated(list) => {
+				if let (
+					Some(SidebarList::Custom(current)),
+					SidebarList::Custom(updated),
+				) = (&self.parent_list, &list)
+				{
+					if current.id == updated.id {
+						sender.input(ContentInput::SelectList(list, self.service));
+					}
+				}
+			},
 			ContentInput::LoadTasks(list, service) => {
 				let mut guard = self.task_factory.guard();
 				guard.clear();
+				self.trash_list_factory.guard().clear();
 				self.service = service;
 
 				let mut service = service.get_service();
@@ -371,7 +1038,7 @@ impl AsyncComponent for ContentModel {
 					match &list {
 						SidebarList::All => {
 							self.parent_list = Some(SidebarList::All);
-							for task in tasks {
+							for task in tasks.into_iter().filter(|task| task.actionable()) {
 								guard.push_back(TaskInit::new(
 									task.clone(),
 									service.read_list(task.parent).await.unwrap(),
@@ -381,12 +1048,17 @@ impl AsyncComponent for ContentModel {
 						},
 						SidebarList::Today => {
 							self.parent_list = Some(SidebarList::Today);
-							for task in tasks.iter().filter(|task| {
-								task.today
-									|| task.due_date.is_some()
-										&& task.due_date.unwrap().date_naive()
-											== Utc::now().date_naive()
-							}) {
+							for task in
+								tasks
+									.iter()
+									.filter(|task| task.actionable())
+									.filter(|task| {
+										task.today
+											|| task.due_date.is_some_and(|date| {
+												time_zone::local_date(date)
+													== time_zone::local_date(Utc::now())
+											})
+									}) {
 								guard.push_back(TaskInit::new(
 									task.clone(),
 									service.read_list(task.parent.clone()).await.unwrap(),
@@ -396,7 +1068,10 @@ impl AsyncComponent for ContentModel {
 						},
 						SidebarList::Starred => {
 							self.parent_list = Some(SidebarList::Starred);
-							for task in tasks.iter().filter(|task| task.favorite) {
+							for task in tasks
+								.iter()
+								.filter(|task| task.actionable() && task.favorite)
+							{
 								guard.push_back(TaskInit::new(
 									task.clone(),
 									service.read_list(task.parent.clone()).await.unwrap(),
@@ -407,7 +1082,8 @@ impl AsyncComponent for ContentModel {
 						SidebarList::Next7Days => {
 							self.parent_list = Some(SidebarList::Next7Days);
 							for task in tasks.iter().filter(|task: &&Task| {
-								task.due_date.is_some()
+								task.actionable()
+									&& task.due_date.is_some()
 									&& is_within_next_7_days(task.due_date.unwrap())
 							}) {
 								guard.push_back(TaskInit::new(
@@ -417,6 +1093,22 @@ impl AsyncComponent for ContentModel {
 							}
 							self.state = ContentState::TasksLoaded;
 						},
+						SidebarList::Actionable => {
+							self.parent_list = Some(SidebarList::Actionable);
+							for task in tasks.iter().filter(|task| {
+								task.status != Status::Completed
+									&& task.actionable()
+									&& !task.is_blocked()
+							}) {
+								match service.read_list(task.parent.clone()).await {
+									Ok(parent) => {
+										guard.push_back(TaskInit::new(task.clone(), parent))
+									},
+									Err(err) => tracing::error!("An error ocurred: {err}"),
+								}
+							}
+							self.state = ContentState::TasksLoaded;
+						},
 						SidebarList::Done => {
 							self.parent_list = Some(SidebarList::Done);
 							for task in tasks
@@ -430,6 +1122,49 @@ impl AsyncComponent for ContentModel {
 							}
 							self.state = ContentState::TasksLoaded;
 						},
+						SidebarList::Trash => {
+							self.parent_list = Some(SidebarList::Trash);
+							match service.read_deleted_lists().await {
+								Ok(lists) => {
+									let mut trash_guard = self.trash_list_factory.guard();
+									for list in lists {
+										trash_guard.push_back(list);
+									}
+								},
+								Err(err) => tracing::error!("An error ocurred: {err}"),
+							}
+							match service.read_deleted_tasks().await {
+								Ok(tasks) => {
+									for task in tasks {
+										match service.read_list(task.parent.clone()).await {
+											Ok(parent) => {
+												guard.push_back(TaskInit::new(task, parent))
+											},
+											Err(err) => tracing::error!("An error ocurred: {err}"),
+										}
+									}
+								},
+								Err(err) => tracing::error!("An error ocurred: {err}"),
+							}
+							self.state = ContentState::TasksLoaded;
+						},
+						SidebarList::Tag(tag) => {
+							self.parent_list = Some(SidebarList::Tag(tag.clone()));
+							match service.read_tasks_with_tag(tag.name.clone()).await {
+								Ok(tasks) => {
+									for task in tasks {
+										match service.read_list(task.parent.clone()).await {
+											Ok(parent) => {
+												guard.push_back(TaskInit::new(task, parent))
+											},
+											Err(err) => tracing::error!("An error ocurred: {err}"),
+										}
+									}
+									self.state = ContentState::TasksLoaded;
+								},
+								Err(err) => tracing::error!("An error ocurred: {err}"),
+							}
+						},
 						SidebarList::Custom(list) => {
 							self.parent_list = Some(SidebarList::Custom(list.clone()));
 							let sender_clone = sender.clone();
@@ -454,9 +1189,11 @@ impl AsyncComponent for ContentModel {
 										Err(err) => tracing::error!("{err}"),
 									}
 								}));
-							} else if let Ok(tasks) =
+							} else if let Ok(mut tasks) =
 								service.read_tasks_from_list(list_clone.id.clone()).await
 							{
+								tasks.retain(|task| list.settings.shows(task));
+								tasks.sort_by(|a, b| list.settings.compare(a, b));
 								if tasks.is_empty() {
 									self.state = ContentState::Empty;
 								} else {
@@ -475,11 +1212,16 @@ impl AsyncComponent for ContentModel {
 					}
 				}
 
-				if guard.is_empty() && self.state != ContentState::Loading {
+				if guard.is_empty()
+					&& self.trash_list_factory.is_empty()
+					&& self.state != ContentState::Loading
+				{
 					self.state = ContentState::Empty;
 				}
 
-				if list.smart() {
+				if list.smart()
+					&& !matches!(list, SidebarList::Trash | SidebarList::Tag(_))
+				{
 					self.state = ContentState::Unselected;
 				}
 
@@ -495,12 +1237,351 @@ impl AsyncComponent for ContentModel {
 				if self.service == service {
 					self.state = ContentState::Unselected;
 				}
+				self.undo_stack.retain(|(edited, _)| *edited != service);
+				self.redo_stack.retain(|(edited, _)| *edited != service);
 			},
 		}
+		self.time_summary = self.time_summary();
+		self.set_section_headers();
 		self.update_view(widgets, sender)
 	}
 }
 
+impl ContentModel {
+	/// Keeps an edit made in the current service to undo it, a new edit can't
+	/// be redone after the ones that were undone.
+	fn record(&mut self, edit: Edit) {
+		if self.service == Service::Smart {
+			return;
+		}
+		self.redo_stack.clear();
+		self.undo_stack.push((self.service, edit));
+		if self.undo_stack.len() > UNDO_LIMIT {
+			self.undo_stack.remove(0);
+		}
+	}
+
+	/// Records a change to a task, the title is saved as it is typed, so
+	/// consecutive edits to it are undone at once.
+	fn record_update(&mut self, before: Task, after: Task, spawned: Vec<Task>) {
+		let title_edit = {
+			let mut renamed = before.clone();
+			renamed.title = after.title.clone();
+			renamed.last_modified_date_time = after.last_modified_date_time;
+			renamed == after
+		};
+		if title_edit && self.redo_stack.is_empty() {
+			if let Some((
+				service,
+				Edit::Update {
+					before: first,
+					after: last,
+					spawned,
+				},
+			)) = self.undo_stack.last_mut()
+			{
+				if *service == self.service
+					&& spawned.is_empty()
+					&& last.len() == 1
+					&& last[0].id == before.id
+					&& last[0].title == before.title
+					&& first[0].title != before.title
+				{
+					last[0] = after;
+					return;
+				}
+			}
+		}
+		self.record(Edit::Update {
+			before: vec![before],
+			after: vec![after],
+			spawned,
+		});
+	}
+
+	/// Completing a recurring task makes the service create its next
+	/// occurrence in the same list. The tasks in those lists are read before
+	/// saving, to tell the new ones apart afterwards.
+	async fn known_tasks(
+		service: Service,
+		before: &[Task],
+		tasks: &[Task],
+	) -> Vec<Task> {
+		let mut lists: Vec<String> = tasks
+			.iter()
+			.filter(|task| {
+				task.recurrence.is_some()
+					&& task.status == Status::Completed
+					&& before.iter().any(|previous| {
+						previous.id == task.id && previous.status != Status::Completed
+					})
+			})
+			.map(|task| task.parent.clone())
+			.collect();
+		lists.sort();
+		lists.dedup();
+		let mut known = vec![];
+		let mut service = service.get_service();
+		for list in lists {
+			match service.read_tasks_from_list(list).await {
+				Ok(list_tasks) => known.extend(list_tasks),
+				Err(err) => tracing::error!("An error ocurred: {err}"),
+			}
+		}
+		known
+	}
+
+	/// The tasks that were added to the lists of `known` since it was read.
+	async fn created_tasks(service: Service, known: Vec<Task>) -> Vec<Task> {
+		let mut lists: Vec<String> =
+			known.iter().map(|task| task.parent.clone()).collect();
+		lists.sort();
+		lists.dedup();
+		let mut created = vec![];
+		let mut service = service.get_service();
+		for list in lists {
+			match service.read_tasks_from_list(list).await {
+				Ok(list_tasks) => {
+					created.extend(list_tasks.into_iter().filter(|task| {
+						!known.iter().any(|previous| previous.id == task.id)
+					}))
+				},
+				Err(err) => tracing::error!("An error ocurred: {err}"),
+			}
+		}
+		created
+	}
+
+	/// Reverts an edit, or makes it again, and returns it as it has to be
+	/// recorded to do the opposite. Updates only set the fields the edit
+	/// changed, keeping the changes made to the tasks since.
+	async fn apply(service: Service, edit: &Edit, undo: bool) -> Result<Edit> {
+		let mut provider = service.get_service();
+		match edit {
+			Edit::Update {
+				before,
+				after,
+				spawned,
+			} => {
+				let (from, to) = if undo {
+					(after, before)
+				} else {
+					(before, after)
+				};
+				let mut tasks = vec![];
+				for (from, to) in from.iter().zip(to) {
+					let mut task = provider
+						.read_task(from.parent.clone(), from.id.clone())
+						.await?;
+					task.apply_change(from, to);
+					// The service takes the recurrence away from a task it completes,
+					// so it has to be there for the next occurrence to be created.
+					if from.recurrence.is_some()
+						&& from.status != Status::Completed
+						&& to.status == Status::Completed
+					{
+						task.recurrence = from.recurrence.clone();
+					}
+					tasks.push(task);
+				}
+				let known = Self::known_tasks(service, from, &tasks).await;
+				provider.update_tasks(tasks).await?;
+				if undo && !spawned.is_empty() {
+					provider.delete_tasks(spawned.clone()).await?;
+					// Deleting them again takes them out of the trash.
+					if service == Service::Computer {
+						provider.delete_tasks(spawned.clone()).await?;
+					}
+				}
+				Ok(Edit::Update {
+					before: before.clone(),
+					after: after.clone(),
+					spawned: Self::created_tasks(service, known).await,
+				})
+			},
+			Edit::Delete(tasks) => {
+				if undo {
+					for task in tasks {
+						provider
+							.restore_task(task.parent.clone(), task.id.clone())
+							.await?;
+					}
+				} else {
+					provider.delete_tasks(tasks.clone()).await?;
+				}
+				Ok(edit.clone())
+			},
+			Edit::Reorder {
+				list_id,
+				task_id,
+				from,
+				to,
+			} => {
+				let placement = if undo { from } else { to };
+				provider
+					.reorder_task(list_id.clone(), task_id.clone(), placement.clone())
+					.await?;
+				Ok(edit.clone())
+			},
+		}
+	}
+
+	/// The name of the list, in its accent color if it has one.
+	fn title_markup(&self) -> String {
+		let Some(list) = &self.parent_list else {
+			return String::new();
+		};
+		let name = gtk::glib::markup_escape_text(&list.name());
+		match list {
+			SidebarList::Custom(list) if list.settings.color.is_some() => format!(
+				"<span foreground=\"{}\">{name}</span>",
+				list.settings.color.as_ref().unwrap()
+			),
+			_ => name.to_string(),
+		}
+	}
+
+	/// Where a task goes among the ones shown, after every task it does not
+	/// come before.
+	fn sorted_index(&self, settings: &ListSettings, task: &Task) -> usize {
+		self
+			.task_factory
+			.iter()
+			.position(|model| {
+				model.is_some_and(|model| {
+					settings.compare(&model.task, task) == Ordering::Greater
+				})
+			})
+			.unwrap_or(self.task_factory.len())
+	}
+
+	/// Hides a task that was just completed if the list hides completed
+	/// tasks, or moves it to where it belongs if the list is sorted.
+	fn arrange_task(&mut self, settings: &ListSettings, task: &Task) {
+		let Some(current) = self
+			.task_factory
+			.iter()
+			.position(|model| model.is_some_and(|model| model.task.id == task.id))
+		else {
+			return;
+		};
+		if !settings.shows(task) {
+			self.task_factory.guard().remove(current);
+		} else if settings.sorted() {
+			let target = self
+				.task_factory
+				.iter()
+				.enumerate()
+				.filter(|(index, _)| *index != current)
+				.filter_map(|(_, model)| model)
+				.position(|model| {
+					settings.compare(&model.task, task) == Ordering::Greater
+				})
+				.unwrap_or(self.task_factory.len() - 1);
+			if target != current {
+				self.task_factory.guard().move_to(current, target);
+			}
+		}
+	}
+
+	/// Shows a header above the first task of every section when the list
+	/// groups its tasks.
+	fn set_section_headers(&self) {
+		let list_box = self.task_factory.widget();
+		let grouping = match &self.parent_list {
+			Some(SidebarList::Custom(list))
+				if list.settings.grouping != TaskGrouping::None =>
+			{
+				list.settings.grouping
+			},
+			_ => {
+				list_box.unset_header_func();
+				return;
+			},
+		};
+		let mut previous = None;
+		let titles: Vec<Option<String>> = self
+			.task_factory
+			.iter()
+			.map(|model| {
+				let section = model.map(|model| grouping.section(&model.task));
+				let title = (section.is_some() && section != previous)
+					.then(|| section_title(grouping, section.unwrap()));
+				previous = section;
+				title
+			})
+			.collect();
+		list_box.set_header_func(move |row, _| {
+			match titles.get(row.index() as usize).cloned().flatten() {
+				Some(title) => {
+					let header = gtk::Label::new(Some(&title));
+					header.add_css_class("heading");
+					header.set_halign(gtk::Align::Start);
+					header.set_margin_top(12);
+					header.set_margin_bottom(6);
+					header.set_margin_start(12);
+					row.set_header(Some(&header));
+				},
+				None => row.set_header(None::<&gtk::Widget>),
+			}
+		});
+	}
+
+	/// Planned versus spent time of the tasks shown, if any was recorded.
+	fn time_summary(&self) -> Option<String> {
+		let (estimated, tracked) = self.task_factory.iter().flatten().fold(
+			(0, Duration::zero()),
+			|(estimated, tracked), model| {
+				(
+					estimated + model.task.estimated_minutes.unwrap_or_default(),
+					tracked + model.task.tracked_time(),
+				)
+			},
+		);
+		if estimated == 0 && tracked.is_zero() {
+			return None;
+		}
+		Some(
+			fl!(
+				"time-summary",
+				planned = format_duration(Duration::minutes(estimated)),
+				spent = format_duration(tracked)
+			)
+			.to_string(),
+		)
+	}
+}
+
+/// The header of a section of a grouped list.
+fn section_title(grouping: TaskGrouping, section: i32) -> String {
+	match grouping {
+		TaskGrouping::None => String::new(),
+		TaskGrouping::Status => match section {
+			0 => fl!("in-progress"),
+			1 => fl!("not-started"),
+			2 => fl!("waiting"),
+			3 => fl!("deferred"),
+			_ => fl!("completed"),
+		}
+		.to_string(),
+		TaskGrouping::Priority => {
+			let priority = match Priority::from(-section) {
+				Priority::Low => fl!("low"),
+				Priority::Normal => fl!("medium"),
+				Priority::High => fl!("high"),
+			};
+			fl!("priority-section", priority = priority.as_str()).to_string()
+		},
+		TaskGrouping::DueDate => match section {
+			0 => fl!("overdue"),
+			1 => fl!("today"),
+			2 => fl!("upcoming"),
+			_ => fl!("no-due-date"),
+		}
+		.to_string(),
+	}
+}
+
 fn is_within_next_7_days(date: DateTime<Utc>) -> bool {
 	let now = Utc::now();
 	let next_7_days = now + chrono::Duration::days(7);
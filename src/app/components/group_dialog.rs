@@ -0,0 +1,147 @@
+use core_done::models::group::Group;
+use gtk::prelude::{
+	BoxExt, ButtonExt, EntryBufferExtManual, EntryExt, ListModelExt, WidgetExt,
+};
+use relm4::{
+	adw,
+	gtk::{
+		self,
+		traits::{GtkWindowExt, OrientableExt},
+	},
+	Component, ComponentParts, ComponentSender, RelmWidgetExt,
+};
+
+use crate::fl;
+
+#[derive(Debug)]
+pub struct GroupDialogComponent {
+	groups: Vec<Group>,
+	group_names: gtk::StringList,
+	new_group: gtk::EntryBuffer,
+}
+
+#[derive(Debug)]
+pub enum GroupDialogInput {
+	Show(Vec<Group>, Option<String>),
+	Confirm,
+}
+
+#[derive(Debug)]
+pub enum GroupDialogOutput {
+	MoveToGroup(Option<String>),
+	MoveToNewGroup(String),
+}
+
+#[relm4::component(pub)]
+impl Component for GroupDialogComponent {
+	type Input = GroupDialogInput;
+	type Output = GroupDialogOutput;
+	type Init = ();
+	type CommandOutput = ();
+
+	view! {
+		#[root]
+		adw::Window {
+			set_hide_on_close: true,
+			set_default_width: 320,
+			set_resizable: false,
+			set_modal: true,
+
+			gtk::Box {
+				set_orientation: gtk::Orientation::Vertical,
+
+				adw::HeaderBar {
+					set_show_end_title_buttons: true,
+					set_css_classes: &["flat"],
+					set_title_widget: Some(&gtk::Box::default())
+				},
+				gtk::Box {
+					set_orientation: gtk::Orientation::Vertical,
+					set_margin_all: 20,
+					set_spacing: 10,
+					gtk::Image {
+						set_icon_size: gtk::IconSize::Large,
+						set_icon_name: Some("folder-symbolic"),
+					},
+					gtk::Label {
+						set_css_classes: &["title-4"],
+						set_label: fl!("move-to-group"),
+					},
+					gtk::Label {
+						set_label: fl!("move-to-group-desc"),
+					},
+					#[name(groups_dropdown)]
+					gtk::DropDown {
+						set_model: Some(&model.group_names),
+					},
+					gtk::Entry {
+						set_placeholder_text: Some(fl!("new-group-name")),
+						set_buffer: &model.new_group,
+						connect_activate => GroupDialogInput::Confirm,
+					},
+					gtk::Button {
+						set_css_classes: &["suggested-action"],
+						set_label: fl!("move"),
+						connect_clicked => GroupDialogInput::Confirm,
+					},
+				}
+			}
+		}
+	}
+
+	fn init(
+		_init: Self::Init,
+		root: &Self::Root,
+		sender: ComponentSender<Self>,
+	) -> ComponentParts<Self> {
+		let model = GroupDialogComponent {
+			groups: vec![],
+			group_names: gtk::StringList::new(&[fl!("no-group")]),
+			new_group: gtk::EntryBuffer::new(Some("")),
+		};
+
+		let widgets = view_output!();
+		ComponentParts { model, widgets }
+	}
+
+	fn update_with_view(
+		&mut self,
+		widgets: &mut Self::Widgets,
+		message: Self::Input,
+		sender: ComponentSender<Self>,
+		root: &Self::Root,
+	) {
+		match message {
+			GroupDialogInput::Show(groups, current) => {
+				let mut names = vec![fl!("no-group").as_str()];
+				names.extend(groups.iter().map(|group| group.name.as_str()));
+				self
+					.group_names
+					.splice(0, self.group_names.n_items(), &names);
+				widgets.groups_dropdown.set_selected(
+					current
+						.and_then(|id| groups.iter().position(|group| group.id == id))
+						.map_or(0, |position| position as u32 + 1),
+				);
+				self.groups = groups;
+				self.new_group.set_text("");
+				root.present();
+			},
+			GroupDialogInput::Confirm => {
+				let new_group = self.new_group.text().trim().to_string();
+				let output = if !new_group.is_empty() {
+					GroupDialogOutput::MoveToNewGroup(new_group)
+				} else {
+					GroupDialogOutput::MoveToGroup(
+						(widgets.groups_dropdown.selected() as usize)
+							.checked_sub(1)
+							.and_then(|position| self.groups.get(position))
+							.map(|group| group.id.clone()),
+					)
+				};
+				sender.output(output).unwrap_or_default();
+				root.close();
+			},
+		}
+	}
+}
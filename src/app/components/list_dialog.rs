@@ -1,10 +1,17 @@
+use core_done::models::{
+	list::List,
+	list_settings::{ListSettings, SortOrder, TaskGrouping},
+};
 use gtk::prelude::{
-	BoxExt, ButtonExt, EntryBufferExtManual, EntryExt, WidgetExt,
+	BoxExt, ButtonExt, EditableExt, EntryBufferExtManual, EntryExt, WidgetExt,
 };
 use relm4::{
 	adw,
+	adw::prelude::{
+		ActionRowExt, ComboRowExt, PreferencesGroupExt, PreferencesRowExt,
+	},
 	gtk::{
-		self,
+		self, gdk,
 		traits::{GtkWindowExt, OrientableExt},
 	},
 	Component, ComponentParts, ComponentSender, RelmWidgetExt,
@@ -18,6 +25,10 @@ pub struct ListDialogComponent {
 	pub name: gtk::EntryBuffer,
 	pub mode: ListDialogMode,
 	pub label: String,
+	pub description: String,
+	pub settings: ListSettings,
+	/// Lists shared with the user can only be renamed by their owner.
+	pub owned: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -29,19 +40,25 @@ pub enum ListDialogMode {
 #[derive(Debug)]
 pub enum ListDialogInput {
 	HandleEntry,
+	SetDescription(String),
+	SetColor(Option<String>),
+	SetSortOrder(SortOrder),
+	SetShowCompleted(bool),
+	SetGrouping(TaskGrouping),
 }
 
 #[derive(Debug)]
 pub enum ListDialogOutput {
 	AddTaskListToSidebar(String),
-	RenameList(String),
+	/// The new name, description and settings of the list being edited.
+	UpdateList(String, String, ListSettings),
 }
 
 #[relm4::component(pub)]
 impl Component for ListDialogComponent {
 	type Input = ListDialogInput;
 	type Output = ListDialogOutput;
-	type Init = Option<String>;
+	type Init = Option<List>;
 	type CommandOutput = ();
 
 	view! {
@@ -75,18 +92,102 @@ impl Component for ListDialogComponent {
 						set_css_classes: &["title-4"],
 						set_label: match model.mode {
 							ListDialogMode::New => "You're about to add a list.",
-							ListDialogMode::Edit => "You're about to rename this list."
+							ListDialogMode::Edit => "You're about to edit this list."
 						},
 					},
 					gtk::Label {
-						set_label: "Pick a descriptive name.",
+						set_label: if model.owned {
+							"Pick a descriptive name."
+						} else {
+							fl!("shared-list-rename").as_str()
+						},
+						set_wrap: true,
 					},
 					#[name = "new_list_entry"]
 					gtk::Entry {
+						set_sensitive: model.owned,
 						set_placeholder_text: Some(fl!("list-name")),
 						set_buffer: &model.name,
 						connect_activate => ListDialogInput::HandleEntry,
 					},
+					adw::PreferencesGroup {
+						set_visible: matches!(model.mode, ListDialogMode::Edit),
+						set_title: fl!("list-settings"),
+						adw::EntryRow {
+							set_title: fl!("list-description"),
+							set_text: &model.description,
+							connect_changed[sender] => move |entry| {
+								sender.input(ListDialogInput::SetDescription(entry.text().to_string()));
+							},
+						},
+						adw::ActionRow {
+							set_title: fl!("list-color"),
+							#[watch]
+							set_subtitle: model.settings.color.as_deref().unwrap_or(fl!("no-color")),
+							add_suffix = &gtk::ColorDialogButton {
+								set_valign: gtk::Align::Center,
+								set_dialog: &gtk::ColorDialog::builder().with_alpha(false).build(),
+								set_rgba: &model
+									.settings
+									.color
+									.as_deref()
+									.and_then(|color| gdk::RGBA::parse(color).ok())
+									.unwrap_or(gdk::RGBA::BLUE),
+								connect_rgba_notify[sender] => move |button| {
+									let rgba = button.rgba();
+									sender.input(ListDialogInput::SetColor(Some(format!(
+										"#{:02x}{:02x}{:02x}",
+										(rgba.red() * 255.0).round() as u8,
+										(rgba.green() * 255.0).round() as u8,
+										(rgba.blue() * 255.0).round() as u8,
+									))));
+								},
+							},
+							add_suffix = &gtk::Button {
+								#[watch]
+								set_visible: model.settings.color.is_some(),
+								set_valign: gtk::Align::Center,
+								set_css_classes: &["flat"],
+								set_icon_name: icon_name::SMALL_X,
+								set_tooltip: fl!("clear-color"),
+								connect_clicked => ListDialogInput::SetColor(None),
+							},
+						},
+						adw::ComboRow {
+							set_title: fl!("sort-order"),
+							set_model: Some(&gtk::StringList::new(&[
+								fl!("sort-manual"),
+								fl!("sort-title"),
+								fl!("sort-due-date"),
+								fl!("sort-priority"),
+								fl!("sort-creation-date"),
+							])),
+							set_selected: i32::from(model.settings.sort_order) as u32,
+							connect_selected_notify[sender] => move |combo_row| {
+								sender.input(ListDialogInput::SetSortOrder((combo_row.selected() as i32).into()));
+							},
+						},
+						adw::ComboRow {
+							set_title: fl!("group-by"),
+							set_model: Some(&gtk::StringList::new(&[
+								fl!("group-none"),
+								fl!("group-status"),
+								fl!("group-priority"),
+								fl!("group-due-date"),
+							])),
+							set_selected: i32::from(model.settings.grouping) as u32,
+							connect_selected_notify[sender] => move |combo_row| {
+								sender.input(ListDialogInput::SetGrouping((combo_row.selected() as i32).into()));
+							},
+						},
+						adw::SwitchRow {
+							set_title: fl!("show-completed"),
+							set_active: model.settings.show_completed,
+							connect_active_notify[sender] => move |switch| {
+								sender.input(ListDialogInput::SetShowCompleted(switch.is_active()));
+							},
+						},
+					},
 					gtk::Button {
 						set_css_classes: &["suggested-action"],
 						set_label: model.label.as_str(),
@@ -102,17 +203,23 @@ impl Component for ListDialogComponent {
 		root: &Self::Root,
 		sender: ComponentSender<Self>,
 	) -> ComponentParts<Self> {
-		let model = if let Some(name) = init {
+		let model = if let Some(list) = init {
 			ListDialogComponent {
-				name: gtk::EntryBuffer::new(Some(name)),
+				name: gtk::EntryBuffer::new(Some(list.name)),
 				mode: ListDialogMode::Edit,
-				label: fl!("rename").clone(),
+				label: fl!("save").clone(),
+				description: list.description,
+				settings: list.settings,
+				owned: list.is_owner,
 			}
 		} else {
 			ListDialogComponent {
 				name: gtk::EntryBuffer::new(Some("")),
 				mode: ListDialogMode::New,
 				label: fl!("add-list").clone(),
+				description: String::new(),
+				settings: ListSettings::default(),
+				owned: true,
 			}
 		};
 
@@ -138,12 +245,29 @@ impl Component for ListDialogComponent {
 					},
 					ListDialogMode::Edit => {
 						sender
-							.output(ListDialogOutput::RenameList(name.to_string()))
+							.output(ListDialogOutput::UpdateList(
+								name.to_string(),
+								self.description.clone(),
+								self.settings.clone(),
+							))
 							.unwrap_or_default();
 					},
 				}
 				root.close();
 			},
+			ListDialogInput::SetDescription(description) => {
+				self.description = description
+			},
+			ListDialogInput::SetColor(color) => self.settings.color = color,
+			ListDialogInput::SetSortOrder(sort_order) => {
+				self.settings.sort_order = sort_order
+			},
+			ListDialogInput::SetShowCompleted(show_completed) => {
+				self.settings.show_completed = show_completed
+			},
+			ListDialogInput::SetGrouping(grouping) => {
+				self.settings.grouping = grouping
+			},
 		}
 	}
 }
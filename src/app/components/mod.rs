@@ -1,8 +1,10 @@
 pub mod about_dialog;
 pub mod content;
 pub mod delete;
+pub mod group_dialog;
 pub mod list_dialog;
 pub mod preferences;
+pub mod search;
 pub mod services_sidebar;
 pub mod task_input;
 pub mod task_list_sidebar;
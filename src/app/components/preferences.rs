@@ -1,29 +1,59 @@
 use anyhow::Result;
 use core_done::service::Service;
+use core_done::services::local::database::Database;
+use core_done::time_zone;
 use libset::Config;
+use relm4::gtk::glib::StaticType;
 use relm4::{
 	adw,
 	adw::prelude::{
-		ActionRowExt, AdwWindowExt, BoxExt, GtkWindowExt, OrientableExt,
-		PreferencesGroupExt, PreferencesPageExt, PreferencesRowExt, WidgetExt,
+		ActionRowExt, AdwWindowExt, BoxExt, EditableExt, EntryRowExt, GtkWindowExt,
+		OrientableExt, PreferencesGroupExt, PreferencesPageExt, PreferencesRowExt,
+		WidgetExt,
 	},
 	adw::traits::ComboRowExt,
 	component::{AsyncComponent, AsyncComponentParts},
-	gtk, AsyncComponentSender,
+	factory::FactoryVecDeque,
+	gtk,
+	gtk::gio::prelude::FileExt,
+	gtk::prelude::{ButtonExt, GtkApplicationExt},
+	prelude::DynamicIndex,
+	AsyncComponentSender,
 };
+use relm4_icons::icon_name;
 
 use crate::app::config::preferences::Preferences;
+use crate::app::config::profiles;
 use crate::app::config::{appearance::ColorScheme, info::APP_ID};
+use crate::app::factories::backup::{BackupModel, BackupOutput};
+use crate::app::factories::profile::{ProfileModel, ProfileOutput};
 use crate::fl;
 
 #[derive(Debug)]
 pub struct PreferencesComponentModel {
 	pub preferences: Preferences,
+	backups: FactoryVecDeque<BackupModel>,
+	encrypted: bool,
+	profiles: FactoryVecDeque<ProfileModel>,
 }
 
 #[derive(Debug)]
 pub enum PreferencesComponentInput {
 	SetColorScheme(ColorScheme),
+	SetTrashRetention(Option<i64>),
+	SetTimeZone(Option<String>),
+	SetBackupInterval(Option<i64>),
+	LoadBackups,
+	CreateBackup,
+	RestoreBackup(DynamicIndex),
+	SetEncrypted(bool),
+	LoadProfiles,
+	AddProfile(String),
+	RemoveProfile(DynamicIndex),
+	ChooseProfileLocation(DynamicIndex),
+	SetProfileService(DynamicIndex, Service, bool),
+	/// Another profile is in use, what is shown comes from its database.
+	ProfileSwitched,
 	MicrosoftLogin,
 	MicrosoftLogout,
 }
@@ -31,6 +61,8 @@ pub enum PreferencesComponentInput {
 #[derive(Debug)]
 pub enum PreferencesComponentOutput {
 	ServiceDisabled(Service),
+	DatabaseRestored,
+	ProfilesChanged,
 }
 
 #[relm4::component(pub async)]
@@ -84,6 +116,138 @@ impl AsyncComponent for PreferencesComponentModel {
 									},
 								},
 							},
+							add = &adw::PreferencesGroup {
+								set_title: fl!("date-time"),
+								adw::ComboRow {
+									set_title: fl!("time-zone"),
+									set_subtitle: fl!("time-zone-description"),
+									add_prefix = &gtk::Image {
+										set_icon_name: Some("preferences-system-time-symbolic")
+									},
+									set_enable_search: true,
+									set_expression: Some(&gtk::PropertyExpression::new(
+										gtk::StringObject::static_type(),
+										None::<gtk::Expression>,
+										"string",
+									)),
+									set_model: Some(&time_zones),
+									set_selected: model
+										.preferences
+										.time_zone
+										.as_ref()
+										.and_then(|zone| time_zone::time_zones().iter().position(|name| name == zone))
+										.map_or(0, |position| position as u32 + 1),
+									connect_selected_notify[sender] => move |combo_row| {
+										let time_zone = match combo_row.selected() {
+											0 => None,
+											position => time_zone::time_zones()
+												.get(position as usize - 1)
+												.map(|name| name.to_string()),
+										};
+										sender.input_sender().send(PreferencesComponentInput::SetTimeZone(time_zone)).unwrap();
+									},
+								},
+							},
+							add = &adw::PreferencesGroup {
+								set_title: fl!("trash"),
+								adw::ComboRow {
+									set_title: fl!("trash-retention"),
+									set_subtitle: fl!("trash-retention-description"),
+									add_prefix = &gtk::Image {
+										set_icon_name: Some("user-trash-symbolic")
+									},
+									set_model: Some(&gtk::StringList::new(&[
+										fl!("one-week"),
+										fl!("one-month"),
+										fl!("three-months"),
+										fl!("never"),
+									])),
+									set_selected: match model.preferences.trash_retention_days {
+										Some(7) => 0,
+										Some(30) => 1,
+										Some(90) => 2,
+										_ => 3,
+									},
+									connect_selected_notify[sender] => move |combo_row| {
+										match combo_row.selected() {
+											0 => sender.input_sender().send(PreferencesComponentInput::SetTrashRetention(Some(7))).unwrap(),
+											1 => sender.input_sender().send(PreferencesComponentInput::SetTrashRetention(Some(30))).unwrap(),
+											2 => sender.input_sender().send(PreferencesComponentInput::SetTrashRetention(Some(90))).unwrap(),
+											_ => sender.input_sender().send(PreferencesComponentInput::SetTrashRetention(None)).unwrap(),
+										}
+									},
+								},
+							},
+							add = &adw::PreferencesGroup {
+								set_title: fl!("backups"),
+								adw::ComboRow {
+									set_title: fl!("backup-interval"),
+									set_subtitle: fl!("backup-interval-description"),
+									add_prefix = &gtk::Image {
+										set_icon_name: Some("document-save-symbolic")
+									},
+									set_model: Some(&gtk::StringList::new(&[
+										fl!("daily"),
+										fl!("weekly"),
+										fl!("never"),
+									])),
+									set_selected: match model.preferences.backup_interval_days {
+										Some(1) => 0,
+										Some(7) => 1,
+										_ => 2,
+									},
+									connect_selected_notify[sender] => move |combo_row| {
+										match combo_row.selected() {
+											0 => sender.input_sender().send(PreferencesComponentInput::SetBackupInterval(Some(1))).unwrap(),
+											1 => sender.input_sender().send(PreferencesComponentInput::SetBackupInterval(Some(7))).unwrap(),
+											_ => sender.input_sender().send(PreferencesComponentInput::SetBackupInterval(None)).unwrap(),
+										}
+									},
+								},
+							},
+							#[local_ref]
+							add = backup_list -> adw::PreferencesGroup {
+								set_title: fl!("restore-backup"),
+								set_description: Some(fl!("restore-backup-description")),
+								#[wrap(Some)]
+								set_header_suffix = &gtk::Button {
+									add_css_class: "flat",
+									set_valign: gtk::Align::Center,
+									set_icon_name: icon_name::PLUS,
+									set_tooltip_text: Some(fl!("back-up-now")),
+									connect_clicked => PreferencesComponentInput::CreateBackup,
+								},
+							},
+							add = &adw::PreferencesGroup {
+								set_title: fl!("security"),
+								set_visible: cfg!(feature = "encryption"),
+								adw::SwitchRow {
+									set_title: fl!("encrypt-database"),
+									set_subtitle: fl!("encrypt-database-description"),
+									add_prefix = &gtk::Image {
+										set_icon_name: Some("channel-secure-symbolic")
+									},
+									#[watch]
+									#[block_signal(encrypt_toggled)]
+									set_active: model.encrypted,
+									connect_active_notify[sender] => move |switch| {
+										sender.input(PreferencesComponentInput::SetEncrypted(switch.is_active()));
+									} @encrypt_toggled,
+								},
+							},
+							#[local_ref]
+							add = profile_list -> adw::PreferencesGroup {
+								set_title: fl!("profiles"),
+								set_description: Some(fl!("profiles-description")),
+								adw::EntryRow {
+									set_title: fl!("new-profile"),
+									set_show_apply_button: true,
+									connect_apply[sender] => move |entry| {
+										sender.input(PreferencesComponentInput::AddProfile(entry.text().to_string()));
+										entry.set_text("");
+									},
+								},
+							},
 							add = &adw::PreferencesGroup {
 								set_title: fl!("services"),
 								adw::SwitchRow {
@@ -121,7 +285,40 @@ impl AsyncComponent for PreferencesComponentModel {
 			Preferences::new()
 		};
 
-		let model = Self { preferences };
+		let model = Self {
+			preferences,
+			backups: FactoryVecDeque::builder()
+				.launch(adw::PreferencesGroup::default())
+				.forward(sender.input_sender(), |output| match output {
+					BackupOutput::Restore(index) => {
+						PreferencesComponentInput::RestoreBackup(index)
+					},
+				}),
+			encrypted: database_encrypted(),
+			profiles: FactoryVecDeque::builder()
+				.launch(adw::PreferencesGroup::default())
+				.forward(sender.input_sender(), |output| match output {
+					ProfileOutput::ChooseLocation(index) => {
+						PreferencesComponentInput::ChooseProfileLocation(index)
+					},
+					ProfileOutput::SetServiceEnabled(index, service, enabled) => {
+						PreferencesComponentInput::SetProfileService(
+							index, service, enabled,
+						)
+					},
+					ProfileOutput::Remove(index) => {
+						PreferencesComponentInput::RemoveProfile(index)
+					},
+				}),
+		};
+		let backup_list = model.backups.widget();
+		let profile_list = model.profiles.widget();
+		sender.input(PreferencesComponentInput::LoadProfiles);
+
+		let time_zones = gtk::StringList::new(&[fl!("system-time-zone")]);
+		for name in time_zone::time_zones() {
+			time_zones.append(name);
+		}
 
 		let widgets = view_output!();
 
@@ -159,6 +356,184 @@ impl AsyncComponent for PreferencesComponentModel {
 					tracing::error!("{err}")
 				}
 			},
+			PreferencesComponentInput::SetTrashRetention(days) => {
+				self.preferences.trash_retention_days = days;
+				if let Err(err) = update_preferences(&self.preferences) {
+					tracing::error!("{err}")
+				}
+			},
+			PreferencesComponentInput::SetTimeZone(zone) => {
+				time_zone::set_user_time_zone(zone.as_deref());
+				self.preferences.time_zone = zone;
+				if let Err(err) = update_preferences(&self.preferences) {
+					tracing::error!("{err}")
+				}
+			},
+			PreferencesComponentInput::SetBackupInterval(days) => {
+				self.preferences.backup_interval_days = days;
+				if let Err(err) = update_preferences(&self.preferences) {
+					tracing::error!("{err}")
+				}
+			},
+			PreferencesComponentInput::LoadBackups => {
+				let mut guard = self.backups.guard();
+				guard.clear();
+				match Database::backups() {
+					Ok(backups) => {
+						for backup in backups {
+							guard.push_back(backup);
+						}
+					},
+					Err(err) => tracing::error!("{err}"),
+				}
+			},
+			PreferencesComponentInput::CreateBackup => {
+				match Database::create_backup() {
+					Ok(_) => sender.input(PreferencesComponentInput::LoadBackups),
+					Err(err) => {
+						tracing::error!("An error ocurred: {err}");
+						widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+					},
+				}
+			},
+			PreferencesComponentInput::RestoreBackup(index) => {
+				if let Some(model) = self.backups.get(index.current_index()) {
+					match Database::restore_backup(&model.backup) {
+						Ok(_) => {
+							widgets
+								.overlay
+								.add_toast(adw::Toast::new(fl!("backup-restored")));
+							sender
+								.output(PreferencesComponentOutput::DatabaseRestored)
+								.unwrap_or_default();
+							sender.input(PreferencesComponentInput::LoadBackups);
+						},
+						Err(err) => {
+							tracing::error!("An error ocurred: {err}");
+							widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+						},
+					}
+				}
+			},
+			PreferencesComponentInput::SetEncrypted(encrypted) => {
+				match set_database_encrypted(encrypted) {
+					Ok(_) => {
+						let message = if encrypted {
+							fl!("database-encrypted")
+						} else {
+							fl!("database-decrypted")
+						};
+						widgets.overlay.add_toast(adw::Toast::new(message));
+					},
+					Err(err) => {
+						tracing::error!("An error ocurred: {err}");
+						widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+					},
+				}
+				self.encrypted = database_encrypted();
+			},
+			PreferencesComponentInput::LoadProfiles => {
+				let mut guard = self.profiles.guard();
+				guard.clear();
+				match profiles::load() {
+					Ok(loaded) => {
+						let current = profiles::current().name;
+						for profile in loaded.profiles {
+							let active = profile.name == current;
+							guard.push_back((profile, active));
+						}
+					},
+					Err(err) => tracing::error!("{err}"),
+				}
+			},
+			PreferencesComponentInput::AddProfile(name) => {
+				match profiles::create(&name) {
+					Ok(_) => {
+						sender.input(PreferencesComponentInput::LoadProfiles);
+						sender
+							.output(PreferencesComponentOutput::ProfilesChanged)
+							.unwrap_or_default();
+					},
+					Err(err) => {
+						tracing::error!("An error ocurred: {err}");
+						widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+					},
+				}
+			},
+			PreferencesComponentInput::RemoveProfile(index) => {
+				if let Some(model) = self.profiles.get(index.current_index()) {
+					match profiles::remove(&model.profile.name) {
+						Ok(_) => {
+							self.profiles.guard().remove(index.current_index());
+							sender
+								.output(PreferencesComponentOutput::ProfilesChanged)
+								.unwrap_or_default();
+						},
+						Err(err) => {
+							tracing::error!("An error ocurred: {err}");
+							widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+						},
+					}
+				}
+			},
+			PreferencesComponentInput::ChooseProfileLocation(index) => {
+				if let Some(model) = self.profiles.get(index.current_index()) {
+					let name = model.profile.name.clone();
+					let active = model.active;
+					let dialog = gtk::FileDialog::builder()
+						.title(fl!("choose-location"))
+						.modal(true)
+						.build();
+					let window = relm4::main_adw_application().active_window();
+					if let Ok(folder) = dialog.select_folder_future(window.as_ref()).await
+					{
+						let result = folder
+							.path()
+							.ok_or_else(|| {
+								anyhow::anyhow!("The folder is not on this device")
+							})
+							.and_then(|folder| profiles::set_location(&name, folder));
+						match result {
+							Ok(_) => {
+								sender.input(PreferencesComponentInput::LoadProfiles);
+								if active {
+									sender.input(PreferencesComponentInput::ProfileSwitched);
+									sender
+										.output(PreferencesComponentOutput::DatabaseRestored)
+										.unwrap_or_default();
+								}
+							},
+							Err(err) => {
+								tracing::error!("An error ocurred: {err}");
+								widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+							},
+						}
+					}
+				}
+			},
+			PreferencesComponentInput::SetProfileService(index, service, enabled) => {
+				if let Some(model) = self.profiles.get(index.current_index()) {
+					match profiles::set_service_enabled(
+						&model.profile.name,
+						service,
+						enabled,
+					) {
+						Ok(_) if model.active => sender
+							.output(PreferencesComponentOutput::ServiceDisabled(service))
+							.unwrap_or_default(),
+						Ok(_) => {},
+						Err(err) => {
+							tracing::error!("An error ocurred: {err}");
+							widgets.overlay.add_toast(adw::Toast::new(&err.to_string()));
+						},
+					}
+				}
+			},
+			PreferencesComponentInput::ProfileSwitched => {
+				self.encrypted = database_encrypted();
+				sender.input(PreferencesComponentInput::LoadBackups);
+				sender.input(PreferencesComponentInput::LoadProfiles);
+			},
 			PreferencesComponentInput::MicrosoftLogin => {
 				let service = Service::Microsoft.get_service();
 				match service.login() {
@@ -190,3 +565,36 @@ fn update_preferences(preferences: &Preferences) -> Result<()> {
 		.set_json::<Preferences>("preferences", preferences.to_owned())?;
 	Ok(())
 }
+
+/// Whether the database in use is encrypted.
+#[cfg(feature = "encryption")]
+fn database_encrypted() -> bool {
+	match Database::encrypted() {
+		Ok(encrypted) => encrypted,
+		Err(err) => {
+			tracing::error!("{err}");
+			false
+		},
+	}
+}
+
+#[cfg(feature = "encryption")]
+fn set_database_encrypted(encrypted: bool) -> Result<()> {
+	if encrypted {
+		Database::encrypt()
+	} else {
+		Database::decrypt()
+	}
+}
+
+/// Databases can only be encrypted when the app is built with the
+/// `encryption` feature, the switch is hidden otherwise.
+#[cfg(not(feature = "encryption"))]
+fn database_encrypted() -> bool {
+	false
+}
+
+#[cfg(not(feature = "encryption"))]
+fn set_database_encrypted(_encrypted: bool) -> Result<()> {
+	anyhow::bail!("This build of the app can't encrypt the database.")
+}
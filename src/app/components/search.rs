@@ -0,0 +1,222 @@
+use std::time::Duration;
+
+use core_done::{models::search::SearchResult, service::Service};
+use relm4::{
+	adw,
+	component::{AsyncComponent, AsyncComponentParts},
+	factory::FactoryVecDeque,
+	gtk::{
+		self, glib,
+		prelude::{BoxExt, EditableExt, OrientableExt, WidgetExt},
+	},
+	prelude::DynamicIndex,
+	AsyncComponentSender, RelmWidgetExt,
+};
+use relm4_icons::icon_name;
+
+use crate::{
+	app::{
+		config::profiles,
+		factories::search_result::{SearchResultModel, SearchResultOutput},
+		models::sidebar_list::SidebarList,
+	},
+	fl,
+};
+
+/// How long typing has to pause for before searching.
+const SEARCH_DELAY: Duration = Duration::from_millis(300);
+
+pub struct SearchModel {
+	results: FactoryVecDeque<SearchResultModel>,
+	query: String,
+	/// The search running for the last query, dropped when it changes.
+	handle: Option<glib::JoinHandle<()>>,
+}
+
+#[derive(Debug)]
+pub enum SearchInput {
+	Focus,
+	Search(String),
+	/// The results of a query, along with the service they come from.
+	ShowResults(String, Vec<(SearchResult, Service)>),
+	OpenResult(DynamicIndex),
+}
+
+#[derive(Debug)]
+pub enum SearchOutput {
+	Open(SidebarList, Service),
+	Close,
+}
+
+#[relm4::component(pub async)]
+impl AsyncComponent for SearchModel {
+	type CommandOutput = ();
+	type Input = SearchInput;
+	type Output = SearchOutput;
+	type Init = ();
+
+	view! {
+		#[root]
+		adw::Clamp {
+			gtk::Box {
+				set_orientation: gtk::Orientation::Vertical,
+				set_spacing: 10,
+				set_margin_all: 10,
+				#[name(entry)]
+				gtk::SearchEntry {
+					set_placeholder_text: Some(fl!("search-placeholder")),
+					connect_search_changed[sender] => move |entry| {
+						sender.input(SearchInput::Search(entry.text().to_string()));
+					},
+					connect_stop_search[sender] => move |_| {
+						sender.output(SearchOutput::Close).unwrap_or_default();
+					},
+				},
+				gtk::Stack {
+					set_transition_duration: 250,
+					set_transition_type: gtk::StackTransitionType::Crossfade,
+					if model.results.is_empty() {
+						gtk::Box {
+							set_vexpand: true,
+							set_hexpand: true,
+							set_orientation: gtk::Orientation::Vertical,
+							set_halign: gtk::Align::Center,
+							set_valign: gtk::Align::Center,
+							set_spacing: 10,
+							gtk::Image {
+								set_icon_name: Some(icon_name::LOUPE),
+								set_pixel_size: 64,
+								set_margin_all: 10,
+							},
+							gtk::Label {
+								set_css_classes: &["title-2"],
+								set_wrap: true,
+								set_wrap_mode: gtk::pango::WrapMode::Word,
+								set_justify: gtk::Justification::Center,
+								#[watch]
+								set_text: if model.query.trim().is_empty() {
+									fl!("search-instructions")
+								} else {
+									fl!("no-results")
+								},
+							},
+						}
+					} else {
+						gtk::ScrolledWindow {
+							set_vexpand: true,
+							set_hexpand: true,
+							#[local_ref]
+							results -> gtk::ListBox {
+								set_css_classes: &["boxed-list"],
+								set_selection_mode: gtk::SelectionMode::None,
+								set_valign: gtk::Align::Start,
+								set_margin_all: 5,
+							},
+						}
+					}
+				}
+			}
+		}
+	}
+
+	async fn init(
+		_init: Self::Init,
+		root: Self::Root,
+		sender: AsyncComponentSender<Self>,
+	) -> AsyncComponentParts<Self> {
+		let model = SearchModel {
+			results: FactoryVecDeque::builder()
+				.launch(gtk::ListBox::default())
+				.forward(sender.input_sender(), |output| match output {
+					SearchResultOutput::Open(index) => SearchInput::OpenResult(index),
+				}),
+			query: String::new(),
+			handle: None,
+		};
+
+		let results = model.results.widget();
+
+		let widgets = view_output!();
+
+		AsyncComponentParts { model, widgets }
+	}
+
+	async fn update_with_view(
+		&mut self,
+		widgets: &mut Self::Widgets,
+		message: Self::Input,
+		sender: AsyncComponentSender<Self>,
+		_root: &Self::Root,
+	) {
+		match message {
+			SearchInput::Focus => {
+				widgets.entry.grab_focus();
+				widgets.entry.select_region(0, -1);
+			},
+			SearchInput::Search(query) => {
+				self.query = query;
+				if let Some(handle) = self.handle.take() {
+					handle.abort();
+				}
+				if self.query.trim().is_empty() {
+					self.results.guard().clear();
+				} else {
+					let query = self.query.clone();
+					let sender = sender.clone();
+					self.handle = Some(relm4::spawn_local(async move {
+						glib::timeout_future(SEARCH_DELAY).await;
+						let searches = Service::list()
+							.into_iter()
+							.filter(|service| profiles::enabled(*service))
+							.map(|service| {
+								let query = query.clone();
+								async move {
+									let mut provider = service.get_service();
+									if !provider.available() {
+										return vec![];
+									}
+									match provider.search(query).await {
+										Ok(results) => results
+											.into_iter()
+											.map(|result| (result, service))
+											.collect(),
+										Err(err) => {
+											tracing::error!("An error ocurred: {err}");
+											vec![]
+										},
+									}
+								}
+							});
+						let results = futures::future::join_all(searches)
+							.await
+							.into_iter()
+							.flatten()
+							.collect();
+						sender.input(SearchInput::ShowResults(query, results));
+					}));
+				}
+			},
+			SearchInput::ShowResults(query, results) => {
+				// Results for a query that has been typed over are dropped.
+				if query == self.query {
+					let mut guard = self.results.guard();
+					guard.clear();
+					for result in results {
+						guard.push_back(result);
+					}
+				}
+			},
+			SearchInput::OpenResult(index) => {
+				if let Some(model) = self.results.get(index.current_index()) {
+					sender
+						.output(SearchOutput::Open(
+							SidebarList::Custom(model.result.list.clone()),
+							model.service,
+						))
+						.unwrap_or_default();
+				}
+			},
+		}
+		self.update_view(widgets, sender)
+	}
+}
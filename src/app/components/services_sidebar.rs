@@ -7,7 +7,10 @@ use relm4::{
 };
 
 use crate::{
-	app::factories::service::{ServiceFactoryModel, ServiceFactoryOutput},
+	app::{
+		config::profiles,
+		factories::service::{ServiceFactoryModel, ServiceFactoryOutput},
+	},
 	fl,
 };
 
@@ -72,7 +75,7 @@ impl AsyncComponent for ServicesSidebarModel {
 			let mut guard = services_factory.guard();
 
 			for service in Service::list() {
-				if service.get_service().available() {
+				if profiles::enabled(service) && service.get_service().available() {
 					guard.push_back(service);
 				}
 			}
@@ -101,7 +104,7 @@ impl AsyncComponent for ServicesSidebarModel {
 				let mut guard = self.services_factory.guard();
 				guard.clear();
 				for service in Service::list() {
-					if service.get_service().available() {
+					if profiles::enabled(service) && service.get_service().available() {
 						guard.push_back(service);
 					}
 				}
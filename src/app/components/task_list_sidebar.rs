@@ -1,6 +1,10 @@
-use core_done::{models::list::List, service::Service};
+use core_done::{
+	models::{group::Group, list::List},
+	service::Service,
+};
 use futures::StreamExt;
 use relm4::{
+	actions::RelmAction,
 	adw,
 	component::{
 		AsyncComponent, AsyncComponentController, AsyncComponentParts,
@@ -8,8 +12,8 @@ use relm4::{
 	},
 	factory::AsyncFactoryVecDeque,
 	gtk::{
-		self,
-		prelude::BoxExt,
+		self, gio,
+		prelude::{BoxExt, Cast},
 		traits::{ButtonExt, GtkWindowExt, OrientableExt, WidgetExt},
 	},
 	prelude::DynamicIndex,
@@ -23,11 +27,17 @@ use crate::{
 		components::{
 			list_dialog::ListDialogOutput, services_sidebar::ServicesSidebarOutput,
 		},
-		factories::task_list::{
-			TaskListFactoryInit, TaskListFactoryModel, TaskListFactoryOutput,
+		config::profiles,
+		factories::{
+			list_group::{
+				ListGroupInit, ListGroupInput, ListGroupModel, ListGroupOutput,
+			},
+			task_list::{
+				TaskListFactoryInit, TaskListFactoryModel, TaskListFactoryOutput,
+			},
 		},
 		models::sidebar_list::SidebarList,
-		AboutAction, PreferencesAction, QuitAction, ShortcutsAction,
+		AboutAction, PreferencesAction, ProfileAction, QuitAction, ShortcutsAction,
 	},
 	fl,
 };
@@ -41,8 +51,13 @@ pub struct TaskListSidebarModel {
 	service: Service,
 	state: TaskListSidebarStatus,
 	task_list_factory: AsyncFactoryVecDeque<TaskListFactoryModel>,
+	group_factory: AsyncFactoryVecDeque<ListGroupModel>,
+	/// The ids of the groups in `group_factory`, in the same order.
+	group_ids: Vec<String>,
 	list_entry: Controller<ListDialogComponent>,
 	services_sidebar_controller: AsyncController<ServicesSidebarModel>,
+	/// The section of the main menu that switches between profiles.
+	profiles_menu: gio::Menu,
 	handle: Option<JoinHandle<()>>,
 }
 
@@ -54,10 +69,16 @@ pub enum TaskListSidebarInput {
 	AddTaskListToSidebar(String),
 	ServiceSelected(Service),
 	ServiceDisabled(Service),
+	LoadGroups(Vec<Group>),
 	SelectList(SidebarList),
+	SelectGroupedList(DynamicIndex, SidebarList),
 	DeleteTaskList(DynamicIndex),
+	GroupedListDeleted,
 	SetStatus(TaskListSidebarStatus),
 	ReloadSidebar(Service),
+	ListUpdated(SidebarList),
+	LoadProfiles,
+	ProfileSwitched,
 }
 
 #[derive(Debug)]
@@ -65,6 +86,7 @@ pub enum TaskListSidebarOutput {
 	SelectList(SidebarList, Service),
 	ServiceDisabled(Service),
 	CleanContent,
+	ListUpdated(SidebarList),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -106,6 +128,7 @@ impl SimpleAsyncComponent for TaskListSidebarModel {
 					set_valign: gtk::Align::Center,
 					connect_clicked => TaskListSidebarInput::OpenNewTaskListDialog
 				},
+				#[name(menu_button)]
 				pack_end = &gtk::MenuButton {
 					set_tooltip: fl!("menu"),
 					set_valign: gtk::Align::Center,
@@ -161,11 +184,18 @@ impl SimpleAsyncComponent for TaskListSidebarModel {
 					TaskListSidebarStatus::Loaded => {
 						gtk::ScrolledWindow {
 							set_vexpand: true,
-							#[local_ref]
-							task_list_widget -> gtk::ListBox {
+							gtk::Box {
+								set_orientation: gtk::Orientation::Vertical,
 								set_margin_all: 5,
-								set_css_classes: &["navigation-sidebar"],
-							},
+								#[local_ref]
+								group_widget -> gtk::Box {
+									set_orientation: gtk::Orientation::Vertical,
+								},
+								#[local_ref]
+								task_list_widget -> gtk::ListBox {
+									set_css_classes: &["navigation-sidebar"],
+								},
+							}
 						}
 					}
 				}
@@ -195,14 +225,35 @@ impl SimpleAsyncComponent for TaskListSidebarModel {
 					TaskListFactoryOutput::DeleteTaskList(index) => {
 						TaskListSidebarInput::DeleteTaskList(index)
 					},
+					TaskListFactoryOutput::GroupChanged => {
+						TaskListSidebarInput::LoadTaskLists
+					},
+					TaskListFactoryOutput::ListUpdated(list) => {
+						TaskListSidebarInput::ListUpdated(list)
+					},
 				}),
+			group_factory: AsyncFactoryVecDeque::builder()
+				.launch(gtk::Box::default())
+				.forward(sender.input_sender(), |output| match output {
+					ListGroupOutput::Select(index, list) => {
+						TaskListSidebarInput::SelectGroupedList(index, list)
+					},
+					ListGroupOutput::ListDeleted => {
+						TaskListSidebarInput::GroupedListDeleted
+					},
+					ListGroupOutput::Reload => TaskListSidebarInput::LoadTaskLists,
+					ListGroupOutput::ListUpdated(list) => {
+						TaskListSidebarInput::ListUpdated(list)
+					},
+				}),
+			group_ids: vec![],
 			list_entry: ListDialogComponent::builder().launch(None).forward(
 				sender.input_sender(),
 				|message| match message {
 					ListDialogOutput::AddTaskListToSidebar(name) => {
 						TaskListSidebarInput::AddTaskListToSidebar(name)
 					},
-					ListDialogOutput::RenameList(_) => todo!(),
+					ListDialogOutput::UpdateList(..) => unreachable!(),
 				},
 			),
 			services_sidebar_controller: ServicesSidebarModel::builder()
@@ -215,11 +266,22 @@ impl SimpleAsyncComponent for TaskListSidebarModel {
 						TaskListSidebarInput::ServiceDisabled(service)
 					},
 				}),
+			profiles_menu: gio::Menu::new(),
 			handle: None,
 		};
 		sender.input(TaskListSidebarInput::LoadTaskLists);
+		sender.input(TaskListSidebarInput::LoadProfiles);
 		let task_list_widget = model.task_list_factory.widget();
+		let group_widget = model.group_factory.widget();
 		let widgets = view_output!();
+		if let Some(menu) = widgets
+			.menu_button
+			.menu_model()
+			.and_then(|menu| menu.downcast::<gio::Menu>().ok())
+		{
+			menu
+				.prepend_section(Some(fl!("profiles").as_str()), &model.profiles_menu);
+		}
 		AsyncComponentParts { model, widgets }
 	}
 
@@ -254,6 +316,34 @@ impl SimpleAsyncComponent for TaskListSidebarModel {
 				.sender()
 				.send(ServicesSidebarInput::ReloadSidebar(service))
 				.unwrap_or_default(),
+			TaskListSidebarInput::ProfileSwitched => {
+				// The selected service goes back to the smart lists, it might not be
+				// in the profile.
+				self
+					.services_sidebar_controller
+					.sender()
+					.send(ServicesSidebarInput::ReloadSidebar(self.service))
+					.unwrap_or_default();
+				sender.input(TaskListSidebarInput::LoadProfiles);
+			},
+			TaskListSidebarInput::LoadProfiles => {
+				self.profiles_menu.remove_all();
+				match profiles::load() {
+					// A single profile has nothing to switch to.
+					Ok(loaded) if loaded.profiles.len() > 1 => {
+						for profile in loaded.profiles {
+							self.profiles_menu.append_item(
+								&RelmAction::<ProfileAction>::to_menu_item_with_target_value(
+									&profile.name,
+									&profile.name,
+								),
+							);
+						}
+					},
+					Ok(_) => {},
+					Err(err) => tracing::error!("{err}"),
+				}
+			},
 			TaskListSidebarInput::OpenNewTaskListDialog => {
 				let list_entry = self.list_entry.widget();
 				list_entry.present();
@@ -277,24 +367,53 @@ impl SimpleAsyncComponent for TaskListSidebarModel {
 					.unwrap_or_default()
 			},
 			TaskListSidebarInput::LoadTaskList(list) => {
-				let mut guard = self.task_list_factory.guard();
-				guard.push_back(TaskListFactoryInit::new(
-					self.service,
-					SidebarList::Custom(list),
-				));
+				let group = list
+					.group
+					.as_ref()
+					.and_then(|id| self.group_ids.iter().position(|group| group == id));
+				match group {
+					Some(index) => self
+						.group_factory
+						.send(index, ListGroupInput::AddList(list)),
+					None => {
+						self
+							.task_list_factory
+							.guard()
+							.push_back(TaskListFactoryInit::new(
+								self.service,
+								SidebarList::Custom(list),
+							));
+					},
+				}
 				self.state = TaskListSidebarStatus::Loaded;
 			},
+			TaskListSidebarInput::LoadGroups(groups) => {
+				let mut guard = self.group_factory.guard();
+				guard.clear();
+				self.group_ids = groups.iter().map(|group| group.id.clone()).collect();
+				for group in groups {
+					guard.push_back(ListGroupInit::new(self.service, group, vec![]));
+				}
+			},
 			TaskListSidebarInput::SetStatus(status) => {
 				self.state = status;
 			},
 			TaskListSidebarInput::LoadTaskLists => {
 				let mut guard = self.task_list_factory.guard();
 				guard.clear();
+				self.group_factory.guard().clear();
+				self.group_ids.clear();
 
 				let mut service = self.service.get_service();
 				if service.stream_support() {
 					let sender_clone = sender.clone();
 					self.handle = Some(tokio::spawn(async move {
+						match service.read_groups().await {
+							Ok(groups) => {
+								sender_clone.input(TaskListSidebarInput::LoadGroups(groups))
+							},
+							Err(err) => tracing::error!("{err}"),
+						}
 						match service.get_lists().await {
 							Ok(mut stream) => {
 								let first = stream.next().await;
@@ -322,23 +441,85 @@ impl SimpleAsyncComponent for TaskListSidebarModel {
 							));
 						}
 					} else {
+						let groups = match service.read_groups().await {
+							Ok(groups) => groups,
+							Err(err) => {
+								tracing::error!("An error ocurred: {err}");
+								vec![]
+							},
+						};
+						let mut grouped_lists: Vec<Vec<List>> = vec![vec![]; groups.len()];
 						for list in service.read_lists().await.unwrap() {
-							guard.push_back(TaskListFactoryInit::new(
+							match list
+								.group
+								.as_ref()
+								.and_then(|id| groups.iter().position(|group| &group.id == id))
+							{
+								Some(index) => grouped_lists[index].push(list),
+								None => guard.push_back(TaskListFactoryInit::new(
+									self.service,
+									SidebarList::Custom(list),
+								)),
+							}
+						}
+						self.group_ids =
+							groups.iter().map(|group| group.id.clone()).collect();
+						let mut group_guard = self.group_factory.guard();
+						for (group, lists) in groups.into_iter().zip(grouped_lists) {
+							group_guard.push_back(ListGroupInit::new(
 								self.service,
-								SidebarList::Custom(list),
+								group,
+								lists,
 							));
 						}
+						match service.read_tags().await {
+							Ok(tags) => {
+								for tag in tags {
+									guard.push_back(TaskListFactoryInit::new(
+										self.service,
+										SidebarList::Tag(tag),
+									));
+								}
+							},
+							Err(err) => tracing::error!("An error ocurred: {err}"),
+						}
+						guard.push_back(TaskListFactoryInit::new(
+							self.service,
+							SidebarList::Trash,
+						));
 					}
-					if guard.is_empty() {
+					if guard.is_empty() && self.group_ids.is_empty() {
 						self.state = TaskListSidebarStatus::Empty;
 					} else {
 						self.state = TaskListSidebarStatus::Loaded;
 					}
 				}
 			},
-			TaskListSidebarInput::SelectList(list) => sender
-				.output(TaskListSidebarOutput::SelectList(list, self.service))
-				.unwrap(),
+			TaskListSidebarInput::SelectList(list) => {
+				for index in 0..self.group_ids.len() {
+					self.group_factory.send(index, ListGroupInput::Unselect);
+				}
+				sender
+					.output(TaskListSidebarOutput::SelectList(list, self.service))
+					.unwrap()
+			},
+			TaskListSidebarInput::SelectGroupedList(group_index, list) => {
+				self.task_list_factory.widget().unselect_all();
+				for index in 0..self.group_ids.len() {
+					if index != group_index.current_index() {
+						self.group_factory.send(index, ListGroupInput::Unselect);
+					}
+				}
+				sender
+					.output(TaskListSidebarOutput::SelectList(list, self.service))
+					.unwrap()
+			},
+			TaskListSidebarInput::ListUpdated(list) => sender
+				.output(TaskListSidebarOutput::ListUpdated(list))
+				.unwrap_or_default(),
+			TaskListSidebarInput::GroupedListDeleted => sender
+				.output(TaskListSidebarOutput::CleanContent)
+				.unwrap_or_default(),
 			TaskListSidebarInput::DeleteTaskList(index) => {
 				self.task_list_factory.guard().remove(index.current_index());
 				sender
@@ -4,6 +4,7 @@ pub mod gettext;
 pub mod info;
 pub mod localization;
 pub mod preferences;
+pub mod profiles;
 pub mod resources;
 pub mod settings;
 pub mod setup;
@@ -5,12 +5,32 @@ use super::appearance::ColorScheme;
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Preferences {
 	pub color_scheme: ColorScheme,
+	/// Days a deleted item stays in the trash, `None` keeps it until removed.
+	#[serde(default = "default_trash_retention")]
+	pub trash_retention_days: Option<i64>,
+	/// IANA name of the zone dates are shown in, `None` follows the system.
+	#[serde(default)]
+	pub time_zone: Option<String>,
+	/// Days between automatic snapshots of the database, `None` turns them off.
+	#[serde(default = "default_backup_interval")]
+	pub backup_interval_days: Option<i64>,
 }
 
 impl Preferences {
 	pub fn new() -> Self {
 		Self {
 			color_scheme: ColorScheme::Default,
+			trash_retention_days: default_trash_retention(),
+			time_zone: None,
+			backup_interval_days: default_backup_interval(),
 		}
 	}
 }
+
+fn default_trash_retention() -> Option<i64> {
+	Some(30)
+}
+
+fn default_backup_interval() -> Option<i64> {
+	Some(1)
+}
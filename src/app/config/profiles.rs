@@ -0,0 +1,238 @@
+use std::{path::PathBuf, sync::RwLock};
+
+use anyhow::{anyhow, Result};
+use core_done::{
+	service::Service,
+	services::local::database::{Database, DATABASE_NAME},
+};
+use libset::{Config, FileType};
+use serde::{Deserialize, Serialize};
+
+use super::{info::APP_ID, settings};
+
+/// The profile every installation starts with.
+pub const DEFAULT_PROFILE: &str = "Default";
+
+/// The profile in use, set once on startup and when switching.
+static CURRENT: RwLock<Option<Profile>> = RwLock::new(None);
+
+/// A separate set of tasks, with its own database and services.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Profile {
+	pub name: String,
+	/// Where the database is kept, `None` uses the app data folder.
+	#[serde(default)]
+	pub database_path: Option<PathBuf>,
+	/// The services shown while the profile is in use.
+	#[serde(default = "Service::list")]
+	pub services: Vec<Service>,
+}
+
+impl Profile {
+	fn new(name: &str, database_path: Option<PathBuf>) -> Self {
+		Self {
+			name: name.to_string(),
+			database_path,
+			services: Service::list(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Profiles {
+	/// The profile the app opens with.
+	pub current: String,
+	pub profiles: Vec<Profile>,
+}
+
+impl Default for Profiles {
+	fn default() -> Self {
+		Self {
+			current: DEFAULT_PROFILE.to_string(),
+			profiles: vec![Profile::new(DEFAULT_PROFILE, None)],
+		}
+	}
+}
+
+impl Profiles {
+	pub fn get(&self, name: &str) -> Option<&Profile> {
+		self.profiles.iter().find(|profile| profile.name == name)
+	}
+
+	fn get_mut(&mut self, name: &str) -> Option<&mut Profile> {
+		self
+			.profiles
+			.iter_mut()
+			.find(|profile| profile.name == name)
+	}
+}
+
+pub fn load() -> Result<Profiles> {
+	let config = Config::new(APP_ID, 1, None)?;
+	if config.path("profiles", FileType::Json)?.exists() {
+		Ok(config.get_json("profiles")?)
+	} else {
+		Ok(Profiles::default())
+	}
+}
+
+fn save(profiles: &Profiles) -> Result<()> {
+	Config::new(APP_ID, 1, None)?.set_json("profiles", profiles)?;
+	Ok(())
+}
+
+/// The profile in use.
+pub fn current() -> Profile {
+	CURRENT
+		.read()
+		.unwrap()
+		.clone()
+		.unwrap_or_else(|| Profile::new(DEFAULT_PROFILE, None))
+}
+
+/// Whether a service is shown in the profile in use, smart lists always are.
+pub fn enabled(service: Service) -> bool {
+	service == Service::Smart || current().services.contains(&service)
+}
+
+/// Points the app at the database of a profile, `None` picks the one it was
+/// last used with. A profile that doesn't exist yet is created.
+pub fn activate(name: Option<&str>) -> Result<Profile> {
+	let mut profiles = load()?;
+	let name = name.unwrap_or(&profiles.current).to_string();
+	let profile = match profiles.get(&name) {
+		Some(profile) => profile.clone(),
+		None => {
+			let profile = Profile::new(&name, Some(new_database_path(&name)?));
+			profiles.profiles.push(profile.clone());
+			save(&profiles)?;
+			profile
+		},
+	};
+	Database::set_database_path(profile.database_path.clone());
+	*CURRENT.write().unwrap() = Some(profile.clone());
+	Ok(profile)
+}
+
+/// Opens another profile and keeps it for the next start, the previous one
+/// stays in use if its database can't be opened.
+pub fn switch(name: &str) -> Result<()> {
+	let previous = current();
+	activate(Some(name))?;
+	if let Err(err) = settings::init_database() {
+		activate(Some(&previous.name))?;
+		return Err(err);
+	}
+	let mut profiles = load()?;
+	profiles.current = name.to_string();
+	save(&profiles)
+}
+
+pub fn create(name: &str) -> Result<()> {
+	let name = name.trim();
+	if name.is_empty() {
+		return Err(anyhow!("The profile needs a name"));
+	}
+	let mut profiles = load()?;
+	if profiles.get(name).is_some() {
+		return Err(anyhow!("There is already a profile named {name}"));
+	}
+	profiles
+		.profiles
+		.push(Profile::new(name, Some(new_database_path(name)?)));
+	save(&profiles)
+}
+
+/// Forgets a profile, its database is left where it is.
+pub fn remove(name: &str) -> Result<()> {
+	if current().name == name {
+		return Err(anyhow!("The profile in use can't be removed"));
+	}
+	let mut profiles = load()?;
+	profiles.profiles.retain(|profile| profile.name != name);
+	save(&profiles)
+}
+
+pub fn set_service_enabled(
+	name: &str,
+	service: Service,
+	enabled: bool,
+) -> Result<()> {
+	let mut profiles = load()?;
+	let profile = profiles
+		.get_mut(name)
+		.ok_or_else(|| anyhow!("The profile {name} doesn't exist"))?;
+	profile.services.retain(|enabled| *enabled != service);
+	if enabled {
+		profile.services.push(service);
+	}
+	let profile = profile.clone();
+	save(&profiles)?;
+	if current().name == name {
+		*CURRENT.write().unwrap() = Some(profile);
+	}
+	Ok(())
+}
+
+/// Keeps the database of a profile in another folder. A database that is
+/// already there is used as it is, otherwise the current one is copied over.
+pub fn set_location(name: &str, folder: PathBuf) -> Result<()> {
+	let mut profiles = load()?;
+	let database_path = folder.join(DATABASE_NAME);
+	if let Some(other) = profiles.profiles.iter().find(|profile| {
+		profile.name != name
+			&& profile.database_path.as_ref() == Some(&database_path)
+	}) {
+		return Err(anyhow!(
+			"The profile {} already keeps its database there",
+			other.name
+		));
+	}
+	let profile = profiles
+		.get_mut(name)
+		.ok_or_else(|| anyhow!("The profile {name} doesn't exist"))?;
+	if !database_path.exists() {
+		let previous = profile.database_path.clone();
+		let exists = match &previous {
+			Some(path) => path.exists(),
+			None => Database::default_database_path()?.exists(),
+		};
+		if exists {
+			Database::copy_database(previous.as_deref(), &database_path)?;
+		}
+	}
+	profile.database_path = Some(database_path);
+	save(&profiles)?;
+	if current().name == name {
+		activate(Some(name))?;
+		settings::init_database()?;
+	}
+	Ok(())
+}
+
+/// New profiles get a folder of their own next to the default database.
+fn new_database_path(name: &str) -> Result<PathBuf> {
+	let folder_name: String = name
+		.chars()
+		.map(|c| {
+			if c.is_alphanumeric() {
+				c.to_ascii_lowercase()
+			} else {
+				'-'
+			}
+		})
+		.collect();
+	let profiles_folder = Database::default_database_path()?
+		.parent()
+		.map(|folder| folder.join("profiles"))
+		.ok_or_else(|| anyhow!("The database is not in a folder"))?;
+	let mut folder = profiles_folder.join(&folder_name);
+	let mut suffix = 1;
+	while folder.exists() {
+		suffix += 1;
+		folder = profiles_folder.join(format!("{folder_name}-{suffix}"));
+	}
+	// The folder is taken right away, so that the next profile gets another.
+	std::fs::create_dir_all(&folder)?;
+	Ok(folder.join(DATABASE_NAME))
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
 use core_done::services::local::database::{Database, DATABASE_NAME};
 use libset::{Config, FileType};
@@ -14,10 +16,17 @@ pub(crate) fn init() -> Result<()> {
 		.join("done")
 		.join("dev.edfloreshz.Done.db");
 
+	// The database of older versions is copied over and kept under another
+	// name, a database that already has data is backed up before.
 	if previous_database_path.exists() {
-		let db = std::fs::read(&previous_database_path)?;
-		std::fs::write(&database_path, db)?;
-		std::fs::remove_dir_all(previous_database_path.parent().unwrap())?;
+		if database_path.exists() && std::fs::metadata(&database_path)?.len() > 0 {
+			Database::create_backup()?;
+		}
+		std::fs::copy(&previous_database_path, &database_path)?;
+		std::fs::rename(
+			&previous_database_path,
+			previous_database_path.with_extension("db.migrated"),
+		)?;
 	}
 
 	if !config.path("preferences", FileType::Json)?.exists() {
@@ -27,12 +36,46 @@ pub(crate) fn init() -> Result<()> {
 		database.set_plain(DATABASE_NAME, String::new())?;
 	}
 
-	Database::ensure_migrations_up_to_date()?;
+	init_database()
+}
 
-	Ok(())
+/// Creates the database of the profile in use if it doesn't exist yet and
+/// brings its schema up to date.
+pub(crate) fn init_database() -> Result<()> {
+	let database_path = Database::database_path()?;
+	if !database_path.exists() {
+		if let Some(folder) = database_path.parent() {
+			std::fs::create_dir_all(folder)?;
+		}
+		std::fs::File::create(&database_path)?;
+	}
+	Database::ensure_migrations_up_to_date()
 }
 
+/// Starts over with the default preferences and an empty database. The
+/// database is backed up first and the backups are left in place.
 pub(crate) fn refresh() -> Result<()> {
-	Config::new(APP_ID, 1, None)?.clean()?;
+	let database_path = Database::database_path()?;
+	if database_path.exists() {
+		if let Err(err) = Database::create_backup() {
+			tracing::error!("Failed to back up the database: {err}");
+		}
+	}
+	Database::close();
+	for suffix in ["", "-wal", "-shm"] {
+		let mut path = database_path.clone().into_os_string();
+		path.push(suffix);
+		remove_if_exists(path.into())?;
+	}
+	remove_if_exists(
+		Config::new(APP_ID, 1, None)?.path("preferences", FileType::Json)?,
+	)?;
 	init()
 }
+
+fn remove_if_exists(path: PathBuf) -> Result<()> {
+	match std::fs::remove_file(path) {
+		Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+		_ => Ok(()),
+	}
+}
@@ -1,6 +1,14 @@
 use super::appearance;
-use super::{actions, gettext, localization, resources, settings};
+use super::{
+	actions, gettext, info::APP_ID, localization, preferences::Preferences,
+	resources, settings,
+};
 use anyhow::Result;
+use chrono::{Duration, Utc};
+use core_done::service::Service;
+use core_done::services::local::database::Database;
+use core_done::time_zone;
+use libset::Config;
 use relm4::gtk::gio::ApplicationFlags;
 use relm4::gtk::prelude::{ApplicationExt, ApplicationExtManual};
 use relm4::{gtk, main_adw_application};
@@ -29,9 +37,42 @@ pub fn connect_signals() {
 
 pub fn init_services() -> Result<()> {
 	settings::init()?;
+	apply_time_zone()?;
 	appearance::init()
 }
 
+/// Shows dates in the time zone picked in the preferences.
+fn apply_time_zone() -> Result<()> {
+	let preferences: Preferences =
+		Config::new(APP_ID, 1, None)?.get_json("preferences")?;
+	time_zone::set_user_time_zone(preferences.time_zone.as_deref());
+	Ok(())
+}
+
+/// Removes what has been in the trash longer than the configured retention.
+pub async fn purge_trash() -> Result<()> {
+	let preferences: Preferences =
+		Config::new(APP_ID, 1, None)?.get_json("preferences")?;
+	if let Some(days) = preferences.trash_retention_days {
+		Service::Computer
+			.get_service()
+			.purge_deleted(Utc::now() - Duration::days(days))
+			.await?;
+	}
+	Ok(())
+}
+
+/// Takes a snapshot of the database if the last one is older than the
+/// configured interval.
+pub fn backup_database() -> Result<()> {
+	let preferences: Preferences =
+		Config::new(APP_ID, 1, None)?.get_json("preferences")?;
+	if let Some(days) = preferences.backup_interval_days {
+		Database::backup_if_due(Duration::days(days))?;
+	}
+	Ok(())
+}
+
 pub fn refresh() -> Result<()> {
 	settings::refresh()
 }
@@ -0,0 +1,85 @@
+use adw::prelude::{ActionRowExt, PreferencesRowExt};
+use core_done::models::attachment::Attachment;
+use relm4::{
+	adw,
+	factory::FactoryView,
+	gtk,
+	gtk::prelude::{ButtonExt, WidgetExt},
+	prelude::{DynamicIndex, FactoryComponent},
+	FactorySender, RelmWidgetExt,
+};
+use relm4_icons::icon_name;
+
+use crate::fl;
+
+#[derive(Debug)]
+pub struct AttachmentModel {
+	pub attachment: Attachment,
+}
+
+#[derive(Debug)]
+pub enum AttachmentOutput {
+	Open(DynamicIndex),
+	Remove(DynamicIndex),
+}
+
+#[relm4::factory(pub)]
+impl FactoryComponent for AttachmentModel {
+	type ParentWidget = adw::PreferencesGroup;
+	type Input = ();
+	type Output = AttachmentOutput;
+	type Init = Attachment;
+	type CommandOutput = ();
+
+	view! {
+		#[root]
+		adw::ActionRow {
+			set_title: self.attachment.name.as_str(),
+			set_subtitle: &glib::format_size(self.attachment.size as u64),
+			set_activatable: true,
+			connect_activated[sender, index] => move |_| {
+				sender.output(AttachmentOutput::Open(index.clone())).unwrap_or_default();
+			},
+			add_prefix = &gtk::Image {
+				set_icon_name: Some("mail-attachment-symbolic"),
+			},
+			add_suffix = &gtk::Button {
+				add_css_class: "flat",
+				set_valign: gtk::Align::Center,
+				set_icon_name: "document-open-symbolic",
+				set_tooltip: fl!("open-attachment"),
+				connect_clicked[sender, index] => move |_| {
+					sender.output(AttachmentOutput::Open(index.clone())).unwrap_or_default();
+				}
+			},
+			add_suffix = &gtk::Button {
+				add_css_class: "flat",
+				set_valign: gtk::Align::Center,
+				set_icon_name: icon_name::X_CIRCULAR,
+				set_tooltip: fl!("remove-attachment"),
+				connect_clicked[sender, index] => move |_| {
+					sender.output(AttachmentOutput::Remove(index.clone())).unwrap_or_default();
+				}
+			},
+		}
+	}
+
+	fn init_model(
+		attachment: Self::Init,
+		_index: &DynamicIndex,
+		_sender: FactorySender<Self>,
+	) -> Self {
+		Self { attachment }
+	}
+
+	fn init_widgets(
+		&mut self,
+		index: &DynamicIndex,
+		root: &Self::Root,
+		_returned_widget: &<Self::ParentWidget as FactoryView>::ReturnedWidget,
+		sender: FactorySender<Self>,
+	) -> Self::Widgets {
+		let widgets = view_output!();
+		widgets
+	}
+}
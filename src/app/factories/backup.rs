@@ -0,0 +1,73 @@
+use adw::prelude::{ActionRowExt, PreferencesRowExt};
+use core_done::{services::local::database::backup::Backup, time_zone};
+use relm4::{
+	adw,
+	factory::FactoryView,
+	gtk,
+	gtk::prelude::{ButtonExt, WidgetExt},
+	prelude::{DynamicIndex, FactoryComponent},
+	FactorySender, RelmWidgetExt,
+};
+use relm4_icons::icon_name;
+
+use crate::fl;
+
+#[derive(Debug)]
+pub struct BackupModel {
+	pub backup: Backup,
+}
+
+#[derive(Debug)]
+pub enum BackupOutput {
+	Restore(DynamicIndex),
+}
+
+#[relm4::factory(pub)]
+impl FactoryComponent for BackupModel {
+	type ParentWidget = adw::PreferencesGroup;
+	type Input = ();
+	type Output = BackupOutput;
+	type Init = Backup;
+	type CommandOutput = ();
+
+	view! {
+		#[root]
+		adw::ActionRow {
+			set_title: &time_zone::to_local(self.backup.created)
+				.format("%d/%m/%Y %H:%M")
+				.to_string(),
+			set_subtitle: &gtk::glib::format_size(self.backup.size),
+			add_prefix = &gtk::Image {
+				set_icon_name: Some("document-save-symbolic"),
+			},
+			add_suffix = &gtk::Button {
+				add_css_class: "flat",
+				set_valign: gtk::Align::Center,
+				set_icon_name: icon_name::UPDATE,
+				set_tooltip: fl!("restore"),
+				connect_clicked[sender, index] => move |_| {
+					sender.output(BackupOutput::Restore(index.clone())).unwrap_or_default();
+				}
+			},
+		}
+	}
+
+	fn init_model(
+		backup: Self::Init,
+		_index: &DynamicIndex,
+		_sender: FactorySender<Self>,
+	) -> Self {
+		Self { backup }
+	}
+
+	fn init_widgets(
+		&mut self,
+		index: &DynamicIndex,
+		root: &Self::Root,
+		_returned_widget: &<Self::ParentWidget as FactoryView>::ReturnedWidget,
+		sender: FactorySender<Self>,
+	) -> Self::Widgets {
+		let widgets = view_output!();
+		widgets
+	}
+}
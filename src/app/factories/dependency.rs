@@ -0,0 +1,88 @@
+use adw::prelude::{ActionRowExt, PreferencesRowExt};
+use core_done::models::dependency::Dependency;
+use relm4::{
+	adw,
+	factory::FactoryView,
+	gtk,
+	gtk::prelude::{ButtonExt, WidgetExt},
+	prelude::{DynamicIndex, FactoryComponent},
+	FactorySender, RelmWidgetExt,
+};
+use relm4_icons::icon_name;
+
+use crate::fl;
+
+#[derive(Debug)]
+pub struct DependencyModel {
+	pub dependency: Dependency,
+}
+
+#[derive(Debug)]
+pub enum DependencyInput {
+	SetCompleted(bool),
+}
+
+#[derive(Debug)]
+pub enum DependencyOutput {
+	Remove(DynamicIndex),
+}
+
+#[relm4::factory(pub)]
+impl FactoryComponent for DependencyModel {
+	type ParentWidget = adw::PreferencesGroup;
+	type Input = DependencyInput;
+	type Output = DependencyOutput;
+	type Init = Dependency;
+	type CommandOutput = ();
+
+	view! {
+		#[root]
+		adw::ActionRow {
+			set_title: self.dependency.title.as_str(),
+			add_prefix = &gtk::Image {
+				#[watch]
+				set_icon_name: Some(if self.dependency.completed {
+					icon_name::CHECK_ROUND_OUTLINE_WHOLE
+				} else {
+					"changes-prevent-symbolic"
+				}),
+			},
+			add_suffix = &gtk::Button {
+				add_css_class: "flat",
+				set_valign: gtk::Align::Center,
+				set_icon_name: icon_name::X_CIRCULAR,
+				set_tooltip: fl!("remove-dependency"),
+				connect_clicked[sender, index] => move |_| {
+					sender.output(DependencyOutput::Remove(index.clone())).unwrap_or_default();
+				}
+			},
+		}
+	}
+
+	fn init_model(
+		dependency: Self::Init,
+		_index: &DynamicIndex,
+		_sender: FactorySender<Self>,
+	) -> Self {
+		Self { dependency }
+	}
+
+	fn init_widgets(
+		&mut self,
+		index: &DynamicIndex,
+		root: &Self::Root,
+		_returned_widget: &<Self::ParentWidget as FactoryView>::ReturnedWidget,
+		sender: FactorySender<Self>,
+	) -> Self::Widgets {
+		let widgets = view_output!();
+		widgets
+	}
+
+	fn update(&mut self, message: Self::Input, _sender: FactorySender<Self>) {
+		match message {
+			DependencyInput::SetCompleted(completed) => {
+				self.dependency.completed = completed
+			},
+		}
+	}
+}
@@ -0,0 +1,82 @@
+use adw::prelude::{ActionRowExt, PreferencesRowExt};
+use core_done::models::linked_resource::LinkedResource;
+use relm4::{
+	adw,
+	factory::FactoryView,
+	gtk,
+	gtk::prelude::{ButtonExt, WidgetExt},
+	prelude::{DynamicIndex, FactoryComponent},
+	FactorySender, RelmWidgetExt,
+};
+use relm4_icons::icon_name;
+
+use crate::fl;
+
+#[derive(Debug)]
+pub struct LinkedResourceModel {
+	pub linked_resource: LinkedResource,
+}
+
+#[derive(Debug)]
+pub enum LinkedResourceOutput {
+	Open(DynamicIndex),
+	Remove(DynamicIndex),
+}
+
+#[relm4::factory(pub)]
+impl FactoryComponent for LinkedResourceModel {
+	type ParentWidget = adw::PreferencesGroup;
+	type Input = ();
+	type Output = LinkedResourceOutput;
+	type Init = LinkedResource;
+	type CommandOutput = ();
+
+	view! {
+		#[root]
+		adw::ActionRow {
+			set_use_markup: false,
+			set_title: self.linked_resource.title(),
+			set_subtitle: self
+				.linked_resource
+				.application_name
+				.as_deref()
+				.unwrap_or(&self.linked_resource.web_url),
+			set_tooltip: &self.linked_resource.web_url,
+			set_activatable: true,
+			connect_activated[sender, index] => move |_| {
+				sender.output(LinkedResourceOutput::Open(index.clone())).unwrap_or_default();
+			},
+			add_prefix = &gtk::Image {
+				set_icon_name: Some("insert-link-symbolic"),
+			},
+			add_suffix = &gtk::Button {
+				add_css_class: "flat",
+				set_valign: gtk::Align::Center,
+				set_icon_name: icon_name::X_CIRCULAR,
+				set_tooltip: fl!("remove-link"),
+				connect_clicked[sender, index] => move |_| {
+					sender.output(LinkedResourceOutput::Remove(index.clone())).unwrap_or_default();
+				}
+			},
+		}
+	}
+
+	fn init_model(
+		linked_resource: Self::Init,
+		_index: &DynamicIndex,
+		_sender: FactorySender<Self>,
+	) -> Self {
+		Self { linked_resource }
+	}
+
+	fn init_widgets(
+		&mut self,
+		index: &DynamicIndex,
+		root: &Self::Root,
+		_returned_widget: &<Self::ParentWidget as FactoryView>::ReturnedWidget,
+		sender: FactorySender<Self>,
+	) -> Self::Widgets {
+		let widgets = view_output!();
+		widgets
+	}
+}
@@ -0,0 +1,220 @@
+use core_done::models::{group::Group, list::List};
+use core_done::service::Service;
+use relm4::factory::{
+	AsyncFactoryComponent, AsyncFactoryVecDeque, DynamicIndex, FactoryView,
+};
+use relm4::gtk::prelude::{
+	BoxExt, ButtonExt, EditableExt, OrientableExt, WidgetExt,
+};
+use relm4::{gtk, AsyncFactorySender, RelmWidgetExt};
+
+use crate::app::models::sidebar_list::SidebarList;
+use crate::fl;
+
+use super::task_list::{
+	TaskListFactoryInit, TaskListFactoryModel, TaskListFactoryOutput,
+};
+
+#[derive(Debug)]
+pub struct ListGroupModel {
+	pub group: Group,
+	pub service: Service,
+	pub index: DynamicIndex,
+	pub lists: AsyncFactoryVecDeque<TaskListFactoryModel>,
+	expanded: bool,
+}
+
+#[derive(Debug, derive_new::new)]
+pub struct ListGroupInit {
+	pub service: Service,
+	pub group: Group,
+	pub lists: Vec<List>,
+}
+
+#[derive(Debug)]
+pub enum ListGroupInput {
+	Toggle,
+	AddList(List),
+	Select(SidebarList),
+	DeleteTaskList(DynamicIndex),
+	Rename(String),
+	Ungroup,
+	Unselect,
+	Reload,
+	ListUpdated(SidebarList),
+}
+
+#[derive(Debug)]
+pub enum ListGroupOutput {
+	Select(DynamicIndex, SidebarList),
+	ListDeleted,
+	Reload,
+	ListUpdated(SidebarList),
+}
+
+#[relm4::factory(pub async)]
+impl AsyncFactoryComponent for ListGroupModel {
+	type ParentWidget = gtk::Box;
+	type CommandOutput = ();
+	type Input = ListGroupInput;
+	type Output = ListGroupOutput;
+	type Init = ListGroupInit;
+	type Widgets = ListGroupWidgets;
+
+	view! {
+		#[root]
+		gtk::Box {
+			set_orientation: gtk::Orientation::Vertical,
+			gtk::Box {
+				set_css_classes: &["toolbar"],
+				gtk::Button {
+					set_css_classes: &["flat", "image-button"],
+					set_valign: gtk::Align::Center,
+					#[watch]
+					set_icon_name: if self.expanded {
+						"pan-down-symbolic"
+					} else {
+						"pan-end-symbolic"
+					},
+					#[watch]
+					set_tooltip: if self.expanded {
+						fl!("collapse-group")
+					} else {
+						fl!("expand-group")
+					},
+					connect_clicked => ListGroupInput::Toggle,
+				},
+				gtk::EditableLabel {
+					add_css_class: "heading",
+					set_hexpand: true,
+					set_text: self.group.name.as_str(),
+					set_tooltip: fl!("rename-group"),
+					connect_editing_notify[sender] => move |label| {
+						if !label.is_editing() {
+							sender.input(ListGroupInput::Rename(label.text().to_string()));
+						}
+					},
+				},
+				gtk::Button {
+					set_css_classes: &["flat", "image-button"],
+					set_valign: gtk::Align::Center,
+					set_icon_name: "edit-clear-symbolic",
+					set_tooltip: fl!("ungroup"),
+					connect_clicked => ListGroupInput::Ungroup,
+				},
+			},
+			gtk::Revealer {
+				#[watch]
+				set_reveal_child: self.expanded,
+				#[local_ref]
+				lists_widget -> gtk::ListBox {
+					set_css_classes: &["navigation-sidebar"],
+				},
+			},
+		}
+	}
+
+	async fn init_model(
+		init: Self::Init,
+		index: &DynamicIndex,
+		sender: AsyncFactorySender<Self>,
+	) -> Self {
+		let mut lists = AsyncFactoryVecDeque::builder()
+			.launch(gtk::ListBox::default())
+			.forward(sender.input_sender(), |output| match output {
+				TaskListFactoryOutput::Select(list) => ListGroupInput::Select(list),
+				TaskListFactoryOutput::DeleteTaskList(index) => {
+					ListGroupInput::DeleteTaskList(index)
+				},
+				TaskListFactoryOutput::GroupChanged => ListGroupInput::Reload,
+				TaskListFactoryOutput::ListUpdated(list) => {
+					ListGroupInput::ListUpdated(list)
+				},
+			});
+		{
+			let mut guard = lists.guard();
+			for list in init.lists {
+				guard.push_back(TaskListFactoryInit::new(
+					init.service,
+					SidebarList::Custom(list),
+				));
+			}
+		}
+		Self {
+			group: init.group,
+			service: init.service,
+			index: index.clone(),
+			lists,
+			expanded: true,
+		}
+	}
+
+	fn init_widgets(
+		&mut self,
+		_index: &DynamicIndex,
+		root: &Self::Root,
+		_returned_widget: &<Self::ParentWidget as FactoryView>::ReturnedWidget,
+		sender: AsyncFactorySender<Self>,
+	) -> Self::Widgets {
+		let lists_widget = self.lists.widget();
+		let widgets = view_output!();
+		widgets
+	}
+
+	async fn update(
+		&mut self,
+		message: Self::Input,
+		sender: AsyncFactorySender<Self>,
+	) {
+		match message {
+			ListGroupInput::Toggle => self.expanded = !self.expanded,
+			ListGroupInput::AddList(list) => {
+				self.lists.guard().push_back(TaskListFactoryInit::new(
+					self.service,
+					SidebarList::Custom(list),
+				));
+			},
+			ListGroupInput::Select(list) => {
+				sender
+					.output(ListGroupOutput::Select(self.index.clone(), list))
+					.unwrap_or_default();
+			},
+			ListGroupInput::DeleteTaskList(index) => {
+				self.lists.guard().remove(index.current_index());
+				sender
+					.output(ListGroupOutput::ListDeleted)
+					.unwrap_or_default();
+			},
+			ListGroupInput::Rename(name) => {
+				let name = name.trim().to_string();
+				if !name.is_empty() && name != self.group.name {
+					let mut group = self.group.clone();
+					group.name = name;
+					let mut service = self.service.get_service();
+					match service.update_group(group.clone()).await {
+						Ok(_) => self.group = group,
+						Err(err) => {
+							tracing::error!("{err}");
+						},
+					}
+				}
+			},
+			ListGroupInput::Ungroup => {
+				let mut service = self.service.get_service();
+				match service.delete_group(self.group.id.clone()).await {
+					Ok(_) => sender.output(ListGroupOutput::Reload).unwrap_or_default(),
+					Err(err) => {
+						tracing::error!("{err}");
+					},
+				}
+			},
+			ListGroupInput::Unselect => self.lists.widget().unselect_all(),
+			ListGroupInput::Reload => {
+				sender.output(ListGroupOutput::Reload).unwrap_or_default()
+			},
+			ListGroupInput::ListUpdated(list) => sender
+				.output(ListGroupOutput::ListUpdated(list))
+				.unwrap_or_default(),
+		}
+	}
+}
@@ -1,4 +1,13 @@
+pub mod attachment;
+pub mod backup;
+pub mod dependency;
+pub mod linked_resource;
+pub mod list_group;
+pub mod profile;
+pub mod search_result;
 pub mod service;
 pub mod sub_tasks;
 pub mod task;
+pub mod task_change;
 pub mod task_list;
+pub mod trash_list;
@@ -0,0 +1,112 @@
+use adw::prelude::{ActionRowExt, ExpanderRowExt, PreferencesRowExt};
+use core_done::service::Service;
+use relm4::{
+	adw,
+	factory::FactoryView,
+	gtk,
+	gtk::prelude::{ButtonExt, WidgetExt},
+	prelude::{DynamicIndex, FactoryComponent},
+	FactorySender, RelmWidgetExt,
+};
+use relm4_icons::icon_name;
+
+use crate::{app::config::profiles::Profile, fl};
+
+#[derive(Debug)]
+pub struct ProfileModel {
+	pub profile: Profile,
+	/// Whether it is the profile in use, which can't be removed.
+	pub active: bool,
+}
+
+#[derive(Debug)]
+pub enum ProfileOutput {
+	ChooseLocation(DynamicIndex),
+	SetServiceEnabled(DynamicIndex, Service, bool),
+	Remove(DynamicIndex),
+}
+
+#[relm4::factory(pub)]
+impl FactoryComponent for ProfileModel {
+	type ParentWidget = adw::PreferencesGroup;
+	type Input = ();
+	type Output = ProfileOutput;
+	type Init = (Profile, bool);
+	type CommandOutput = ();
+
+	view! {
+		#[root]
+		adw::ExpanderRow {
+			set_title: &self.profile.name,
+			set_subtitle: if self.active {
+				fl!("profile-in-use").as_str()
+			} else {
+				""
+			},
+			add_prefix = &gtk::Image {
+				set_icon_name: Some("avatar-default-symbolic"),
+			},
+			add_suffix = &gtk::Button {
+				add_css_class: "flat",
+				set_visible: !self.active,
+				set_valign: gtk::Align::Center,
+				set_icon_name: icon_name::X_CIRCULAR,
+				set_tooltip: fl!("remove-profile"),
+				connect_clicked[sender, index] => move |_| {
+					sender.output(ProfileOutput::Remove(index.clone())).unwrap_or_default();
+				}
+			},
+			add_row = &adw::ActionRow {
+				set_title: fl!("database-location"),
+				set_subtitle: &self
+					.profile
+					.database_path
+					.as_ref()
+					.map(|path| path.display().to_string())
+					.unwrap_or_else(|| fl!("default-location").to_string()),
+				add_suffix = &gtk::Button {
+					add_css_class: "flat",
+					set_valign: gtk::Align::Center,
+					set_icon_name: "folder-open-symbolic",
+					set_tooltip: fl!("choose-location"),
+					connect_clicked[sender, index] => move |_| {
+						sender.output(ProfileOutput::ChooseLocation(index.clone())).unwrap_or_default();
+					}
+				},
+			},
+			add_row = &adw::SwitchRow {
+				set_title: "Microsoft To Do",
+				add_prefix = &gtk::Image {
+					set_resource: Some(Service::Microsoft.icon())
+				},
+				set_active: self.profile.services.contains(&Service::Microsoft),
+				connect_active_notify[sender, index] => move |switch| {
+					sender.output(ProfileOutput::SetServiceEnabled(
+						index.clone(),
+						Service::Microsoft,
+						switch.is_active(),
+					)).unwrap_or_default();
+				}
+			},
+		}
+	}
+
+	fn init_model(
+		(profile, active): Self::Init,
+		_index: &DynamicIndex,
+		_sender: FactorySender<Self>,
+	) -> Self {
+		Self { profile, active }
+	}
+
+	fn init_widgets(
+		&mut self,
+		index: &DynamicIndex,
+		root: &Self::Root,
+		_returned_widget: &<Self::ParentWidget as FactoryView>::ReturnedWidget,
+		sender: FactorySender<Self>,
+	) -> Self::Widgets {
+		let widgets = view_output!();
+		widgets
+	}
+}
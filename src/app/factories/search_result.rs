@@ -0,0 +1,90 @@
+use adw::prelude::{ActionRowExt, PreferencesRowExt};
+use core_done::{models::search::SearchResult, service::Service};
+use relm4::{
+	adw,
+	factory::FactoryView,
+	gtk,
+	gtk::prelude::WidgetExt,
+	prelude::{DynamicIndex, FactoryComponent},
+	FactorySender,
+};
+use relm4_icons::icon_name;
+
+#[derive(Debug)]
+pub struct SearchResultModel {
+	pub result: SearchResult,
+	pub service: Service,
+}
+
+#[derive(Debug)]
+pub enum SearchResultOutput {
+	Open(DynamicIndex),
+}
+
+#[relm4::factory(pub)]
+impl FactoryComponent for SearchResultModel {
+	type ParentWidget = gtk::ListBox;
+	type Input = ();
+	type Output = SearchResultOutput;
+	type Init = (SearchResult, Service);
+	type CommandOutput = ();
+
+	view! {
+		#[root]
+		adw::ActionRow {
+			set_use_markup: false,
+			set_title: &self.title(),
+			set_subtitle: &self.subtitle(),
+			set_activatable: true,
+			connect_activated[sender, index] => move |_| {
+				sender.output(SearchResultOutput::Open(index.clone())).unwrap_or_default();
+			},
+			add_prefix = &gtk::Image {
+				set_visible: self.result.task.is_some(),
+				set_icon_name: Some(icon_name::CHECK_ROUND_OUTLINE2),
+			},
+			add_prefix = &gtk::Label {
+				set_visible: self.result.task.is_none(),
+				set_text: self.result.list.icon.as_deref().unwrap_or_default(),
+			},
+		}
+	}
+
+	fn init_model(
+		(result, service): Self::Init,
+		_index: &DynamicIndex,
+		_sender: FactorySender<Self>,
+	) -> Self {
+		Self { result, service }
+	}
+
+	fn init_widgets(
+		&mut self,
+		index: &DynamicIndex,
+		root: &Self::Root,
+		_returned_widget: &<Self::ParentWidget as FactoryView>::ReturnedWidget,
+		sender: FactorySender<Self>,
+	) -> Self::Widgets {
+		let widgets = view_output!();
+		widgets
+	}
+}
+
+impl SearchResultModel {
+	fn title(&self) -> String {
+		match &self.result.task {
+			Some(task) => task.title.clone(),
+			None => self.result.list.name.clone(),
+		}
+	}
+
+	/// Where the match was found, the list of a task and the service.
+	fn subtitle(&self) -> String {
+		match &self.result.task {
+			Some(_) => {
+				format!("{} · {}", self.result.list.name, self.service.to_string())
+			},
+			None => self.service.to_string(),
+		}
+	}
+}
@@ -1,37 +1,66 @@
-use std::str::FromStr;
-
 use crate::fl;
 use adw::prelude::{
 	ActionRowExt, BoxExt, ExpanderRowExt, OrientableExt, ToggleButtonExt,
 };
-use adw::traits::{EntryRowExt, PreferencesRowExt};
-use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use adw::traits::{
+	ComboRowExt, EntryRowExt, PreferencesGroupExt, PreferencesRowExt,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc};
+use core_done::models::attachment::Attachment;
+use core_done::models::dependency::Dependency;
+use core_done::models::linked_resource::LinkedResource;
 use core_done::models::list::List;
 use core_done::models::priority::Priority;
-use core_done::models::recurrence::Day;
+use core_done::models::recurrence::{
+	Day, Frequency, RangeType, Recurrence, WeekIndex,
+};
 use core_done::models::status::Status;
 use core_done::models::task::Task;
-use glib::Propagation;
+use core_done::models::task_change::TaskChange;
+use core_done::notes;
+use core_done::service::Service;
+use core_done::time_zone;
+use glib::{Propagation, StaticType, ToValue};
 use relm4::factory::{AsyncFactoryComponent, FactoryVecDeque};
 use relm4::factory::{AsyncFactorySender, DynamicIndex, FactoryView};
+use relm4::gtk::gio::{self, prelude::FileExt};
 use relm4::{
 	adw, gtk,
 	gtk::prelude::{
-		ButtonExt, CheckButtonExt, EditableExt, ListBoxRowExt, WidgetExt,
+		ButtonExt, CheckButtonExt, EditableExt, GtkApplicationExt, ListBoxRowExt,
+		ListModelExt, TextBufferExt, TextViewExt, WidgetExt,
 	},
 	RelmWidgetExt,
 };
 use relm4_icons::icon_name;
 
+use super::attachment::{AttachmentModel, AttachmentOutput};
+use super::dependency::{DependencyInput, DependencyModel, DependencyOutput};
+use super::linked_resource::{LinkedResourceModel, LinkedResourceOutput};
 use super::sub_tasks::messages::SubTaskOutput;
 use super::sub_tasks::model::{SubTaskInit, SubTaskModel};
+use super::task_change::{merge_changes, TaskChangeModel};
 
 #[derive(Debug)]
 pub struct TaskModel {
 	pub task: Task,
 	pub sub_tasks: FactoryVecDeque<SubTaskModel>,
+	pub attachments: FactoryVecDeque<AttachmentModel>,
+	pub linked_resources: FactoryVecDeque<LinkedResourceModel>,
+	pub dependencies: FactoryVecDeque<DependencyModel>,
+	/// The changes made to the task, newest first, loaded when the history is
+	/// expanded.
+	pub history: FactoryVecDeque<TaskChangeModel>,
+	/// The Markdown source of the notes while they are being edited.
+	notes_buffer: gtk::TextBuffer,
+	editing_notes: bool,
+	/// The tasks offered to wait for, listed after a placeholder entry in
+	/// `candidate_names`.
+	candidates: Vec<Dependency>,
+	candidate_names: gtk::StringList,
 	pub parent_list: List,
 	pub index: DynamicIndex,
+	timer: Option<glib::SourceId>,
 }
 
 #[derive(derive_new::new)]
@@ -45,30 +74,74 @@ pub enum TaskInput {
 	SetCompleted(bool),
 	ModifyTitle(String),
 	Favorite,
-	SetNotes(Option<String>),
+	EditNotes,
+	SaveNotes,
+	SetTags(Vec<String>),
 	SetPriority(i32),
-	SetStatus(bool),
+	SetStatus(Status),
 	SetToday(bool),
+	SetStartDate(Option<DateTime<Utc>>),
 	SetDueDate(Option<DateTime<Utc>>),
 	SetReminderDate(Option<DateTime<Utc>>),
 	SetReminderHour(u32),
 	SetReminderMinute(u32),
+	SetEstimate(i64),
+	ToggleTimer,
+	Tick,
+	SetRecurrenceFrequency(Option<Frequency>),
+	SetRecurrenceInterval(i32),
 	SetDayInRecurrence((bool, Day)),
+	SetRecurrenceDayOfMonth(i32),
+	SetRecurrenceWeekIndex(WeekIndex),
+	SetRecurrenceMonth(i32),
+	/// The position of the range type in the list of choices.
+	SetRecurrenceRangeType(u32),
+	SetRecurrenceEndDate(Option<NaiveDate>),
+	SetRecurrenceOccurrences(i32),
 	SetDate(DateType, DateDay),
 	UpdateSubTask(DynamicIndex, Task),
 	RemoveSubTask(DynamicIndex),
 	CreateSubTask,
+	AddAttachment,
+	AttachmentAdded(Attachment),
+	OpenAttachment(DynamicIndex),
+	RemoveAttachment(DynamicIndex),
+	AddLink(String),
+	OpenLink(DynamicIndex),
+	RemoveLink(DynamicIndex),
+	ChooseDependency,
+	SetDependencyCandidates(Vec<Dependency>),
+	AddDependency(u32),
+	DependencyAdded(Dependency),
+	RemoveDependency(DynamicIndex),
+	SetDependencyCompleted(String, bool),
+	LoadHistory,
+	SetHistory(Vec<TaskChange>),
 }
 
 #[derive(Debug)]
 pub enum TaskOutput {
 	Remove(DynamicIndex),
-	UpdateTask(Task),
+	Restore(DynamicIndex),
+	Reorder(String, DynamicIndex),
+	/// The task as it was before the change and as it is now.
+	UpdateTask(Task, Task),
+	CreateSubTask(Task, Task),
+	UpdateSubTask(Task, Task),
+	RemoveSubTask(Task, String),
+	CreateAttachment(DynamicIndex, Task, Attachment, Vec<u8>),
+	OpenAttachment(Task, Attachment),
+	RemoveAttachment(Task, String),
+	ChooseDependency(DynamicIndex, Task),
+	AddDependency(DynamicIndex, Task, String),
+	RemoveDependency(Task, String),
+	LoadHistory(DynamicIndex, Task),
 }
 
 #[derive(Debug)]
 pub enum DateType {
 	Reminder,
+	StartDate,
 	DueDate,
 }
 
@@ -81,7 +154,7 @@ pub enum DateDay {
 
 #[relm4::factory(pub async)]
 impl AsyncFactoryComponent for TaskModel {
-	type ParentWidget = adw::PreferencesGroup;
+	type ParentWidget = gtk::ListBox;
 	type CommandOutput = ();
 	type Input = TaskInput;
 	type Output = TaskOutput;
@@ -91,6 +164,23 @@ impl AsyncFactoryComponent for TaskModel {
 	view! {
 		root = adw::ExpanderRow {
 			set_expanded: false,
+			add_controller = gtk::DragSource {
+				set_actions: gtk::gdk::DragAction::MOVE,
+				connect_prepare[task_id = self.task.id.clone()] => move |_, _, _| {
+					Some(gtk::gdk::ContentProvider::for_value(&task_id.to_value()))
+				},
+			},
+			add_controller = gtk::DropTarget::new(String::static_type(), gtk::gdk::DragAction::MOVE) {
+				connect_drop[sender, index] => move |_, value, _, _| {
+					match value.get::<String>() {
+						Ok(task_id) => {
+							sender.output(TaskOutput::Reorder(task_id, index.clone())).unwrap_or_default();
+							true
+						},
+						Err(_) => false,
+					}
+				},
+			},
 			#[watch]
 			set_title: self.task.title.as_str(),
 			#[watch]
@@ -104,6 +194,23 @@ impl AsyncFactoryComponent for TaskModel {
 					sender.input(TaskInput::SetCompleted(checkbox.is_active()));
 				}
 			},
+			add_suffix = &gtk::Button {
+				add_css_class: "flat",
+				set_visible: self.task.deletion_date.is_some(),
+				set_icon_name: icon_name::UPDATE,
+				set_tooltip: fl!("restore"),
+				set_valign: gtk::Align::Center,
+				connect_clicked[sender, index] => move |_| {
+					sender.output(TaskOutput::Restore(index.clone())).unwrap()
+				}
+			},
+			add_suffix = &gtk::Image {
+				add_css_class: "dim-label",
+				set_icon_name: Some("changes-prevent-symbolic"),
+				set_tooltip: fl!("blocked-tooltip"),
+				#[watch]
+				set_visible: self.task.is_blocked(),
+			},
 			#[name(delete)]
 			add_suffix = &gtk::Button {
 				add_css_class: "destructive-action",
@@ -144,34 +251,82 @@ impl AsyncFactoryComponent for TaskModel {
 					sender.input(TaskInput::ModifyTitle(buffer));
 				},
 			},
-			add_row = &adw::EntryRow {
+			add_row = &adw::ExpanderRow {
 				set_title: fl!("notes"),
-				set_show_apply_button: true,
-				set_enable_emoji_completion: true,
-				set_text: self.task.notes.as_deref().unwrap_or(""),
-				connect_changed[sender] => move |entry| {
-					let buffer = entry.text().to_string();
-					if buffer.is_empty() {
-						sender.input(TaskInput::SetNotes(None));
-					} else {
-						sender.input(TaskInput::SetNotes(Some(buffer)));
-					}
+				set_use_markup: false,
+				#[watch]
+				set_subtitle: self
+					.task
+					.notes
+					.as_deref()
+					.and_then(|notes| notes.lines().next())
+					.unwrap_or_default(),
+				add_prefix = &gtk::Image {
+					set_icon_name: Some(icon_name::EDITOR)
+				},
+				add_row = &gtk::Box {
+					set_orientation: gtk::Orientation::Vertical,
+					set_spacing: 10,
+					set_margin_all: 10,
+					gtk::Label {
+						#[watch]
+						set_visible: !self.editing_notes,
+						#[watch]
+						set_markup: &self.notes_markup(),
+						set_wrap: true,
+						set_selectable: true,
+						set_xalign: 0.0,
+					},
+					gtk::ScrolledWindow {
+						#[watch]
+						set_visible: self.editing_notes,
+						set_min_content_height: 120,
+						set_propagate_natural_height: true,
+						gtk::TextView {
+							set_buffer: Some(&self.notes_buffer),
+							set_wrap_mode: gtk::WrapMode::WordChar,
+							set_monospace: true,
+							set_top_margin: 6,
+							set_bottom_margin: 6,
+							set_left_margin: 6,
+							set_right_margin: 6,
+						}
+					},
+					gtk::Box {
+						set_halign: gtk::Align::End,
+						set_spacing: 10,
+						gtk::Label {
+							#[watch]
+							set_visible: self.editing_notes,
+							add_css_class: "dim-label",
+							set_label: fl!("markdown-supported"),
+						},
+						gtk::Button {
+							#[watch]
+							set_visible: !self.editing_notes,
+							set_label: fl!("edit-notes"),
+							connect_clicked => TaskInput::EditNotes,
+						},
+						gtk::Button {
+							#[watch]
+							set_visible: self.editing_notes,
+							add_css_class: "suggested-action",
+							set_label: fl!("save-notes"),
+							connect_clicked => TaskInput::SaveNotes,
+						},
+					},
 				},
+			},
+			add_row = &adw::EntryRow {
+				set_title: fl!("tags"),
+				set_tooltip: fl!("tags-desc"),
+				set_show_apply_button: true,
+				set_text: self.task.tags.join(", ").as_str(),
 				connect_activate[sender] => move |entry| {
-					let buffer = entry.text().to_string();
-					if buffer.is_empty() {
-						sender.input(TaskInput::SetNotes(None));
-					} else {
-						sender.input(TaskInput::SetNotes(Some(buffer)));
-					}
+					sender.input(TaskInput::SetTags(parse_tags(&entry.text())));
 				},
 				connect_apply[sender] => move |entry| {
-					let buffer = entry.text().to_string();
-					if buffer.is_empty() {
-						sender.input(TaskInput::SetNotes(None));
-					} else {
-						sender.input(TaskInput::SetNotes(Some(buffer)));
-					}
+					sender.input(TaskInput::SetTags(parse_tags(&entry.text())));
 				},
 			},
 			add_row = &adw::ActionRow {
@@ -191,21 +346,23 @@ impl AsyncFactoryComponent for TaskModel {
 					}
 				}
 			},
-			add_row = &adw::ActionRow {
-				set_title: fl!("completed"),
-				set_subtitle: fl!("completed-desc"),
+			add_row = &adw::ComboRow {
+				set_title: fl!("status"),
+				set_subtitle: fl!("status-desc"),
 				add_prefix = &gtk::Image {
 					set_icon_name: Some(icon_name::CHECK_ROUND_OUTLINE_WHOLE)
 				},
-				add_suffix = &gtk::Switch {
-					set_tooltip: fl!("completed-tooltip"),
-					#[watch]
-					set_active: self.task.status == Status::Completed,
-					set_valign: gtk::Align::Center,
-					connect_state_set[sender] => move |_, state| {
-						sender.input(TaskInput::SetStatus(state));
-						Propagation::Proceed
-					}
+				set_model: Some(&gtk::StringList::new(&[
+					fl!("not-started"),
+					fl!("in-progress"),
+					fl!("waiting"),
+					fl!("deferred"),
+					fl!("completed"),
+				])),
+				#[watch]
+				set_selected: status_position(self.task.status),
+				connect_selected_notify[sender] => move |combo_row| {
+					sender.input(TaskInput::SetStatus(status_from_position(combo_row.selected())));
 				}
 			},
 			add_row = &adw::ActionRow {
@@ -268,7 +425,7 @@ impl AsyncFactoryComponent for TaskModel {
 				add_suffix = &gtk::Label {
 					set_css_classes: &["accent"],
 					#[watch]
-					set_label: &self.task.reminder_date.map(|date| date.format("%m/%d/%Y %H:%M").to_string()).unwrap_or(fl!("no-date-set").to_string()),
+					set_label: &self.task.reminder_date.map(|date| time_zone::to_local(date).format("%m/%d/%Y %H:%M").to_string()).unwrap_or(fl!("no-date-set").to_string()),
 					set_valign: gtk::Align::Center,
 				},
 				add_row = &gtk::Box {
@@ -282,14 +439,12 @@ impl AsyncFactoryComponent for TaskModel {
 							gtk::Calendar {
 								set_margin_all: 10,
 								add_css_class: "card",
-								set_day: self.task.reminder_date.unwrap_or(Utc::now()).day() as i32,
-								set_month: self.task.reminder_date.unwrap_or(Utc::now()).month() as i32 - 1,
-								set_year: self.task.reminder_date.unwrap_or(Utc::now()).year(),
+								set_day: time_zone::local_date(self.task.reminder_date.unwrap_or(Utc::now())).day() as i32,
+								set_month: time_zone::local_date(self.task.reminder_date.unwrap_or(Utc::now())).month() as i32 - 1,
+								set_year: time_zone::local_date(self.task.reminder_date.unwrap_or(Utc::now())).year(),
 								connect_day_selected[sender] => move |calendar| {
-									if let Ok(date) = calendar.date().format("%Y-%m-%dT%H:%M:%SZ") {
-										if let Ok(date) = DateTime::<Utc>::from_str(date.to_string().as_str()) {
-											sender.input(TaskInput::SetReminderDate(Some(date)))
-										}
+									if let Some(date) = calendar_date(calendar) {
+										sender.input(TaskInput::SetReminderDate(Some(date)))
 									}
 								}
 							},
@@ -331,7 +486,7 @@ impl AsyncFactoryComponent for TaskModel {
 							set_spacing: 6,
 							gtk::SpinButton {
 								set_adjustment: &gtk::Adjustment::new(
-									self.task.reminder_date.unwrap_or_default().time().hour() as f64, 0.0, 23.0, 1.0, 1.0, 0.0
+									time_zone::to_local(self.task.reminder_date.unwrap_or_default()).hour() as f64, 0.0, 23.0, 1.0, 1.0, 0.0
 								),
 								set_orientation: gtk::Orientation::Vertical,
 								set_wrap: true,
@@ -349,7 +504,7 @@ impl AsyncFactoryComponent for TaskModel {
 							},
 							gtk::SpinButton {
 								set_adjustment: &gtk::Adjustment::new(
-									self.task.reminder_date.unwrap_or_default().time().minute() as f64, 0.0, 59.0, 1.0, 1.0, 0.0
+									time_zone::to_local(self.task.reminder_date.unwrap_or_default()).minute() as f64, 0.0, 59.0, 1.0, 1.0, 0.0
 								),
 								set_orientation: gtk::Orientation::Vertical,
 								set_wrap: true,
@@ -368,63 +523,262 @@ impl AsyncFactoryComponent for TaskModel {
 						set_title: fl!("recurrence"),
 						set_subtitle: fl!("set-recurrence"),
 						add_row = &gtk::Box {
-							set_valign: gtk::Align::Center,
-							set_halign: gtk::Align::Center,
-							set_margin_all: 5,
-							set_css_classes: &["linked"],
-							gtk::ToggleButton {
-								set_label: fl!("mon"),
-								set_tooltip: fl!("monday"),
+							set_orientation: gtk::Orientation::Vertical,
+							adw::ComboRow {
+								set_title: fl!("repeat"),
+								set_model: Some(&gtk::StringList::new(&[
+									fl!("never"),
+									fl!("daily"),
+									fl!("weekly"),
+									fl!("monthly"),
+									fl!("monthly-relative"),
+									fl!("yearly"),
+									fl!("yearly-relative"),
+								])),
+								set_selected: frequency_position(self.task.recurrence.as_ref()),
+								connect_selected_notify[sender] => move |combo_row| {
+									sender.input(TaskInput::SetRecurrenceFrequency(frequency_from_position(combo_row.selected())))
+								},
+							},
+							adw::ActionRow {
 								#[watch]
-								set_active: self.task.recurrence.monday,
-								connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Monday)))
+								set_visible: self.task.recurrence.is_some(),
+								set_title: fl!("repeat-every"),
+								set_subtitle: fl!("repeat-every-desc"),
+								add_suffix = &gtk::SpinButton {
+									set_adjustment: &gtk::Adjustment::new(
+										self.task.recurrence.as_ref().map(|recurrence| recurrence.interval).unwrap_or(1) as f64, 1.0, 99.0, 1.0, 1.0, 0.0
+									),
+									set_valign: gtk::Align::Center,
+									set_numeric: true,
+									connect_value_changed[sender] => move |spin| {
+										sender.input(TaskInput::SetRecurrenceInterval(spin.value() as i32))
+									},
+								},
 							},
-							gtk::ToggleButton {
-								set_label: fl!("tue"),
-								set_tooltip: fl!("tuesday"),
+							gtk::Box {
 								#[watch]
-								set_active: self.task.recurrence.tuesday,
-								connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Tuesday)))
+								set_visible: self.task.recurrence.as_ref().is_some_and(|recurrence| matches!(recurrence.frequency, Frequency::Weekly | Frequency::RelativeMonthly | Frequency::RelativeYearly)),
+								set_valign: gtk::Align::Center,
+								set_halign: gtk::Align::Center,
+								set_margin_all: 5,
+								set_css_classes: &["linked"],
+								gtk::ToggleButton {
+									set_label: fl!("mon"),
+									set_tooltip: fl!("monday"),
+									#[watch]
+									set_active: self.task.recurrence.as_ref().is_some_and(|recurrence| recurrence.has_day(Day::Monday)),
+									connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Monday)))
+								},
+								gtk::ToggleButton {
+									set_label: fl!("tue"),
+									set_tooltip: fl!("tuesday"),
+									#[watch]
+									set_active: self.task.recurrence.as_ref().is_some_and(|recurrence| recurrence.has_day(Day::Tuesday)),
+									connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Tuesday)))
+								},
+								gtk::ToggleButton {
+									set_label: fl!("wed"),
+									set_tooltip: fl!("wednesday"),
+									#[watch]
+									set_active: self.task.recurrence.as_ref().is_some_and(|recurrence| recurrence.has_day(Day::Wednesday)),
+									connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Wednesday)))
+								},
+								gtk::ToggleButton {
+									set_label: fl!("thu"),
+									set_tooltip: fl!("thursday"),
+									#[watch]
+									set_active: self.task.recurrence.as_ref().is_some_and(|recurrence| recurrence.has_day(Day::Thursday)),
+									connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Thursday)))
+								},
+								gtk::ToggleButton {
+									set_label: fl!("fri"),
+									set_tooltip: fl!("friday"),
+									#[watch]
+									set_active: self.task.recurrence.as_ref().is_some_and(|recurrence| recurrence.has_day(Day::Friday)),
+									connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Friday)))
+								},
+								gtk::ToggleButton {
+									set_label: fl!("sat"),
+									set_tooltip: fl!("saturday"),
+									#[watch]
+									set_active: self.task.recurrence.as_ref().is_some_and(|recurrence| recurrence.has_day(Day::Saturday)),
+									connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Saturday)))
+								},
+								gtk::ToggleButton {
+									set_label: fl!("sun"),
+									set_tooltip: fl!("sunday"),
+									#[watch]
+									set_active: self.task.recurrence.as_ref().is_some_and(|recurrence| recurrence.has_day(Day::Sunday)),
+									connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Sunday)))
+								},
 							},
-							gtk::ToggleButton {
-								set_label: fl!("wed"),
-								set_tooltip: fl!("wednesday"),
+							adw::ComboRow {
+								#[watch]
+								set_visible: self.task.recurrence.as_ref().is_some_and(|recurrence| matches!(recurrence.frequency, Frequency::RelativeMonthly | Frequency::RelativeYearly)),
+								set_title: fl!("week-of-month"),
+								set_model: Some(&gtk::StringList::new(&[
+									fl!("first"),
+									fl!("second"),
+									fl!("third"),
+									fl!("fourth"),
+									fl!("last"),
+								])),
 								#[watch]
-								set_active: self.task.recurrence.wednesday,
-								connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Wednesday)))
+								set_selected: self.task.recurrence.as_ref().and_then(|recurrence| recurrence.index).unwrap_or_default() as u32,
+								connect_selected_notify[sender] => move |combo_row| {
+									sender.input(TaskInput::SetRecurrenceWeekIndex(week_index_from_position(combo_row.selected())))
+								},
 							},
-							gtk::ToggleButton {
-								set_label: fl!("thu"),
-								set_tooltip: fl!("thursday"),
+							adw::ActionRow {
 								#[watch]
-								set_active: self.task.recurrence.thursday,
-								connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Thursday)))
+								set_visible: self.task.recurrence.as_ref().is_some_and(|recurrence| matches!(recurrence.frequency, Frequency::AbsoluteMonthly | Frequency::AbsoluteYearly)),
+								set_title: fl!("day-of-month"),
+								add_suffix = &gtk::SpinButton {
+									set_adjustment: &gtk::Adjustment::new(
+										self.task.recurrence.as_ref().map(|recurrence| recurrence.day_of_month).unwrap_or(1).max(1) as f64, 1.0, 31.0, 1.0, 1.0, 0.0
+									),
+									set_valign: gtk::Align::Center,
+									set_numeric: true,
+									set_wrap: true,
+									connect_value_changed[sender] => move |spin| {
+										sender.input(TaskInput::SetRecurrenceDayOfMonth(spin.value() as i32))
+									},
+								},
 							},
-							gtk::ToggleButton {
-								set_label: fl!("fri"),
-								set_tooltip: fl!("friday"),
+							adw::ComboRow {
+								#[watch]
+								set_visible: self.task.recurrence.as_ref().is_some_and(|recurrence| matches!(recurrence.frequency, Frequency::AbsoluteYearly | Frequency::RelativeYearly)),
+								set_title: fl!("month"),
+								set_model: Some(&gtk::StringList::new(&[
+									fl!("january"),
+									fl!("february"),
+									fl!("march"),
+									fl!("april"),
+									fl!("may"),
+									fl!("june"),
+									fl!("july"),
+									fl!("august"),
+									fl!("september"),
+									fl!("october"),
+									fl!("november"),
+									fl!("december"),
+								])),
 								#[watch]
-								set_active: self.task.recurrence.friday,
-								connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Friday)))
+								set_selected: self.task.recurrence.as_ref().map(|recurrence| recurrence.month.max(1) as u32 - 1).unwrap_or_default(),
+								connect_selected_notify[sender] => move |combo_row| {
+									sender.input(TaskInput::SetRecurrenceMonth(combo_row.selected() as i32 + 1))
+								},
 							},
-							gtk::ToggleButton {
-								set_label: fl!("sat"),
-								set_tooltip: fl!("saturday"),
+							adw::ComboRow {
+								#[watch]
+								set_visible: self.task.recurrence.is_some(),
+								set_title: fl!("ends"),
+								set_model: Some(&gtk::StringList::new(&[
+									fl!("never"),
+									fl!("ends-on-date"),
+									fl!("ends-after"),
+								])),
 								#[watch]
-								set_active: self.task.recurrence.saturday,
-								connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Saturday)))
+								set_selected: self.task.recurrence.as_ref().map(|recurrence| range_type_position(recurrence.range.range_type)).unwrap_or_default(),
+								connect_selected_notify[sender] => move |combo_row| {
+									sender.input(TaskInput::SetRecurrenceRangeType(combo_row.selected()))
+								},
 							},
-							gtk::ToggleButton {
-								set_label: fl!("sun"),
-								set_tooltip: fl!("sunday"),
+							adw::ActionRow {
 								#[watch]
-								set_active: self.task.recurrence.sunday,
-								connect_toggled[sender] => move |toggled_button| sender.input(TaskInput::SetDayInRecurrence((toggled_button.is_active(), Day::Sunday)))
+								set_visible: self.task.recurrence.as_ref().is_some_and(|recurrence| recurrence.range.range_type == RangeType::Numbered),
+								set_title: fl!("occurrences"),
+								add_suffix = &gtk::SpinButton {
+									set_adjustment: &gtk::Adjustment::new(
+										self.task.recurrence.as_ref().map(|recurrence| recurrence.range.number_of_occurrences).unwrap_or(1).max(1) as f64, 1.0, 999.0, 1.0, 1.0, 0.0
+									),
+									set_valign: gtk::Align::Center,
+									set_numeric: true,
+									connect_value_changed[sender] => move |spin| {
+										sender.input(TaskInput::SetRecurrenceOccurrences(spin.value() as i32))
+									},
+								},
+							},
+							gtk::Calendar {
+								#[watch]
+								set_visible: self.task.recurrence.as_ref().is_some_and(|recurrence| matches!(recurrence.range.range_type, RangeType::EndDate(_))),
+								set_margin_all: 10,
+								add_css_class: "card",
+								connect_day_selected[sender] => move |calendar| {
+									let date = calendar.date();
+									sender.input(TaskInput::SetRecurrenceEndDate(NaiveDate::from_ymd_opt(
+										date.year(),
+										date.month() as u32,
+										date.day_of_month() as u32,
+									)))
+								}
 							},
 						},
 					}
 				}
 			},
+			add_row = &adw::ExpanderRow {
+				add_prefix = &gtk::Image {
+					set_icon_name: Some(icon_name::WORK_WEEK)
+				},
+				set_title: fl!("start-date"),
+				set_subtitle: fl!("set-start-date"),
+				set_enable_expansion: true,
+				add_suffix = &gtk::Label {
+					set_css_classes: &["accent"],
+					#[watch]
+					set_label: &self.task.start_date.map(|date| time_zone::to_local(date).format("%m/%d/%Y").to_string()).unwrap_or(fl!("no-date-set").to_string()),
+					set_valign: gtk::Align::Center,
+				},
+				add_row = &gtk::Box {
+					set_orientation: gtk::Orientation::Vertical,
+					#[name(start_date_calendar)]
+					gtk::Calendar {
+						set_margin_all: 10,
+						add_css_class: "card",
+						set_day: time_zone::local_date(self.task.start_date.unwrap_or(Utc::now())).day() as i32,
+						set_month: time_zone::local_date(self.task.start_date.unwrap_or(Utc::now())).month() as i32 - 1,
+						set_year: time_zone::local_date(self.task.start_date.unwrap_or(Utc::now())).year(),
+						connect_day_selected[sender] => move |calendar| {
+							if let Some(date) = calendar_date(calendar) {
+								sender.input(TaskInput::SetStartDate(Some(date)))
+							}
+						}
+					},
+					gtk::Box {
+						set_margin_all: 10,
+						set_margin_bottom: 5,
+						set_margin_top: 5,
+						set_spacing: 10,
+						gtk::Button {
+							set_hexpand: true,
+							set_label: fl!("today"),
+							set_tooltip: fl!("set-day-today"),
+							connect_clicked[sender] => move |_| {
+								sender.input(TaskInput::SetDate(DateType::StartDate, DateDay::Today));
+							}
+						},
+						gtk::Button {
+							set_hexpand: true,
+							set_label: fl!("tomorrow"),
+							set_tooltip: fl!("set-day-tomorrow"),
+							connect_clicked[sender] => move |_| {
+								sender.input(TaskInput::SetDate(DateType::StartDate, DateDay::Tomorrow));
+							}
+						}
+					},
+					gtk::Button {
+						set_margin_all:10,
+						set_margin_top: 5,
+						set_label: fl!("none"),
+						set_tooltip: fl!("unset"),
+						connect_clicked[sender] => move |_| {
+							sender.input(TaskInput::SetDate(DateType::StartDate, DateDay::None));
+						}
+					}
+				}
+			},
 			add_row = &adw::ExpanderRow {
 				add_prefix = &gtk::Image {
 					set_icon_name: Some(icon_name::WORK_WEEK)
@@ -436,7 +790,7 @@ impl AsyncFactoryComponent for TaskModel {
 				add_suffix = &gtk::Label {
 					set_css_classes: &["accent"],
 					#[watch]
-					set_label: &self.task.due_date.map(|date| date.format("%m/%d/%Y %H:%M").to_string()).unwrap_or(fl!("no-date-set").to_string()),
+					set_label: &self.task.due_date.map(|date| time_zone::to_local(date).format("%m/%d/%Y %H:%M").to_string()).unwrap_or(fl!("no-date-set").to_string()),
 					set_valign: gtk::Align::Center,
 				},
 				add_row = &gtk::Box {
@@ -445,15 +799,12 @@ impl AsyncFactoryComponent for TaskModel {
 					gtk::Calendar {
 						set_margin_all: 10,
 						add_css_class: "card",
-						set_day: self.task.due_date.unwrap_or(Utc::now()).day() as i32,
-						set_month: self.task.due_date.unwrap_or(Utc::now()).month() as i32 - 1,
-						set_year: self.task.due_date.unwrap_or(Utc::now()).year(),
+						set_day: time_zone::local_date(self.task.due_date.unwrap_or(Utc::now())).day() as i32,
+						set_month: time_zone::local_date(self.task.due_date.unwrap_or(Utc::now())).month() as i32 - 1,
+						set_year: time_zone::local_date(self.task.due_date.unwrap_or(Utc::now())).year(),
 						connect_day_selected[sender] => move |calendar| {
-							if let Ok(date) = calendar.date().format("%Y-%m-%dT%H:%M:%SZ") {
-								println!("{date}");
-								if let Ok(date) = DateTime::<Utc>::from_str(date.as_str()) {
-										sender.input(TaskInput::SetDueDate(Some(date)))
-								}
+							if let Some(date) = calendar_date(calendar) {
+								sender.input(TaskInput::SetDueDate(Some(date)))
 							}
 						}
 					},
@@ -490,6 +841,46 @@ impl AsyncFactoryComponent for TaskModel {
 					}
 				}
 			},
+			add_row = &adw::SpinRow {
+				set_visible: self.parent_list.service != Service::Microsoft,
+				set_title: fl!("estimate"),
+				set_subtitle: fl!("estimate-desc"),
+				add_prefix = &gtk::Image {
+					set_icon_name: Some("document-open-recent-symbolic")
+				},
+				set_adjustment: Some(&gtk::Adjustment::new(
+					self.task.estimated_minutes.unwrap_or_default() as f64, 0.0, 14400.0, 5.0, 30.0, 0.0
+				)),
+				connect_value_notify[sender] => move |row| {
+					sender.input(TaskInput::SetEstimate(row.value() as i64))
+				}
+			},
+			add_row = &adw::ActionRow {
+				set_visible: self.parent_list.service != Service::Microsoft,
+				set_title: fl!("time-tracked"),
+				#[watch]
+				set_subtitle: &format_duration(self.task.tracked_time()),
+				add_prefix = &gtk::Image {
+					set_icon_name: Some("alarm-symbolic")
+				},
+				add_suffix = &gtk::Button {
+					add_css_class: "flat",
+					set_valign: gtk::Align::Center,
+					#[watch]
+					set_icon_name: if self.task.is_tracking() {
+						"media-playback-stop-symbolic"
+					} else {
+						"media-playback-start-symbolic"
+					},
+					#[watch]
+					set_tooltip: if self.task.is_tracking() {
+						fl!("stop-timer")
+					} else {
+						fl!("start-timer")
+					},
+					connect_clicked => TaskInput::ToggleTimer
+				}
+			},
 			add_row = &adw::ExpanderRow {
 				#[watch]
 				set_enable_expansion: !self.sub_tasks.is_empty(),
@@ -506,6 +897,107 @@ impl AsyncFactoryComponent for TaskModel {
 				add_row = sub_tasks -> adw::PreferencesGroup {
 					set_margin_all: 10,
 				}
+			},
+			add_row = &adw::ExpanderRow {
+				#[watch]
+				set_enable_expansion: !self.attachments.is_empty(),
+				#[watch]
+				set_expanded: !self.attachments.is_empty(),
+				set_title: fl!("attachments"),
+				add_prefix = &gtk::Image {
+					set_icon_name: Some("mail-attachment-symbolic")
+				},
+				add_suffix = &gtk::Button {
+					add_css_class: "flat",
+					set_icon_name: icon_name::PLUS,
+					set_tooltip: fl!("add-attachment"),
+					connect_clicked => TaskInput::AddAttachment
+				},
+				#[local_ref]
+				add_row = attachments -> adw::PreferencesGroup {
+					set_margin_all: 10,
+				}
+			},
+			add_row = &adw::ExpanderRow {
+				#[watch]
+				set_expanded: !self.linked_resources.is_empty(),
+				set_title: fl!("links"),
+				add_prefix = &gtk::Image {
+					set_icon_name: Some("insert-link-symbolic")
+				},
+				add_row = &adw::EntryRow {
+					set_title: fl!("add-link"),
+					set_show_apply_button: true,
+					connect_activate[sender] => move |entry| {
+						sender.input(TaskInput::AddLink(entry.text().to_string()));
+						entry.set_text("");
+					},
+					connect_apply[sender] => move |entry| {
+						sender.input(TaskInput::AddLink(entry.text().to_string()));
+						entry.set_text("");
+					},
+				},
+				#[local_ref]
+				add_row = linked_resources -> adw::PreferencesGroup {
+					set_margin_all: 10,
+				}
+			},
+			add_row = &adw::ExpanderRow {
+				set_visible: self.parent_list.service != Service::Microsoft,
+				#[watch]
+				set_enable_expansion: !self.dependencies.is_empty() || !self.candidates.is_empty(),
+				#[watch]
+				set_expanded: !self.dependencies.is_empty() || !self.candidates.is_empty(),
+				set_title: fl!("blocked-by"),
+				#[watch]
+				set_subtitle: if self.task.is_blocked() {
+					fl!("blocked-tooltip").as_str()
+				} else {
+					""
+				},
+				add_prefix = &gtk::Image {
+					set_icon_name: Some("changes-prevent-symbolic")
+				},
+				add_suffix = &gtk::Button {
+					add_css_class: "flat",
+					set_icon_name: icon_name::PLUS,
+					set_tooltip: fl!("add-dependency"),
+					connect_clicked => TaskInput::ChooseDependency
+				},
+				add_row = &adw::ComboRow {
+					#[watch]
+					set_visible: !self.candidates.is_empty(),
+					set_title: fl!("add-dependency"),
+					set_model: Some(&self.candidate_names),
+					connect_selected_notify[sender] => move |row| {
+						let selected = row.selected();
+						if selected != gtk::INVALID_LIST_POSITION && selected > 0 {
+							sender.input(TaskInput::AddDependency(selected));
+						}
+					}
+				},
+				#[local_ref]
+				add_row = dependencies -> adw::PreferencesGroup {
+					set_margin_all: 10,
+				}
+			},
+			add_row = &adw::ExpanderRow {
+				set_visible: self.parent_list.service != Service::Microsoft,
+				set_title: fl!("history"),
+				add_prefix = &gtk::Image {
+					set_icon_name: Some("document-open-recent-symbolic")
+				},
+				connect_expanded_notify[sender] => move |row| {
+					if row.is_expanded() {
+						sender.input(TaskInput::LoadHistory);
+					}
+				},
+				#[local_ref]
+				add_row = history -> adw::PreferencesGroup {
+					set_margin_all: 10,
+					#[watch]
+					set_description: self.history.is_empty().then_some(fl!("no-history").as_str()),
+				}
 			}
 		}
 	}
@@ -527,14 +1019,56 @@ impl AsyncFactoryComponent for TaskModel {
 					},
 					SubTaskOutput::Remove(index) => TaskInput::RemoveSubTask(index),
 				}),
+			attachments: FactoryVecDeque::builder()
+				.launch(adw::PreferencesGroup::default())
+				.forward(sender.input_sender(), |output| match output {
+					AttachmentOutput::Open(index) => TaskInput::OpenAttachment(index),
+					AttachmentOutput::Remove(index) => TaskInput::RemoveAttachment(index),
+				}),
+			linked_resources: FactoryVecDeque::builder()
+				.launch(adw::PreferencesGroup::default())
+				.forward(sender.input_sender(), |output| match output {
+					LinkedResourceOutput::Open(index) => TaskInput::OpenLink(index),
+					LinkedResourceOutput::Remove(index) => TaskInput::RemoveLink(index),
+				}),
+			dependencies: FactoryVecDeque::builder()
+				.launch(adw::PreferencesGroup::default())
+				.forward(sender.input_sender(), |output| match output {
+					DependencyOutput::Remove(index) => TaskInput::RemoveDependency(index),
+				}),
+			history: FactoryVecDeque::builder()
+				.launch(adw::PreferencesGroup::default())
+				.detach(),
+			notes_buffer: gtk::TextBuffer::builder()
+				.text(init.task.notes.as_deref().unwrap_or_default())
+				.build(),
+			editing_notes: false,
+			candidates: vec![],
+			candidate_names: gtk::StringList::new(&[]),
 			parent_list: init.parent_list,
 			index: index.clone(),
+			timer: None,
 		};
+		if model.task.is_tracking() {
+			model.start_ticking(&sender);
+		}
 		{
 			let mut sub_tasks_guard = model.sub_tasks.guard();
 			for sub_task in init.task.sub_tasks {
 				sub_tasks_guard.push_back(SubTaskInit::new(sub_task));
 			}
+			let mut attachments_guard = model.attachments.guard();
+			for attachment in init.task.attachments {
+				attachments_guard.push_back(attachment);
+			}
+			let mut linked_resources_guard = model.linked_resources.guard();
+			for linked_resource in init.task.linked_resources {
+				linked_resources_guard.push_back(linked_resource);
+			}
+			let mut dependencies_guard = model.dependencies.guard();
+			for dependency in init.task.blocked_by {
+				dependencies_guard.push_back(dependency);
+			}
 		}
 		model
 	}
@@ -547,6 +1081,10 @@ impl AsyncFactoryComponent for TaskModel {
 		sender: AsyncFactorySender<Self>,
 	) -> Self::Widgets {
 		let sub_tasks = self.sub_tasks.widget();
+		let attachments = self.attachments.widget();
+		let linked_resources = self.linked_resources.widget();
+		let dependencies = self.dependencies.widget();
+		let history = self.history.widget();
 		let widgets = view_output!();
 		widgets
 	}
@@ -557,27 +1095,54 @@ impl AsyncFactoryComponent for TaskModel {
 		message: Self::Input,
 		sender: AsyncFactorySender<Self>,
 	) {
+		let sends_own_output = matches!(
+			message,
+			TaskInput::EditNotes
+				| TaskInput::CreateSubTask
+				| TaskInput::UpdateSubTask(_, _)
+				| TaskInput::RemoveSubTask(_)
+				| TaskInput::AddAttachment
+				| TaskInput::AttachmentAdded(_)
+				| TaskInput::OpenAttachment(_)
+				| TaskInput::RemoveAttachment(_)
+				| TaskInput::OpenLink(_)
+				| TaskInput::ChooseDependency
+				| TaskInput::SetDependencyCandidates(_)
+				| TaskInput::AddDependency(_)
+				| TaskInput::DependencyAdded(_)
+				| TaskInput::RemoveDependency(_)
+				| TaskInput::SetDependencyCompleted(_, _)
+				| TaskInput::Tick
+				| TaskInput::LoadHistory
+				| TaskInput::SetHistory(_)
+		);
+		let previous = self.task.clone();
 		match message {
-			TaskInput::SetNotes(notes) => {
-				self.task.notes = notes;
+			TaskInput::EditNotes => self.editing_notes = true,
+			TaskInput::SaveNotes => {
+				let (start, end) = self.notes_buffer.bounds();
+				let notes = self.notes_buffer.text(&start, &end, false).to_string();
+				self.task.notes = (!notes.trim().is_empty()).then_some(notes);
+				self.editing_notes = false;
+			},
+			TaskInput::SetTags(tags) => {
+				self.task.tags = tags;
 			},
 			TaskInput::SetPriority(priority) => {
 				self.task.priority = priority.into();
 			},
 			TaskInput::SetStatus(status) => {
-				self.task.status = if status {
-					Status::Completed
-				} else {
-					Status::NotStarted
-				}
+				self.task.status = status;
 			},
 			TaskInput::SetToday(today) => self.task.today = today,
 			TaskInput::SetCompleted(toggled) => {
-				self.task.status = if toggled {
-					Status::Completed
-				} else {
-					Status::NotStarted
-				};
+				if toggled {
+					self.task.status = Status::Completed;
+					self.task.stop_timer();
+					self.stop_ticking();
+				} else if self.task.status == Status::Completed {
+					self.task.status = Status::NotStarted;
+				}
 			},
 			TaskInput::Favorite => {
 				self.task.favorite = !self.task.favorite;
@@ -602,26 +1167,42 @@ impl AsyncFactoryComponent for TaskModel {
 						sender.input(TaskInput::SetReminderDate(date));
 						if let Some(date) = date {
 							self.task.reminder_date = Some(date);
-							widgets.reminder_calendar.set_year(date.year());
-							widgets.reminder_calendar.set_month(date.month() as i32 - 1);
-							widgets.reminder_calendar.set_day(date.day() as i32);
+							let day = time_zone::local_date(date);
+							widgets.reminder_calendar.set_year(day.year());
+							widgets.reminder_calendar.set_month(day.month() as i32 - 1);
+							widgets.reminder_calendar.set_day(day.day() as i32);
 						} else {
 							self.task.reminder_date = None;
 						}
 					},
+					DateType::StartDate => {
+						sender.input(TaskInput::SetStartDate(date));
+						if let Some(date) = date {
+							let day = time_zone::local_date(date);
+							widgets.start_date_calendar.set_year(day.year());
+							widgets
+								.start_date_calendar
+								.set_month(day.month() as i32 - 1);
+							widgets.start_date_calendar.set_day(day.day() as i32);
+						}
+					},
 					DateType::DueDate => {
 						sender.input(TaskInput::SetDueDate(date));
 						if let Some(date) = date {
 							self.task.due_date = Some(date);
-							widgets.due_date_calendar.set_year(date.year());
-							widgets.due_date_calendar.set_month(date.month() as i32 - 1);
-							widgets.due_date_calendar.set_day(date.day() as i32);
+							let day = time_zone::local_date(date);
+							widgets.due_date_calendar.set_year(day.year());
+							widgets.due_date_calendar.set_month(day.month() as i32 - 1);
+							widgets.due_date_calendar.set_day(day.day() as i32);
 						} else {
 							self.task.due_date = None;
 						}
 					},
 				}
 			},
+			TaskInput::SetStartDate(start_date) => {
+				self.task.start_date = start_date;
+			},
 			TaskInput::SetDueDate(due_date) => {
 				if let Some(date) = due_date {
 					self.task.due_date = Some(date);
@@ -637,44 +1218,103 @@ impl AsyncFactoryComponent for TaskModel {
 				}
 			},
 			TaskInput::SetReminderHour(hour) => {
-				if let Some(date) = self.task.reminder_date {
-					if let Some(new_date) = date.with_hour(hour) {
-						self.task.reminder_date = Some(new_date);
-					}
-				} else {
-					let now = Utc::now().with_hour(hour).unwrap();
-					let now = now.with_minute(0).unwrap();
-					self.task.reminder_date = Some(now);
+				let reminder = match self.task.reminder_date {
+					Some(date) => time_zone::to_local(date).naive_local().with_hour(hour),
+					None => time_zone::local_date(Utc::now()).and_hms_opt(hour, 0, 0),
+				};
+				if let Some(reminder) = reminder {
+					self.task.reminder_date =
+						Some(time_zone::from_local(reminder, time_zone::user_time_zone()));
 				}
 			},
 			TaskInput::SetReminderMinute(minute) => {
-				if let Some(date) = self.task.reminder_date {
-					if let Some(new_date) = date.with_minute(minute) {
-						self.task.reminder_date = Some(new_date);
+				let reminder = match self.task.reminder_date {
+					Some(date) => {
+						time_zone::to_local(date).naive_local().with_minute(minute)
+					},
+					None => time_zone::local_date(Utc::now()).and_hms_opt(0, minute, 0),
+				};
+				if let Some(reminder) = reminder {
+					self.task.reminder_date =
+						Some(time_zone::from_local(reminder, time_zone::user_time_zone()));
+				}
+			},
+			TaskInput::SetRecurrenceFrequency(frequency) => {
+				self.task.recurrence = frequency.map(|frequency| {
+					let mut recurrence = Recurrence::new(frequency);
+					match &self.task.recurrence {
+						Some(previous) => {
+							recurrence.interval = previous.interval;
+							recurrence.range = previous.range.clone();
+						},
+						None => {
+							recurrence.range.start_date = Some(time_zone::local_date(
+								self.task.due_date.unwrap_or_else(Utc::now),
+							))
+						},
 					}
-				} else {
-					let now = Utc::now().with_hour(0).unwrap();
-					let now = now.with_minute(minute).unwrap();
-					self.task.reminder_date = Some(now);
+					recurrence
+				});
+			},
+			TaskInput::SetRecurrenceInterval(interval) => {
+				if let Some(recurrence) = &mut self.task.recurrence {
+					recurrence.interval = interval;
 				}
 			},
-			TaskInput::SetDayInRecurrence((active, day)) => match day {
-				Day::Monday => self.task.recurrence.monday = active,
-				Day::Tuesday => self.task.recurrence.tuesday = active,
-				Day::Wednesday => self.task.recurrence.wednesday = active,
-				Day::Thursday => self.task.recurrence.thursday = active,
-				Day::Friday => self.task.recurrence.friday = active,
-				Day::Saturday => self.task.recurrence.saturday = active,
-				Day::Sunday => self.task.recurrence.sunday = active,
+			TaskInput::SetDayInRecurrence((active, day)) => {
+				if let Some(recurrence) = &mut self.task.recurrence {
+					recurrence.set_day(day, active);
+				}
+			},
+			TaskInput::SetRecurrenceDayOfMonth(day_of_month) => {
+				if let Some(recurrence) = &mut self.task.recurrence {
+					recurrence.day_of_month = day_of_month;
+				}
+			},
+			TaskInput::SetRecurrenceWeekIndex(index) => {
+				if let Some(recurrence) = &mut self.task.recurrence {
+					recurrence.index = Some(index);
+				}
+			},
+			TaskInput::SetRecurrenceMonth(month) => {
+				if let Some(recurrence) = &mut self.task.recurrence {
+					recurrence.month = month;
+				}
+			},
+			TaskInput::SetRecurrenceRangeType(position) => {
+				let due_date =
+					time_zone::local_date(self.task.due_date.unwrap_or_else(Utc::now));
+				if let Some(recurrence) = &mut self.task.recurrence {
+					let end_date = recurrence.range.end_date().unwrap_or(due_date);
+					recurrence.range.range_type =
+						range_type_from_position(position, end_date);
+				}
+			},
+			TaskInput::SetRecurrenceEndDate(end_date) => {
+				if let (Some(recurrence), Some(end_date)) =
+					(&mut self.task.recurrence, end_date)
+				{
+					recurrence.range.range_type = RangeType::EndDate(end_date);
+				}
+			},
+			TaskInput::SetRecurrenceOccurrences(occurrences) => {
+				if let Some(recurrence) = &mut self.task.recurrence {
+					recurrence.range.number_of_occurrences = occurrences;
+				}
 			},
 			TaskInput::CreateSubTask => {
+				let sub_task = Task::new(String::new(), self.task.parent.clone());
 				let index = self.sub_tasks.guard().push_back(SubTaskInit {
-					sub_task: Task::default(),
+					sub_task: sub_task.clone(),
 				});
 				self
 					.task
 					.sub_tasks
-					.insert(index.current_index(), Task::default());
+					.insert(index.current_index(), sub_task.clone());
+				sender
+					.output_sender()
+					.send(TaskOutput::CreateSubTask(self.task.clone(), sub_task))
+					.unwrap_or_default();
 			},
 			TaskInput::UpdateSubTask(index, sub_task) => {
 				self
@@ -687,20 +1327,349 @@ impl AsyncFactoryComponent for TaskModel {
 							*x = sub_task.clone()
 						}
 					});
+				sender
+					.output_sender()
+					.send(TaskOutput::UpdateSubTask(self.task.clone(), sub_task))
+					.unwrap_or_default();
 			},
 			TaskInput::RemoveSubTask(index) => {
-				self.task.sub_tasks.remove(index.current_index());
+				let sub_task = self.task.sub_tasks.remove(index.current_index());
 				self
 					.sub_tasks
 					.guard()
 					.remove(index.current_index())
 					.unwrap();
+				sender
+					.output_sender()
+					.send(TaskOutput::RemoveSubTask(self.task.clone(), sub_task.id))
+					.unwrap_or_default();
+			},
+			TaskInput::SetEstimate(minutes) => {
+				self.task.estimated_minutes = (minutes > 0).then_some(minutes);
+			},
+			TaskInput::ToggleTimer => {
+				if self.task.is_tracking() {
+					self.task.stop_timer();
+					self.stop_ticking();
+				} else {
+					self.task.start_timer();
+					self.start_ticking(&sender);
+				}
+			},
+			TaskInput::Tick => {},
+			TaskInput::AddAttachment => {
+				let dialog = gtk::FileDialog::builder()
+					.title(fl!("add-attachment"))
+					.modal(true)
+					.build();
+				let window = relm4::main_adw_application().active_window();
+				if let Ok(file) = dialog.open_future(window.as_ref()).await {
+					match load_attachment(&self.task.id, &file).await {
+						Ok((attachment, content)) => sender
+							.output_sender()
+							.send(TaskOutput::CreateAttachment(
+								self.index.clone(),
+								self.task.clone(),
+								attachment,
+								content,
+							))
+							.unwrap_or_default(),
+						Err(err) => tracing::error!("An error ocurred: {err}"),
+					}
+				}
+			},
+			TaskInput::AttachmentAdded(attachment) => {
+				self.task.attachments.push(attachment.clone());
+				self.attachments.guard().push_back(attachment);
+			},
+			TaskInput::OpenAttachment(index) => {
+				if let Some(attachment) =
+					self.task.attachments.get(index.current_index())
+				{
+					sender
+						.output_sender()
+						.send(TaskOutput::OpenAttachment(
+							self.task.clone(),
+							attachment.clone(),
+						))
+						.unwrap_or_default();
+				}
+			},
+			TaskInput::RemoveAttachment(index) => {
+				let attachment = self.task.attachments.remove(index.current_index());
+				self.attachments.guard().remove(index.current_index());
+				sender
+					.output_sender()
+					.send(TaskOutput::RemoveAttachment(
+						self.task.clone(),
+						attachment.id,
+					))
+					.unwrap_or_default();
+			},
+			TaskInput::AddLink(web_url) => {
+				let web_url = web_url.trim();
+				if !web_url.is_empty() {
+					let web_url = if web_url.contains(':') {
+						web_url.to_string()
+					} else {
+						format!("https://{web_url}")
+					};
+					let linked_resource = LinkedResource::new(&web_url, None);
+					self.task.linked_resources.push(linked_resource.clone());
+					self.linked_resources.guard().push_back(linked_resource);
+				}
+			},
+			TaskInput::OpenLink(index) => {
+				if let Some(linked_resource) =
+					self.task.linked_resources.get(index.current_index())
+				{
+					if let Err(err) = open::that(&linked_resource.web_url) {
+						tracing::error!("An error ocurred: {err}");
+					}
+				}
+			},
+			TaskInput::RemoveLink(index) => {
+				self.task.linked_resources.remove(index.current_index());
+				self.linked_resources.guard().remove(index.current_index());
 			},
+			TaskInput::ChooseDependency => {
+				sender
+					.output_sender()
+					.send(TaskOutput::ChooseDependency(
+						self.index.clone(),
+						self.task.clone(),
+					))
+					.unwrap_or_default();
+			},
+			TaskInput::SetDependencyCandidates(candidates) => {
+				let mut names = vec![fl!("choose-dependency").as_str()];
+				names
+					.extend(candidates.iter().map(|candidate| candidate.title.as_str()));
+				self
+					.candidate_names
+					.splice(0, self.candidate_names.n_items(), &names);
+				self.candidates = candidates;
+			},
+			TaskInput::AddDependency(selected) => {
+				if let Some(candidate) = self.candidates.get(selected as usize - 1) {
+					sender
+						.output_sender()
+						.send(TaskOutput::AddDependency(
+							self.index.clone(),
+							self.task.clone(),
+							candidate.task_id.clone(),
+						))
+						.unwrap_or_default();
+				}
+				self.candidates.clear();
+				self
+					.candidate_names
+					.splice(0, self.candidate_names.n_items(), &[]);
+			},
+			TaskInput::DependencyAdded(dependency) => {
+				self.task.blocked_by.push(dependency.clone());
+				self.dependencies.guard().push_back(dependency);
+			},
+			TaskInput::RemoveDependency(index) => {
+				let dependency = self.task.blocked_by.remove(index.current_index());
+				self.dependencies.guard().remove(index.current_index());
+				sender
+					.output_sender()
+					.send(TaskOutput::RemoveDependency(
+						self.task.clone(),
+						dependency.task_id,
+					))
+					.unwrap_or_default();
+			},
+			TaskInput::SetDependencyCompleted(task_id, completed) => {
+				if let Some(position) = self
+					.task
+					.blocked_by
+					.iter()
+					.position(|dependency| dependency.task_id == task_id)
+				{
+					self.task.blocked_by[position].completed = completed;
+					self
+						.dependencies
+						.send(position, DependencyInput::SetCompleted(completed));
+				}
+			},
+			TaskInput::LoadHistory => {
+				sender
+					.output_sender()
+					.send(TaskOutput::LoadHistory(
+						self.index.clone(),
+						self.task.clone(),
+					))
+					.unwrap_or_default();
+			},
+			TaskInput::SetHistory(changes) => {
+				let mut history = self.history.guard();
+				history.clear();
+				for change in merge_changes(changes) {
+					history.push_back(change);
+				}
+			},
+		}
+		if !sends_own_output {
+			sender
+				.output_sender()
+				.send(TaskOutput::UpdateTask(previous, self.task.clone()))
+				.unwrap_or_default();
 		}
-		sender
-			.output_sender()
-			.send(TaskOutput::UpdateTask(self.task.clone()))
-			.unwrap_or_default();
 		self.update_view(widgets, sender);
 	}
 }
+
+impl TaskModel {
+	/// The notes rendered for reading, or a hint when there are none.
+	fn notes_markup(&self) -> String {
+		match self.task.notes.as_deref() {
+			Some(notes) => notes::markdown_to_markup(notes),
+			None => format!("<i>{}</i>", fl!("no-notes")),
+		}
+	}
+
+	/// Refreshes the tracked time shown while the timer runs.
+	fn start_ticking(&mut self, sender: &AsyncFactorySender<Self>) {
+		let sender = sender.clone();
+		self.stop_ticking();
+		self.timer = Some(glib::timeout_add_seconds_local(30, move || {
+			if sender.input_sender().send(TaskInput::Tick).is_ok() {
+				glib::ControlFlow::Continue
+			} else {
+				glib::ControlFlow::Break
+			}
+		}));
+	}
+
+	fn stop_ticking(&mut self) {
+		if let Some(timer) = self.timer.take() {
+			timer.remove();
+		}
+	}
+}
+
+/// Formats a duration as hours and minutes, e.g. `1h 05m`.
+pub(crate) fn format_duration(duration: Duration) -> String {
+	let minutes = duration.num_minutes().max(0);
+	format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+fn status_position(status: Status) -> u32 {
+	match status {
+		Status::NotStarted => 0,
+		Status::InProgress => 1,
+		Status::Waiting => 2,
+		Status::Deferred => 3,
+		Status::Completed => 4,
+	}
+}
+
+fn status_from_position(position: u32) -> Status {
+	match position {
+		1 => Status::InProgress,
+		2 => Status::Waiting,
+		3 => Status::Deferred,
+		4 => Status::Completed,
+		_ => Status::NotStarted,
+	}
+}
+
+fn frequency_position(recurrence: Option<&Recurrence>) -> u32 {
+	match recurrence.map(|recurrence| recurrence.frequency) {
+		None => 0,
+		Some(Frequency::Daily) => 1,
+		Some(Frequency::Weekly) => 2,
+		Some(Frequency::AbsoluteMonthly) => 3,
+		Some(Frequency::RelativeMonthly) => 4,
+		Some(Frequency::AbsoluteYearly) => 5,
+		Some(Frequency::RelativeYearly) => 6,
+	}
+}
+
+fn frequency_from_position(position: u32) -> Option<Frequency> {
+	match position {
+		1 => Some(Frequency::Daily),
+		2 => Some(Frequency::Weekly),
+		3 => Some(Frequency::AbsoluteMonthly),
+		4 => Some(Frequency::RelativeMonthly),
+		5 => Some(Frequency::AbsoluteYearly),
+		6 => Some(Frequency::RelativeYearly),
+		_ => None,
+	}
+}
+
+fn week_index_from_position(position: u32) -> WeekIndex {
+	match position {
+		1 => WeekIndex::Second,
+		2 => WeekIndex::Third,
+		3 => WeekIndex::Fourth,
+		4 => WeekIndex::Last,
+		_ => WeekIndex::First,
+	}
+}
+
+/// Ranges that end on a date start with `end_date` as their last day.
+fn range_type_from_position(position: u32, end_date: NaiveDate) -> RangeType {
+	match position {
+		1 => RangeType::EndDate(end_date),
+		2 => RangeType::Numbered,
+		_ => RangeType::NoEnd,
+	}
+}
+
+fn range_type_position(range_type: RangeType) -> u32 {
+	match range_type {
+		RangeType::NoEnd => 0,
+		RangeType::EndDate(_) => 1,
+		RangeType::Numbered => 2,
+	}
+}
+
+fn parse_tags(text: &str) -> Vec<String> {
+	let mut tags: Vec<String> = vec![];
+	for tag in text.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+		if !tags.iter().any(|existing| existing == tag) {
+			tags.push(tag.to_string());
+		}
+	}
+	tags
+}
+
+async fn load_attachment(
+	task_id: &str,
+	file: &gio::File,
+) -> Result<(Attachment, Vec<u8>), glib::Error> {
+	let info = file
+		.query_info_future(
+			"standard::display-name,standard::content-type",
+			gio::FileQueryInfoFlags::NONE,
+			glib::Priority::DEFAULT,
+		)
+		.await?;
+	let content_type = info
+		.content_type()
+		.and_then(|content_type| gio::content_type_get_mime_type(&content_type))
+		.map(|mime_type| mime_type.to_string())
+		.unwrap_or(String::from("application/octet-stream"));
+	let (content, _) = file.load_contents_future().await?;
+	let attachment = Attachment::new(
+		task_id,
+		info.display_name().as_str(),
+		&content_type,
+		content.len() as i64,
+	);
+	Ok((attachment, content.to_vec()))
+}
+
+/// The start of the day selected in a calendar, in the user's time zone.
+fn calendar_date(calendar: &gtk::Calendar) -> Option<DateTime<Utc>> {
+	let date = calendar.date();
+	NaiveDate::from_ymd_opt(
+		date.year(),
+		date.month() as u32,
+		date.day_of_month() as u32,
+	)
+	.map(time_zone::start_of_day)
+}
@@ -0,0 +1,178 @@
+use adw::prelude::{ActionRowExt, PreferencesRowExt};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use core_done::{
+	models::{
+		priority::Priority,
+		status::Status,
+		task_change::{TaskChange, TaskField},
+	},
+	time_zone,
+};
+use relm4::{
+	adw,
+	factory::FactoryView,
+	gtk,
+	gtk::prelude::WidgetExt,
+	prelude::{DynamicIndex, FactoryComponent},
+	FactorySender,
+};
+
+use crate::{app::factories::task::format_duration, fl};
+
+/// The title and the notes are saved as they are typed, edits closer than
+/// this are shown as one.
+const MERGE_WINDOW_SECONDS: i64 = 60;
+
+#[derive(Debug)]
+pub struct TaskChangeModel {
+	pub change: TaskChange,
+}
+
+#[relm4::factory(pub)]
+impl FactoryComponent for TaskChangeModel {
+	type ParentWidget = adw::PreferencesGroup;
+	type Input = ();
+	type Output = ();
+	type Init = TaskChange;
+	type CommandOutput = ();
+
+	view! {
+		#[root]
+		adw::ActionRow {
+			set_title: &self.title(),
+			set_subtitle: &self.description(),
+			set_subtitle_lines: 2,
+			add_suffix = &gtk::Label {
+				add_css_class: "dim-label",
+				set_text: &time_zone::to_local(self.change.changed_date_time)
+					.format("%d/%m/%Y %H:%M")
+					.to_string(),
+			},
+		}
+	}
+
+	fn init_model(
+		change: Self::Init,
+		_index: &DynamicIndex,
+		_sender: FactorySender<Self>,
+	) -> Self {
+		Self { change }
+	}
+
+	fn init_widgets(
+		&mut self,
+		_index: &DynamicIndex,
+		root: &Self::Root,
+		_returned_widget: &<Self::ParentWidget as FactoryView>::ReturnedWidget,
+		_sender: FactorySender<Self>,
+	) -> Self::Widgets {
+		let widgets = view_output!();
+		widgets
+	}
+}
+
+impl TaskChangeModel {
+	fn title(&self) -> String {
+		match self.change.field {
+			TaskField::Title => fl!("title"),
+			TaskField::Notes => fl!("notes"),
+			TaskField::Status => fl!("status"),
+			TaskField::Priority => fl!("importance"),
+			TaskField::Favorite => fl!("favorite-task"),
+			TaskField::Today => fl!("today"),
+			TaskField::DueDate => fl!("due-date"),
+			TaskField::ReminderDate => fl!("reminder"),
+			TaskField::StartDate => fl!("start-date"),
+			TaskField::Recurrence => fl!("recurrence"),
+			TaskField::Parent => fl!("moved-to-list"),
+			TaskField::DeletionDate => {
+				if self.change.new_value.is_some() {
+					fl!("moved-to-trash")
+				} else {
+					fl!("restored-from-trash")
+				}
+			},
+			TaskField::EstimatedMinutes => fl!("estimate"),
+		}
+		.to_string()
+	}
+
+	/// The values before and after the change, for the fields where they
+	/// mean something to the user.
+	fn description(&self) -> String {
+		match self.change.field {
+			TaskField::Parent | TaskField::DeletionDate => String::new(),
+			_ => format!(
+				"{} → {}",
+				self.format_value(self.change.old_value.as_deref()),
+				self.format_value(self.change.new_value.as_deref())
+			),
+		}
+	}
+
+	fn format_value(&self, value: Option<&str>) -> String {
+		let Some(value) = value.filter(|value| !value.is_empty()) else {
+			return fl!("none").to_string();
+		};
+		let number = value.parse::<i64>().unwrap_or_default();
+		match self.change.field {
+			TaskField::Status => match Status::from(number as i32) {
+				Status::NotStarted => fl!("not-started"),
+				Status::Completed => fl!("completed"),
+				Status::InProgress => fl!("in-progress"),
+				Status::Waiting => fl!("waiting"),
+				Status::Deferred => fl!("deferred"),
+			}
+			.to_string(),
+			TaskField::Priority => match Priority::from(number as i32) {
+				Priority::Low => fl!("low"),
+				Priority::Normal => fl!("medium"),
+				Priority::High => fl!("high"),
+			}
+			.to_string(),
+			TaskField::Favorite | TaskField::Today => {
+				if number == 1 { fl!("yes") } else { fl!("no") }.to_string()
+			},
+			TaskField::DueDate | TaskField::ReminderDate | TaskField::StartDate => {
+				NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+					.map(|date| {
+						time_zone::to_local(date.and_utc())
+							.format("%d/%m/%Y %H:%M")
+							.to_string()
+					})
+					.unwrap_or(value.to_string())
+			},
+			TaskField::EstimatedMinutes => {
+				format_duration(chrono::Duration::minutes(number))
+			},
+			TaskField::Recurrence => fl!("repeat").to_string(),
+			TaskField::Title
+			| TaskField::Notes
+			| TaskField::Parent
+			| TaskField::DeletionDate => value.to_string(),
+		}
+	}
+}
+
+/// Joins runs of edits to the title or the notes, given newest first,
+/// keeping the oldest value and the newest one.
+pub(crate) fn merge_changes(changes: Vec<TaskChange>) -> Vec<TaskChange> {
+	let mut merged: Vec<TaskChange> = vec![];
+	let mut previous: Option<DateTime<Utc>> = None;
+	for change in changes {
+		let joins = merged.last().is_some_and(|last| {
+			last.field == change.field
+				&& matches!(change.field, TaskField::Title | TaskField::Notes)
+				&& previous.is_some_and(|previous| {
+					(previous - change.changed_date_time).num_seconds()
+						< MERGE_WINDOW_SECONDS
+				})
+		});
+		previous = Some(change.changed_date_time);
+		match merged.last_mut() {
+			Some(last) if joins => last.old_value = change.old_value,
+			_ => merged.push(change),
+		}
+	}
+	merged
+}
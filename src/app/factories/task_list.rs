@@ -1,3 +1,5 @@
+use core_done::models::group::Group;
+use core_done::models::list_settings::ListSettings;
 use core_done::service::Service;
 use relm4::actions::{ActionGroupName, RelmAction, RelmActionGroup};
 use relm4::factory::AsyncFactoryComponent;
@@ -13,6 +15,9 @@ use relm4::{
 use crate::app::components::delete::{
 	DeleteComponent, DeleteInit, DeleteOutput,
 };
+use crate::app::components::group_dialog::{
+	GroupDialogComponent, GroupDialogInput, GroupDialogOutput,
+};
 use crate::app::components::list_dialog::{
 	ListDialogComponent, ListDialogOutput,
 };
@@ -24,8 +29,9 @@ pub struct TaskListFactoryModel {
 	pub service: Service,
 	pub index: DynamicIndex,
 	pub list: SidebarList,
-	pub rename: Controller<ListDialogComponent>,
+	pub edit: Controller<ListDialogComponent>,
 	pub delete: Controller<DeleteComponent>,
+	pub group_dialog: Controller<GroupDialogComponent>,
 }
 
 #[derive(Debug, derive_new::new)]
@@ -38,19 +44,25 @@ pub struct TaskListFactoryInit {
 pub enum TaskListFactoryInput {
 	Select,
 	Delete,
-	RenameList(String),
+	UpdateList(String, String, ListSettings),
 	ChangeIcon(String),
+	ChooseGroup,
+	MoveToGroup(Option<String>),
+	MoveToNewGroup(String),
 }
 
 #[derive(Debug)]
 pub enum TaskListFactoryOutput {
 	Select(SidebarList),
 	DeleteTaskList(DynamicIndex),
+	GroupChanged,
+	ListUpdated(SidebarList),
 }
 
 relm4::new_action_group!(pub(super) TaskListActionGroup, "win");
-relm4::new_stateless_action!(RenameAction, TaskListActionGroup, "rename");
+relm4::new_stateless_action!(EditAction, TaskListActionGroup, "edit");
 relm4::new_stateless_action!(DeleteAction, TaskListActionGroup, "delete");
+relm4::new_stateless_action!(MoveAction, TaskListActionGroup, "move");
 
 #[relm4::factory(pub async)]
 impl AsyncFactoryComponent for TaskListFactoryModel {
@@ -64,7 +76,8 @@ impl AsyncFactoryComponent for TaskListFactoryModel {
 	menu! {
 		primary_menu: {
 			section! {
-				"Rename" => RenameAction,
+				"Edit" => EditAction,
+				"Move to group" => MoveAction,
 				"Delete" => DeleteAction,
 			}
 		}
@@ -120,6 +133,17 @@ impl AsyncFactoryComponent for TaskListFactoryModel {
 						set_text: self.list.name().as_str(),
 						set_margin_all: 5,
 					},
+					gtk::Image {
+						#[watch]
+						set_visible: self.list.shared(),
+						set_icon_name: Some("emblem-shared-symbolic"),
+						#[watch]
+						set_tooltip: if self.list.owned() {
+							fl!("shared-list")
+						} else {
+							fl!("shared-with-you")
+						},
+					},
 					#[name(list_actions)]
 					gtk::MenuButton {
 						#[watch]
@@ -139,29 +163,48 @@ impl AsyncFactoryComponent for TaskListFactoryModel {
 		index: &DynamicIndex,
 		sender: AsyncFactorySender<Self>,
 	) -> Self {
-		let rename = ListDialogComponent::builder()
-			.launch(Some(init.list.name()))
+		let edit = ListDialogComponent::builder()
+			.launch(match &init.list {
+				SidebarList::Custom(list) => Some(list.clone()),
+				_ => None,
+			})
 			.forward(sender.input_sender(), |message| match message {
 				ListDialogOutput::AddTaskListToSidebar(_) => {
 					TaskListFactoryInput::Select
 				},
-				ListDialogOutput::RenameList(name) => {
-					TaskListFactoryInput::RenameList(name)
+				ListDialogOutput::UpdateList(name, description, settings) => {
+					TaskListFactoryInput::UpdateList(name, description, settings)
 				},
 			});
 		let delete = DeleteComponent::builder()
 			.launch(DeleteInit {
 				warning: "You're about to delete this list".into(),
-				delete_warning: "If you do this, all of its tasks will be lost.".into(),
+				delete_warning: if init.service == Service::Computer {
+					"The list and its tasks will be moved to the trash.".into()
+				} else {
+					"If you do this, all of its tasks will be lost.".into()
+				},
 			})
 			.forward(sender.input_sender(), |message| match message {
 				DeleteOutput::Delete => TaskListFactoryInput::Delete,
 			});
+		let group_dialog = GroupDialogComponent::builder().launch(()).forward(
+			sender.input_sender(),
+			|message| match message {
+				GroupDialogOutput::MoveToGroup(group) => {
+					TaskListFactoryInput::MoveToGroup(group)
+				},
+				GroupDialogOutput::MoveToNewGroup(name) => {
+					TaskListFactoryInput::MoveToNewGroup(name)
+				},
+			},
+		);
 		TaskListFactoryModel {
 			service: init.service,
 			index: index.clone(),
-			rename,
+			edit,
 			delete,
+			group_dialog,
 			list: init.list,
 		}
 	}
@@ -177,11 +220,9 @@ impl AsyncFactoryComponent for TaskListFactoryModel {
 
 		let mut actions = RelmActionGroup::<TaskListActionGroup>::new();
 
-		let rename_action = {
-			let rename_widget = self.rename.widget().clone();
-			RelmAction::<RenameAction>::new_stateless(move |_| {
-				rename_widget.present()
-			})
+		let edit_action = {
+			let edit_widget = self.edit.widget().clone();
+			RelmAction::<EditAction>::new_stateless(move |_| edit_widget.present())
 		};
 
 		let delete_action = {
@@ -190,9 +231,18 @@ impl AsyncFactoryComponent for TaskListFactoryModel {
 				delete_widget.present()
 			})
 		};
+		delete_action.set_enabled(self.list.owned());
+
+		let move_action = {
+			let sender = sender.clone();
+			RelmAction::<MoveAction>::new_stateless(move |_| {
+				sender.input(TaskListFactoryInput::ChooseGroup)
+			})
+		};
 
-		actions.add_action(rename_action);
+		actions.add_action(edit_action);
 		actions.add_action(delete_action);
+		actions.add_action(move_action);
 
 		widgets.list_actions.insert_action_group(
 			TaskListActionGroup::NAME,
@@ -213,13 +263,20 @@ impl AsyncFactoryComponent for TaskListFactoryModel {
 					.output(TaskListFactoryOutput::Select(self.list.clone()))
 					.unwrap_or_default();
 			},
-			TaskListFactoryInput::RenameList(name) => {
+			TaskListFactoryInput::UpdateList(name, description, settings) => {
 				if let SidebarList::Custom(list) = &self.list {
-					let mut renamed_list = list.clone();
-					renamed_list.name = name.clone();
+					let mut list = list.clone();
+					list.name = name;
+					list.description = description;
+					list.settings = settings;
 					let mut service = self.service.get_service();
-					match service.update_list(renamed_list.clone()).await {
-						Ok(_) => self.list = SidebarList::Custom(renamed_list),
+					match service.update_list(list.clone()).await {
+						Ok(_) => {
+							self.list = SidebarList::Custom(list);
+							sender
+								.output(TaskListFactoryOutput::ListUpdated(self.list.clone()))
+								.unwrap_or_default();
+						},
 						Err(err) => {
 							tracing::error!("{err}");
 						},
@@ -243,6 +300,48 @@ impl AsyncFactoryComponent for TaskListFactoryModel {
 					}
 				}
 			},
+			TaskListFactoryInput::ChooseGroup => {
+				if let SidebarList::Custom(list) = &self.list {
+					let mut service = self.service.get_service();
+					match service.read_groups().await {
+						Ok(groups) => self
+							.group_dialog
+							.emit(GroupDialogInput::Show(groups, list.group.clone())),
+						Err(err) => {
+							tracing::error!("{err}");
+						},
+					}
+				}
+			},
+			TaskListFactoryInput::MoveToGroup(group) => {
+				if let SidebarList::Custom(list) = &self.list {
+					let mut list = list.clone();
+					list.group = group;
+					let mut service = self.service.get_service();
+					match service.update_list(list.clone()).await {
+						Ok(_) => {
+							self.list = SidebarList::Custom(list);
+							sender
+								.output(TaskListFactoryOutput::GroupChanged)
+								.unwrap_or_default();
+						},
+						Err(err) => {
+							tracing::error!("{err}");
+						},
+					}
+				}
+			},
+			TaskListFactoryInput::MoveToNewGroup(name) => {
+				let mut service = self.service.get_service();
+				match service.create_group(Group::new(&name, self.service)).await {
+					Ok(group) => {
+						sender.input(TaskListFactoryInput::MoveToGroup(Some(group.id)))
+					},
+					Err(err) => {
+						tracing::error!("{err}");
+					},
+				}
+			},
 			TaskListFactoryInput::ChangeIcon(icon) => {
 				if let SidebarList::Custom(list) = &self.list {
 					let mut list = list.clone();
@@ -0,0 +1,85 @@
+use adw::prelude::{ActionRowExt, PreferencesRowExt};
+use core_done::models::list::List;
+use relm4::{
+	adw,
+	factory::FactoryView,
+	gtk,
+	gtk::prelude::{ButtonExt, WidgetExt},
+	prelude::{DynamicIndex, FactoryComponent},
+	FactorySender, RelmWidgetExt,
+};
+use relm4_icons::icon_name;
+
+use crate::fl;
+
+#[derive(Debug)]
+pub struct TrashListModel {
+	pub list: List,
+}
+
+#[derive(Debug)]
+pub enum TrashListOutput {
+	Restore(DynamicIndex),
+	Delete(DynamicIndex),
+}
+
+#[relm4::factory(pub)]
+impl FactoryComponent for TrashListModel {
+	type ParentWidget = adw::PreferencesGroup;
+	type Input = ();
+	type Output = TrashListOutput;
+	type Init = List;
+	type CommandOutput = ();
+
+	view! {
+		#[root]
+		adw::ActionRow {
+			set_title: self.list.name.as_str(),
+			set_subtitle: &self
+				.list
+				.deletion_date
+				.map(|date| date.format("%d/%m/%Y").to_string())
+				.unwrap_or_default(),
+			add_prefix = &gtk::Label {
+				set_text: self.list.icon.as_deref().unwrap_or_default(),
+			},
+			add_suffix = &gtk::Button {
+				add_css_class: "flat",
+				set_valign: gtk::Align::Center,
+				set_icon_name: icon_name::UPDATE,
+				set_tooltip: fl!("restore"),
+				connect_clicked[sender, index] => move |_| {
+					sender.output(TrashListOutput::Restore(index.clone())).unwrap_or_default();
+				}
+			},
+			add_suffix = &gtk::Button {
+				set_css_classes: &["destructive-action", "circular"],
+				set_valign: gtk::Align::Center,
+				set_icon_name: icon_name::X_CIRCULAR,
+				set_tooltip: fl!("delete-forever"),
+				connect_clicked[sender, index] => move |_| {
+					sender.output(TrashListOutput::Delete(index.clone())).unwrap_or_default();
+				}
+			},
+		}
+	}
+
+	fn init_model(
+		list: Self::Init,
+		_index: &DynamicIndex,
+		_sender: FactorySender<Self>,
+	) -> Self {
+		Self { list }
+	}
+
+	fn init_widgets(
+		&mut self,
+		index: &DynamicIndex,
+		root: &Self::Root,
+		_returned_widget: &<Self::ParentWidget as FactoryView>::ReturnedWidget,
+		sender: FactorySender<Self>,
+	) -> Self::Widgets {
+		let widgets = view_output!();
+		widgets
+	}
+}
@@ -1,4 +1,4 @@
-use core_done::models::list::List;
+use core_done::models::{list::List, tag::Tag};
 use relm4_icons::icon_name;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
@@ -11,8 +11,11 @@ pub enum SidebarList {
 	Today,
 	Starred,
 	Next7Days,
+	Actionable,
 	Done,
+	Trash,
 	Custom(List),
+	Tag(Tag),
 }
 
 impl Default for SidebarList {
@@ -23,9 +26,14 @@ impl Default for SidebarList {
 
 impl SidebarList {
 	pub fn list() -> Vec<SidebarList> {
-		let mut list: Vec<SidebarList> = SidebarList::iter().collect();
-		list.pop();
-		list
+		SidebarList::iter()
+			.filter(|list| {
+				!matches!(
+					list,
+					SidebarList::Trash | SidebarList::Custom(_) | SidebarList::Tag(_)
+				)
+			})
+			.collect()
 	}
 
 	pub fn name(&self) -> String {
@@ -33,14 +41,19 @@ impl SidebarList {
 		let today: &String = fl!("today");
 		let starred: &String = fl!("starred");
 		let next_7_days: &String = fl!("next-7-days");
+		let actionable: &String = fl!("actionable");
 		let completed_list: &String = fl!("completed-list");
+		let trash: &String = fl!("trash");
 		match self {
 			SidebarList::All => all.clone(),
 			SidebarList::Today => today.clone(),
 			SidebarList::Starred => starred.clone(),
 			SidebarList::Next7Days => next_7_days.clone(),
+			SidebarList::Actionable => actionable.clone(),
 			SidebarList::Done => completed_list.clone(),
+			SidebarList::Trash => trash.clone(),
 			SidebarList::Custom(list) => list.name.clone(),
+			SidebarList::Tag(tag) => tag.name.clone(),
 		}
 	}
 
@@ -49,14 +62,20 @@ impl SidebarList {
 		let today_desc: &String = fl!("today-desc");
 		let starred_desc: &String = fl!("starred-desc");
 		let next_7_days_desc: &String = fl!("next-7-days-desc");
+		let actionable_desc: &String = fl!("actionable-desc");
 		let completed_list_desc: &String = fl!("completed-list-desc");
+		let trash_desc: &String = fl!("trash-desc");
+		let tag_desc: &String = fl!("tag-desc");
 		match self {
 			SidebarList::All => all_desc.clone(),
 			SidebarList::Today => today_desc.clone(),
 			SidebarList::Starred => starred_desc.clone(),
 			SidebarList::Next7Days => next_7_days_desc.clone(),
+			SidebarList::Actionable => actionable_desc.clone(),
 			SidebarList::Done => completed_list_desc.clone(),
+			SidebarList::Trash => trash_desc.clone(),
 			SidebarList::Custom(list) => list.description.clone(),
+			SidebarList::Tag(_) => tag_desc.clone(),
 		}
 	}
 
@@ -66,12 +85,25 @@ impl SidebarList {
 			SidebarList::Today => Some(icon_name::IMAGE_ADJUST_BRIGHTNESS),
 			SidebarList::Starred => Some(icon_name::STAR_FILLED_ROUNDED),
 			SidebarList::Next7Days => Some(icon_name::WORK_WEEK),
+			SidebarList::Actionable => Some("media-playback-start-symbolic"),
 			SidebarList::Done => Some(icon_name::CHECK_ROUND_OUTLINE_WHOLE),
+			SidebarList::Trash => Some(icon_name::USER_TRASH),
 			SidebarList::Custom(list) => list.icon.as_deref(),
+			SidebarList::Tag(_) => Some(icon_name::TAG),
 		}
 	}
 
 	pub fn smart(&self) -> bool {
 		!matches!(self, SidebarList::Custom(_))
 	}
+
+	pub fn shared(&self) -> bool {
+		matches!(self, SidebarList::Custom(list) if list.is_shared)
+	}
+
+	/// Whether the user can rename and delete the list, smart lists are
+	/// never touched.
+	pub fn owned(&self) -> bool {
+		matches!(self, SidebarList::Custom(list) if list.is_owner)
+	}
 }
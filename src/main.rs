@@ -1,7 +1,9 @@
 use anyhow::Result;
-use app::config::{info::APP_ID, setup};
-use relm4::RelmApp;
+use app::config::{info::APP_ID, profiles, setup};
+use glib::{Char, OptionArg, OptionFlags};
+use relm4::{gtk::prelude::ApplicationExt, RelmApp};
 
+use crate::fl;
 use app::Done;
 
 mod app;
@@ -9,6 +11,30 @@ mod app;
 fn main() -> Result<()> {
 	let app = RelmApp::new(APP_ID);
 	setup::init()?;
+	register_profile_option();
 	app.run_async::<Done>(());
 	Ok(())
 }
+
+/// Lets the app be started with `--profile <name>` to open a profile for
+/// this run only. GApplication rejects options it doesn't know about, so it
+/// has to be registered and is read once the command line is parsed.
+fn register_profile_option() {
+	let application = relm4::main_application();
+	application.add_main_option(
+		"profile",
+		Char::from(b'p'),
+		OptionFlags::NONE,
+		OptionArg::String,
+		fl!("profile-option"),
+		Some("NAME"),
+	);
+	application.connect_handle_local_options(|_, options| {
+		let profile = options.lookup::<String>("profile").ok().flatten();
+		if let Err(err) = profiles::activate(profile.as_deref()) {
+			tracing::error!("Failed to open the profile: {err}");
+		}
+		// Carries on with the default handling of the command line.
+		-1
+	});
+}